@@ -0,0 +1,100 @@
+//! Serves the same routes as the Lambda function from a long-running HTTP server, for hosting
+//! outside AWS Lambda. Configured through the environment:
+//!
+//! ```text
+//! SERVER_ADDRESS          address to listen on, default 0.0.0.0:8080
+//! REPOSITORY_BACKEND      dynamodb (default) or memory
+//! MODEL_PORTFOLIOS_PATH   model portfolios for the memory backend
+//! ADVISERS_PATH           adviser profiles for the memory backend
+//! AUTH_CLAIMS_HEADER      header carrying the caller's claims as JSON, default x-authenticated-claims
+//! ```
+//!
+//! The document generator and report configuration are chosen by the same variables as the
+//! Lambda function.
+
+use std::sync::Arc;
+
+use http::header::HeaderName;
+use lambda_http::Error;
+use tokio::net::TcpListener;
+use tracing_subscriber::{fmt, EnvFilter};
+
+use gcwm_report_gen::domain::generation_job::GenerationJob;
+use gcwm_report_gen::domain::report::{adviser_profile::AdviserProfile, investment_holdings::InvestmentPortfolio};
+use gcwm_report_gen::driven::config::{load_report_config, load_stored_wording_templates, WORDING_TEMPLATES_VERSION_ENV};
+use gcwm_report_gen::driven::doc_generator::document_generator_from_env;
+use gcwm_report_gen::driven::repository::adviser_dynamo_db::AdviserDynamoDbRepo;
+use gcwm_report_gen::driven::repository::dynamo_db::InvestmentPortfolioDynamoDbRepo;
+use gcwm_report_gen::driven::repository::generation_job_dynamo_db::GenerationJobDynamoDbRepo;
+use gcwm_report_gen::driven::repository::in_memory::{InMemoryAdviserRepo, InMemoryGenerationJobRepo, InMemoryInvestmentPortfolioRepo};
+use gcwm_report_gen::driven::repository::wording_templates_dynamo_db::WordingTemplatesDynamoDbRepo;
+use gcwm_report_gen::driven::repository::{AdviserRepository, GenerationJobRepository, InvestmentPortfoliosRepository};
+use gcwm_report_gen::driving::cors::CorsConfig;
+use gcwm_report_gen::driving::server::{Server, AUTH_CLAIMS_HEADER_ENV, DEFAULT_AUTH_CLAIMS_HEADER};
+
+const DEFAULT_SERVER_ADDRESS: &str = "0.0.0.0:8080";
+
+fn read_env_file(variable: &str) -> Result<Option<String>, Error> {
+    match std::env::var(variable) {
+        Ok(path) => Ok(Some(std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))?)),
+        Err(_) => Ok(None)
+    }
+}
+
+async fn serve<R, A, J>(investment_portfolio_repo: R, adviser_repo: A, job_repo: J) -> Result<(), Error>
+where
+    R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Send + Sync + 'static + std::fmt::Debug,
+    A: AdviserRepository<AdviserProfile> + Send + Sync + 'static + std::fmt::Debug,
+    J: GenerationJobRepository<GenerationJob> + Send + Sync + 'static + std::fmt::Debug,
+{
+    let mut report_config = load_report_config()?;
+    if let Ok(version) = std::env::var(WORDING_TEMPLATES_VERSION_ENV) {
+        report_config.templates = load_stored_wording_templates(&WordingTemplatesDynamoDbRepo::new().await, version).await?;
+    }
+    let claims_header = std::env::var(AUTH_CLAIMS_HEADER_ENV).unwrap_or_else(|_| DEFAULT_AUTH_CLAIMS_HEADER.to_string());
+    let address = std::env::var("SERVER_ADDRESS").unwrap_or_else(|_| DEFAULT_SERVER_ADDRESS.to_string());
+
+    let server = Arc::new(Server {
+        investment_portfolio_repo: Arc::new(investment_portfolio_repo),
+        adviser_repo: Arc::new(adviser_repo),
+        job_repo: Arc::new(job_repo),
+        doc_gen: document_generator_from_env().await?,
+        report_config: Arc::new(report_config),
+        cors_config: Arc::new(CorsConfig::from_env()),
+        claims_header: HeaderName::try_from(claims_header)?,
+    });
+
+    server.serve(TcpListener::bind(&address).await?).await?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    dotenv::dotenv().ok();
+
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .json()
+        .with_span_events(fmt::format::FmtSpan::ENTER | fmt::format::FmtSpan::EXIT)
+        .init();
+
+    match std::env::var("REPOSITORY_BACKEND").as_deref() {
+        Ok("dynamodb") | Err(_) => serve(
+            InvestmentPortfolioDynamoDbRepo::new().await,
+            AdviserDynamoDbRepo::new().await,
+            GenerationJobDynamoDbRepo::new().await
+        ).await,
+        Ok("memory") => {
+            let portfolio_repo = match read_env_file("MODEL_PORTFOLIOS_PATH")? {
+                Some(json) => InMemoryInvestmentPortfolioRepo::from_json(&json)?,
+                None => InMemoryInvestmentPortfolioRepo::default()
+            };
+            let adviser_repo = match read_env_file("ADVISERS_PATH")? {
+                Some(json) => InMemoryAdviserRepo::from_json(&json)?,
+                None => InMemoryAdviserRepo::default()
+            };
+            serve(portfolio_repo, adviser_repo, InMemoryGenerationJobRepo::default()).await
+        }
+        Ok(other) => Err(format!("unknown REPOSITORY_BACKEND {}, expected dynamodb or memory", other).into())
+    }
+}
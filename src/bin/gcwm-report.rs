@@ -0,0 +1,158 @@
+//! Builds reports from a saved request body without deploying anything, using in-memory
+//! repositories and the in-process document generator.
+//!
+//! ```text
+//! gcwm-report validate request.json
+//! gcwm-report report request.json --portfolios portfolios.json > report.json
+//! gcwm-report render request.json --format pdf --format docx --out-dir out
+//! ```
+
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use gcwm_report_gen::domain::report::{create_report::create_report, Report};
+use gcwm_report_gen::driven::config::load_report_config;
+use gcwm_report_gen::driven::doc_generator::{local::LocalDocGenerator, DocumentGenerator, OutputFormat};
+use gcwm_report_gen::driven::repository::in_memory::{InMemoryAdviserRepo, InMemoryInvestmentPortfolioRepo};
+use gcwm_report_gen::driven::storage::{filesystem::FilesystemStorage, ObjectNaming};
+use gcwm_report_gen::driving::data_transfer_object::DataTransferObject;
+
+const USAGE: &str = "\
+Usage:
+  gcwm-report validate <request.json> [options]
+  gcwm-report report <request.json> [options]
+  gcwm-report render <request.json> [--format <format>]... [--out-dir <dir>] [options]
+
+Options:
+  --portfolios <file>  JSON array of model portfolios, see config/model_portfolios.example.json
+  --advisers <file>    JSON array of adviser profiles
+  --as-at <YYYY-MM-DD> Date to prepare the report as at, overriding the request's asAt
+
+Branding, contents and wording templates are read from the files named by
+FIRM_BRANDING_PATH, CONTENTS_CONFIG_PATH and WORDING_TEMPLATES_PATH, which may be set in .env.";
+
+enum Command {
+    Validate,
+    Report,
+    Render { formats: Vec<OutputFormat>, out_dir: String }
+}
+
+struct Args {
+    command: Command,
+    request: String,
+    portfolios: Option<String>,
+    advisers: Option<String>,
+    as_at: Option<NaiveDate>
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let command = args.next().ok_or("missing command")?;
+    let mut request = None;
+    let mut portfolios = None;
+    let mut advisers = None;
+    let mut as_at = None;
+    let mut formats = Vec::new();
+    let mut out_dir = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--portfolios" => portfolios = Some(value()?),
+            "--advisers" => advisers = Some(value()?),
+            "--as-at" => {
+                let date = value()?;
+                as_at = Some(NaiveDate::parse_from_str(&date, "%Y-%m-%d").map_err(|_| format!("invalid date {}", date))?);
+            }
+            "--format" if command == "render" => {
+                let format = value()?;
+                formats.push(serde_json::from_value(serde_json::Value::String(format.clone())).map_err(|_| format!("unknown format {}", format))?);
+            }
+            "--out-dir" if command == "render" => out_dir = Some(value()?),
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+            _ if request.is_none() => request = Some(arg),
+            _ => return Err(format!("unexpected argument {}", arg))
+        }
+    }
+
+    let command = match command.as_str() {
+        "validate" => Command::Validate,
+        "report" => Command::Report,
+        "render" => Command::Render {
+            formats: if formats.is_empty() { vec![OutputFormat::Docx] } else { formats },
+            out_dir: out_dir.unwrap_or_else(|| ".".to_string())
+        },
+        other => return Err(format!("unknown command {}", other))
+    };
+
+    Ok(Args { command, request: request.ok_or("missing request file")?, portfolios, advisers, as_at })
+}
+
+fn read(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))
+}
+
+async fn build_report(args: &Args) -> Result<Report, String> {
+    let mut request: DataTransferObject = serde_json::from_str(&read(&args.request)?)
+        .map_err(|e| format!("{}: invalid request: {}", args.request, e))?;
+
+    let portfolio_repo = match &args.portfolios {
+        Some(path) => InMemoryInvestmentPortfolioRepo::from_json(&read(path)?).map_err(|e| format!("{}: {}", path, e))?,
+        None => InMemoryInvestmentPortfolioRepo::default()
+    };
+    let adviser_repo = match &args.advisers {
+        Some(path) => InMemoryAdviserRepo::from_json(&read(path)?).map_err(|e| format!("{}: {}", path, e))?,
+        None => InMemoryAdviserRepo::default()
+    };
+    let report_config = load_report_config().map_err(|e| e.to_string())?;
+
+    if args.as_at.is_some() {
+        request.as_at = args.as_at;
+    }
+    let clock = request.clock();
+
+    create_report(request.report_type, Arc::new(portfolio_repo), Arc::new(adviser_repo), &report_config, clock.as_ref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+async fn run(args: Args) -> Result<(), String> {
+    let report = build_report(&args).await?;
+
+    match args.command {
+        Command::Validate => eprintln!("{}: valid", args.request),
+        Command::Report => println!("{}", serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?),
+        Command::Render { formats, out_dir } => {
+            let generator = LocalDocGenerator::new(FilesystemStorage::new(out_dir), ObjectNaming::default(), Duration::from_secs(3600));
+            let instructions = serde_json::to_value(report.instructions()).map_err(|e| e.to_string())?;
+            let links = generator.generate_all(&instructions, &formats).await.map_err(|e| e.to_string())?;
+            for (format, link) in links {
+                println!("{}: {}", format, link);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenv::dotenv().ok();
+
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("gcwm-report: {}\n\n{}", error, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("gcwm-report: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
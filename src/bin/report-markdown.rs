@@ -0,0 +1,42 @@
+//! Prints the Markdown rendition of a document instructions JSON file, or of stdin when no file
+//! is given.
+//!
+//! ```text
+//! report-markdown instructions.json > report.md
+//! ```
+
+use std::io::Read;
+use std::process::ExitCode;
+
+use gcwm_report_gen::driven::doc_generator::markdown::render_markdown;
+use serde_json::Value;
+
+fn main() -> ExitCode {
+    let input = match std::env::args().nth(1) {
+        Some(path) if path != "-" => std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e)),
+        _ => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input).map(|_| input).map_err(|e| e.to_string())
+        }
+    };
+
+    let rendered = input
+        .and_then(|input| serde_json::from_str::<Value>(&input).map_err(|e| format!("Invalid document instructions JSON: {}", e)))
+        // Accept API responses as well as bare instructions
+        .map(|json| match json.get("payload") {
+            Some(payload) if payload.is_object() => payload.clone(),
+            _ => json
+        })
+        .and_then(|instructions| render_markdown(&instructions).map_err(|e| e.to_string()));
+
+    match rendered {
+        Ok(markdown) => {
+            print!("{}", markdown);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("report-markdown: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
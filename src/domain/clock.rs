@@ -0,0 +1,27 @@
+use chrono::{NaiveDate, Utc};
+
+/// The date a report is prepared as at. Validation that depends on today's date reads it from
+/// here, so a stored request can be rebuilt as at its original date.
+pub trait Clock: Send + Sync {
+    fn today(&self) -> NaiveDate;
+}
+
+/// Reads today's date from the system clock, in UTC.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn today(&self) -> NaiveDate {
+        Utc::now().date_naive()
+    }
+}
+
+/// Always returns the same date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedClock(pub NaiveDate);
+
+impl Clock for FixedClock {
+    fn today(&self) -> NaiveDate {
+        self.0
+    }
+}
@@ -1,29 +1,77 @@
 use serde::{Deserialize, Serialize};
 
+use crate::domain::report::adviser_profile::AdviserProfile;
+
 use super::name_string::NameString;
-// use uuid::Uuid;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Adviser {
-    //#[serde(with = "uuid::serde::simple")]
-    //id: uuid::Uuid,
     pub adviser_first_name: NameString,
-    pub adviser_last_name: NameString
+    pub adviser_last_name: NameString,
+    pub profile: Option<AdviserProfile>
 }
 
 impl Adviser {
     pub fn new(
-        //id: Uuid,
         unvalidated_adviser_first_name: String,
-        unvalidated_adviser_last_name: String
+        unvalidated_adviser_last_name: String,
+        adviser_profile: Option<AdviserProfile>
     ) -> Result<Self, String> {
 
-        // TODO function to check adviser exists in database
-
         let adviser_first_name = NameString::try_from(unvalidated_adviser_first_name)?;
         let adviser_last_name = NameString::try_from(unvalidated_adviser_last_name)?;
 
-        Ok(Self { adviser_first_name, adviser_last_name })
+        // The directory entry must belong to the adviser named on the report
+        if let Some(profile) = &adviser_profile {
+            if !profile.is_named(&adviser_first_name, &adviser_last_name) {
+                return Err(format!(
+                    "Adviser {} {} does not match directory entry {}", 
+                    adviser_first_name, 
+                    adviser_last_name, 
+                    profile.id()
+                ));
+            }
+        }
+
+        Ok(Self { adviser_first_name, adviser_last_name, profile: adviser_profile })
+
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driving::data_transfer_object::report_type_data_transfer_object::adviser_data_transfer_object::AdviserProfileDto;
 
+    fn profile() -> AdviserProfile {
+        AdviserProfile::try_from(AdviserProfileDto {
+            id: "adv-001".to_string(),
+            first_name: "Jane".to_string(),
+            last_name: "Smith".to_string(),
+            qualifications: vec!["Chartered Financial Planner".to_string()],
+            fca_reference_number: "JXS01234".to_string(),
+            phone: "01234 567890".to_string(),
+            email: "jane.smith@example.com".to_string(),
+            office: "Guildford".to_string(),
+            signature_image_location: None
+        }).unwrap()
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_adviser_without_profile() {
+        let adviser = Adviser::new("jane".to_string(), "smith".to_string(), None).unwrap();
+        assert_eq!(adviser.adviser_first_name.value(), "Jane");
+        assert!(adviser.profile.is_none());
+    }
+
+    #[test]
+    fn test_adviser_matching_profile() {
+        let adviser = Adviser::new("Jane".to_string(), "Smith".to_string(), Some(profile())).unwrap();
+        assert_eq!(adviser.profile.unwrap().qualifications_line(), Some("Chartered Financial Planner".to_string()));
+    }
+
+    #[test]
+    fn test_adviser_mismatched_profile() {
+        assert!(Adviser::new("John".to_string(), "Smith".to_string(), Some(profile())).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+use std::fmt;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct EmailAddress(String);
+
+impl EmailAddress {
+    pub fn value(&self) -> &String {
+        &self.0
+    }
+}
+
+impl fmt::Display for EmailAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<String> for EmailAddress {
+    type Error = &'static str;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let trimmed = value.trim();
+
+        if trimmed.len() > 254 {
+            return Err("An email address cannot be longer than 254 characters");
+        }
+
+        let email_regex = Regex::new(r"^[A-Za-z0-9._%+'-]+@[A-Za-z0-9-]+(\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}$").unwrap();
+
+        if !email_regex.is_match(trimmed) {
+            return Err("Invalid email address format");
+        }
+
+        Ok(Self(trimmed.to_lowercase()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_email_addresses() {
+        assert_eq!(EmailAddress::try_from(" Jane.Smith@Example.co.uk ".to_string()).unwrap().value(), "jane.smith@example.co.uk");
+        assert!(EmailAddress::try_from("o'connor+reports@firm.com".to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_email_addresses() {
+        assert!(EmailAddress::try_from("".to_string()).is_err());
+        assert!(EmailAddress::try_from("jane.smith".to_string()).is_err());
+        assert!(EmailAddress::try_from("jane@smith".to_string()).is_err());
+        assert!(EmailAddress::try_from("jane smith@example.com".to_string()).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+use std::fmt;
+use serde::{Deserialize, Serialize};
+
+/// An FCA individual reference number, e.g. `ABC01234`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct FcaReferenceNumber(String);
+
+impl FcaReferenceNumber {
+    pub fn value(&self) -> &String {
+        &self.0
+    }
+}
+
+impl fmt::Display for FcaReferenceNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<String> for FcaReferenceNumber {
+    type Error = &'static str;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let normalised = value.trim().to_uppercase();
+
+        // Individual reference numbers are three letters followed by five digits
+        if normalised.len() != 8 {
+            return Err("An FCA reference number must be 8 characters long");
+        }
+
+        let (letters, digits) = normalised.split_at(3);
+        if !letters.chars().all(|c| c.is_ascii_uppercase()) || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err("An FCA reference number must be three letters followed by five digits");
+        }
+
+        Ok(Self(normalised))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_fca_reference_numbers() {
+        assert_eq!(FcaReferenceNumber::try_from("ABC01234".to_string()).unwrap().value(), "ABC01234");
+        assert_eq!(FcaReferenceNumber::try_from(" jds00123 ".to_string()).unwrap().value(), "JDS00123");
+    }
+
+    #[test]
+    fn test_invalid_fca_reference_numbers() {
+        assert!(FcaReferenceNumber::try_from("".to_string()).is_err());
+        assert!(FcaReferenceNumber::try_from("AB012345".to_string()).is_err());
+        assert!(FcaReferenceNumber::try_from("ABC0123".to_string()).is_err());
+        assert!(FcaReferenceNumber::try_from("ABC0123X".to_string()).is_err());
+    }
+}
@@ -2,6 +2,7 @@ use std::fmt;
 use std::str::FromStr;
 use std::convert::TryFrom;
 
+use chrono::{Days, Months, NaiveDate};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -15,6 +16,35 @@ pub enum Frequency {
     Annually,
 }
 
+impl Frequency {
+    /// How many times a year something at this frequency happens.
+    pub fn per_year(&self) -> u32 {
+        match self {
+            Frequency::Daily => 365,
+            Frequency::Weekly => 52,
+            Frequency::BiWeekly => 26,
+            Frequency::Monthly => 12,
+            Frequency::Quarterly => 4,
+            Frequency::SemiAnnually => 2,
+            Frequency::Annually => 1,
+        }
+    }
+
+    /// Returns the date of the `n`th occurrence after `start`, counting `start` itself as the
+    /// zeroth. Monthly schedules keep to the starting day, falling back to the end of shorter months.
+    pub fn nth_occurrence(&self, start: NaiveDate, n: u32) -> Option<NaiveDate> {
+        match self {
+            Frequency::Daily => start.checked_add_days(Days::new(n as u64)),
+            Frequency::Weekly => start.checked_add_days(Days::new(7 * n as u64)),
+            Frequency::BiWeekly => start.checked_add_days(Days::new(14 * n as u64)),
+            Frequency::Monthly => start.checked_add_months(Months::new(n)),
+            Frequency::Quarterly => start.checked_add_months(Months::new(3 * n)),
+            Frequency::SemiAnnually => start.checked_add_months(Months::new(6 * n)),
+            Frequency::Annually => start.checked_add_months(Months::new(12 * n)),
+        }
+    }
+}
+
 impl fmt::Display for Frequency {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let frequency_str = match self {
@@ -65,6 +95,21 @@ mod tests {
         assert_eq!(Frequency::Quarterly.to_string(), "quarterly");
     }
 
+    #[test]
+    fn test_per_year() {
+        assert_eq!(Frequency::Monthly.per_year(), 12);
+        assert_eq!(Frequency::BiWeekly.per_year(), 26);
+    }
+
+    #[test]
+    fn test_nth_occurrence() {
+        let start = NaiveDate::from_ymd_opt(2027, 1, 31).unwrap();
+
+        assert_eq!(Frequency::Monthly.nth_occurrence(start, 1), NaiveDate::from_ymd_opt(2027, 2, 28));
+        assert_eq!(Frequency::Monthly.nth_occurrence(start, 2), NaiveDate::from_ymd_opt(2027, 3, 31));
+        assert_eq!(Frequency::BiWeekly.nth_occurrence(start, 1), NaiveDate::from_ymd_opt(2027, 2, 14));
+    }
+
     #[test]
     fn test_from_str() {
         assert_eq!("monthly".parse::<Frequency>().unwrap(), Frequency::Monthly);
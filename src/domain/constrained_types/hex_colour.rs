@@ -0,0 +1,55 @@
+use std::fmt;
+use serde::{Deserialize, Serialize};
+
+/// A colour in `#RRGGBB` form, normalised to upper case.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct HexColour(String);
+
+impl HexColour {
+    pub fn value(&self) -> &String {
+        &self.0
+    }
+}
+
+impl fmt::Display for HexColour {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<String> for HexColour {
+    type Error = &'static str;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let trimmed = value.trim();
+
+        let digits = trimmed
+            .strip_prefix('#')
+            .ok_or("A colour must start with #")?;
+
+        if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err("A colour must be six hexadecimal digits, e.g. #1F3864");
+        }
+
+        Ok(Self(format!("#{}", digits.to_uppercase())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_colours() {
+        assert_eq!(HexColour::try_from("#1f3864".to_string()).unwrap().value(), "#1F3864");
+        assert_eq!(HexColour::try_from(" #FFFFFF ".to_string()).unwrap().value(), "#FFFFFF");
+    }
+
+    #[test]
+    fn test_invalid_colours() {
+        assert!(HexColour::try_from("".to_string()).is_err());
+        assert!(HexColour::try_from("1F3864".to_string()).is_err());
+        assert!(HexColour::try_from("#1F386".to_string()).is_err());
+        assert!(HexColour::try_from("#1F386G".to_string()).is_err());
+    }
+}
@@ -1,7 +1,9 @@
 use std::fmt;
-use chrono::{NaiveDate, Datelike, Duration, Utc};
+use chrono::{NaiveDate, Datelike, Duration};
 use serde::{Deserialize, Serialize};
 
+use crate::domain::clock::Clock;
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct LastReviewReportAndMeetingDate(NaiveDate);
 
@@ -32,11 +34,12 @@ impl fmt::Display for LastReviewReportAndMeetingDate {
     }
 }
 
-/// Implements conversion from a UK formatted date string into `LastReviewReportAndMeetingDate`
-impl TryFrom<String> for LastReviewReportAndMeetingDate {
+/// Implements conversion from a UK formatted date string into `LastReviewReportAndMeetingDate`,
+/// checked against the clock's date
+impl TryFrom<(String, &dyn Clock)> for LastReviewReportAndMeetingDate {
     type Error = String;
 
-    fn try_from(value: String) -> Result<Self, Self::Error> {
+    fn try_from((value, clock): (String, &dyn Clock)) -> Result<Self, Self::Error> {
         // Ensure the string is not empty
         if value.trim().is_empty() {
             return Err("Meeting date cannot be empty.".to_string());
@@ -46,8 +49,7 @@ impl TryFrom<String> for LastReviewReportAndMeetingDate {
         let parsed_date = NaiveDate::parse_from_str(&value, "%d/%m/%Y")
             .map_err(|_| "Invalid date format. Expected DD/MM/YYYY.".to_string())?;
 
-        // Get the current date
-        let today = Utc::now().naive_utc().date();
+        let today = clock.today();
 
         // Ensure the date is not more than 4 years in the past.
         // (Approximately 4 years = 1460 days.)
@@ -76,11 +78,17 @@ impl TryFrom<String> for LastReviewReportAndMeetingDate {
 mod tests {
     use super::*;
     use chrono::NaiveDate;
+    use crate::domain::clock::FixedClock;
+
+    fn parse(date_str: String) -> Result<LastReviewReportAndMeetingDate, String> {
+        let clock = FixedClock(NaiveDate::from_ymd_opt(2025, 6, 1).unwrap());
+        LastReviewReportAndMeetingDate::try_from((date_str, &clock as &dyn Clock))
+    }
 
     #[test]
     fn test_valid_date_within_4_years() {
         let date_str = "20/01/2023".to_string();
-        let result = LastReviewReportAndMeetingDate::try_from(date_str);
+        let result = parse(date_str);
         assert!(result.is_ok());
         let meeting_date = result.unwrap();
         assert_eq!(meeting_date.value(), &NaiveDate::from_ymd(2023, 1, 20));
@@ -90,7 +98,7 @@ mod tests {
     fn test_date_more_than_4_years_old() {
         // Using a date more than 4 years old (e.g. 19/01/2019)
         let date_str = "19/01/2019".to_string();
-        let result = LastReviewReportAndMeetingDate::try_from(date_str);
+        let result = parse(date_str);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
@@ -100,19 +108,19 @@ mod tests {
 
     #[test]
     fn test_future_date() {
-        let date_str = "20/01/2026".to_string();
-        let result = LastReviewReportAndMeetingDate::try_from(date_str);
+        let date_str = "20/06/2025".to_string();
+        let result = parse(date_str);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            "Meeting date must be in the past. Provided date: 20/01/2026".to_string()
+            "Meeting date must be in the past. Provided date: 20/06/2025".to_string()
         );
     }
 
     #[test]
     fn test_invalid_format() {
         let date_str = "2023-01-20".to_string();
-        let result = LastReviewReportAndMeetingDate::try_from(date_str);
+        let result = parse(date_str);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
@@ -123,7 +131,7 @@ mod tests {
     #[test]
     fn test_empty_date() {
         let date_str = "".to_string();
-        let result = LastReviewReportAndMeetingDate::try_from(date_str);
+        let result = parse(date_str);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), "Meeting date cannot be empty.".to_string());
     }
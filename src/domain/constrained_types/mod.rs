@@ -25,4 +25,9 @@ pub mod abrdn_full_account_number;
 pub mod transact_platform_number;
 pub mod transact_reference_number;
 pub mod abrdn_sipp_number;
-pub mod bank_account_numbers;
\ No newline at end of file
+pub mod bank_account_numbers;
+pub mod fca_reference_number;
+pub mod phone_number;
+pub mod email_address;
+pub mod s3_location;
+pub mod hex_colour;
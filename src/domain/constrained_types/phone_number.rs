@@ -0,0 +1,68 @@
+use std::fmt;
+use serde::{Deserialize, Serialize};
+
+/// A UK telephone number, kept as entered apart from surrounding whitespace.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct PhoneNumber(String);
+
+impl PhoneNumber {
+    pub fn value(&self) -> &String {
+        &self.0
+    }
+}
+
+impl fmt::Display for PhoneNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<String> for PhoneNumber {
+    type Error = &'static str;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let trimmed = value.trim();
+
+        if !trimmed.chars().all(|c| c.is_ascii_digit() || c == ' ' || c == '+' || c == '(' || c == ')' || c == '-') {
+            return Err("A phone number can only contain digits, spaces, brackets, hyphens and a leading +");
+        }
+
+        // The optional trunk prefix in "+44 (0)20 ..." is not dialled
+        let digits: String = trimmed.replace("(0)", "").chars().filter(|c| c.is_ascii_digit()).collect();
+
+        let national_digits = if trimmed.starts_with("+44") {
+            format!("0{}", &digits[2..])
+        } else if trimmed.contains('+') {
+            return Err("Only UK phone numbers are supported");
+        } else {
+            digits
+        };
+
+        if !national_digits.starts_with('0') || !(10..=11).contains(&national_digits.len()) {
+            return Err("A UK phone number must have 10 or 11 digits and start with 0 or +44");
+        }
+
+        Ok(Self(trimmed.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_phone_numbers() {
+        assert_eq!(PhoneNumber::try_from(" 01234 567890 ".to_string()).unwrap().value(), "01234 567890");
+        assert!(PhoneNumber::try_from("+44 (0)20 7946 0000".to_string()).is_ok());
+        assert!(PhoneNumber::try_from("+44 20 7946 0000".to_string()).is_ok());
+        assert!(PhoneNumber::try_from("07700-900123".to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_phone_numbers() {
+        assert!(PhoneNumber::try_from("".to_string()).is_err());
+        assert!(PhoneNumber::try_from("12345".to_string()).is_err());
+        assert!(PhoneNumber::try_from("+1 202 555 0100".to_string()).is_err());
+        assert!(PhoneNumber::try_from("0123 456 789O".to_string()).is_err());
+    }
+}
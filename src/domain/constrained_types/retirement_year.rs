@@ -1,8 +1,10 @@
 use std::convert::TryFrom;
 use std::fmt;
-use chrono::{Datelike, Utc};
+use chrono::Datelike;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::clock::Clock;
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct RetirementYear(i32);
 
@@ -22,24 +24,24 @@ impl Into<String> for InvalidYearError {
     }
 }
 
-// Implement TryFrom<&str> for RetirementYear
-impl TryFrom<&str> for RetirementYear {
+// Implement TryFrom<(&str, &dyn Clock)> for RetirementYear
+impl TryFrom<(&str, &dyn Clock)> for RetirementYear {
     type Error = InvalidYearError;
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
+    fn try_from((value, clock): (&str, &dyn Clock)) -> Result<Self, Self::Error> {
         match value.parse::<i32>() {
-            Ok(year) => RetirementYear::try_from(year),
+            Ok(year) => RetirementYear::try_from((year, clock)),
             Err(_) => Err(InvalidYearError),
         }
     }
 }
 
-// Implement TryFrom<i32> for RetirementYear
-impl TryFrom<i32> for RetirementYear {
+// Implement TryFrom<(i32, &dyn Clock)> for RetirementYear, checked against the clock's year
+impl TryFrom<(i32, &dyn Clock)> for RetirementYear {
     type Error = InvalidYearError;
 
-    fn try_from(year: i32) -> Result<Self, Self::Error> {
-        let current_year = Utc::now().year();
+    fn try_from((year, clock): (i32, &dyn Clock)) -> Result<Self, Self::Error> {
+        let current_year = clock.today().year();
         if year >= current_year {
             Ok(RetirementYear(year))
         } else {
@@ -0,0 +1,79 @@
+use std::fmt;
+use serde::{Deserialize, Serialize};
+
+/// An object location in S3 of the form `s3://bucket/key`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct S3Location(String);
+
+impl S3Location {
+    pub fn value(&self) -> &String {
+        &self.0
+    }
+
+    pub fn bucket(&self) -> &str {
+        self.0.trim_start_matches("s3://").split('/').next().unwrap_or_default()
+    }
+
+    pub fn key(&self) -> &str {
+        let without_scheme = self.0.trim_start_matches("s3://");
+        &without_scheme[self.bucket().len() + 1..]
+    }
+}
+
+impl fmt::Display for S3Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<String> for S3Location {
+    type Error = &'static str;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let trimmed = value.trim();
+
+        let without_scheme = trimmed
+            .strip_prefix("s3://")
+            .ok_or("An S3 location must start with s3://")?;
+
+        let (bucket, key) = without_scheme
+            .split_once('/')
+            .ok_or("An S3 location must include an object key")?;
+
+        // Bucket naming rules: 3-63 characters of lowercase letters, digits, dots and hyphens
+        if bucket.len() < 3 || bucket.len() > 63 {
+            return Err("An S3 bucket name must be between 3 and 63 characters long");
+        }
+
+        if !bucket.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '.' || c == '-') {
+            return Err("An S3 bucket name can only contain lowercase letters, digits, dots and hyphens");
+        }
+
+        if key.is_empty() || key.ends_with('/') {
+            return Err("An S3 object key cannot be empty");
+        }
+
+        Ok(Self(trimmed.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_s3_locations() {
+        let location = S3Location::try_from("s3://gcwm-branding/logos/logo.png".to_string()).unwrap();
+        assert_eq!(location.bucket(), "gcwm-branding");
+        assert_eq!(location.key(), "logos/logo.png");
+    }
+
+    #[test]
+    fn test_invalid_s3_locations() {
+        assert!(S3Location::try_from("".to_string()).is_err());
+        assert!(S3Location::try_from("https://gcwm-branding/logo.png".to_string()).is_err());
+        assert!(S3Location::try_from("s3://gcwm-branding".to_string()).is_err());
+        assert!(S3Location::try_from("s3://gcwm-branding/".to_string()).is_err());
+        assert!(S3Location::try_from("s3://GCWM/logo.png".to_string()).is_err());
+    }
+}
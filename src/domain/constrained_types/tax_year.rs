@@ -1,8 +1,10 @@
 use std::fmt;
-use chrono::Datelike;
+use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+use crate::domain::clock::Clock;
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TaxYear(String);
 
 impl TaxYear {
@@ -11,8 +13,49 @@ impl TaxYear {
         &self.0
     }
 
-    /// Validates the tax year format and rules.
-    fn is_valid_tax_year(tax_year: &str) -> Result<(), String> {
+    /// Returns 6th April, the first day of the tax year.
+    pub fn first_day(&self) -> Result<NaiveDate, String> {
+        NaiveDate::from_ymd_opt(self.start_year()?, 4, 6).ok_or_else(|| format!("Invalid tax year {}", self.0))
+    }
+
+    /// Returns 5th April of the following year, the last day of the tax year.
+    pub fn last_day(&self) -> Result<NaiveDate, String> {
+        NaiveDate::from_ymd_opt(self.start_year()? + 1, 4, 5).ok_or_else(|| format!("Invalid tax year {}", self.0))
+    }
+
+    /// Returns the tax year `years` before this one, which may be in the past.
+    pub fn years_before(&self, years: i32) -> Result<TaxYear, String> {
+        let start_year = self.start_year()? - years;
+        Ok(TaxYear(format!("{}/{}", start_year, start_year + 1)))
+    }
+
+    /// Returns the tax year after this one.
+    pub fn next(&self) -> Result<TaxYear, String> {
+        self.years_before(-1)
+    }
+
+    /// Returns the tax year a date falls in. Tax years run from 6th April to 5th April.
+    pub fn containing(date: NaiveDate) -> TaxYear {
+        let start_year = if (date.month(), date.day()) >= (4, 6) { date.year() } else { date.year() - 1 };
+        TaxYear(format!("{}/{}", start_year, start_year + 1))
+    }
+
+    /// Parses a tax year in `YYYY/YYYY` form, which may be in the past.
+    pub fn parse(value: &str) -> Result<TaxYear, String> {
+        let trimmed = value.trim();
+        TaxYear::check_format(trimmed)?;
+        Ok(TaxYear(trimmed.to_string()))
+    }
+
+    fn start_year(&self) -> Result<i32, String> {
+        self.0
+            .get(..4)
+            .and_then(|year| year.parse().ok())
+            .ok_or_else(|| format!("Invalid tax year {}", self.0))
+    }
+
+    /// Validates the tax year format.
+    fn check_format(tax_year: &str) -> Result<(), String> {
         // Split the input into two parts.
         let parts: Vec<&str> = tax_year.split('/').collect();
         // Check that there are exactly two parts and that each part has exactly 4 characters.
@@ -35,14 +78,16 @@ impl TaxYear {
             ));
         }
 
-        // Get the current year.
-        let current_year = chrono::Utc::now().year();
+        Ok(())
+    }
 
+    /// Validates that the tax year has not already ended by `today`.
+    fn check_not_past(&self, today: NaiveDate) -> Result<(), String> {
         // Ensure tax year is the present or future.
-        if start_year < current_year {
+        if *self < TaxYear::containing(today) {
             return Err(format!(
-                "Invalid tax year. Tax year cannot be in the past. Provided: {}/{}",
-                start_year, end_year
+                "Invalid tax year. Tax year cannot be in the past. Provided: {}",
+                self
             ));
         }
 
@@ -56,13 +101,54 @@ impl fmt::Display for TaxYear {
     }
 }
 
-impl TryFrom<String> for TaxYear {
+/// Implements conversion from a tax year in `YYYY/YYYY` form that has not ended as at the clock's date
+impl TryFrom<(String, &dyn Clock)> for TaxYear {
     type Error = String;
 
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        let trimmed = value.trim();
-        TaxYear::is_valid_tax_year(trimmed)?;
-        Ok(TaxYear(trimmed.to_string()))
+    fn try_from((value, clock): (String, &dyn Clock)) -> Result<Self, Self::Error> {
+        let tax_year = TaxYear::parse(&value)?;
+        tax_year.check_not_past(clock.today())?;
+        Ok(tax_year)
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::clock::FixedClock;
+
+    #[test]
+    fn test_first_and_last_day() {
+        let tax_year = TaxYear::parse("2027/2028").unwrap();
+
+        assert_eq!(tax_year.first_day().unwrap(), NaiveDate::from_ymd_opt(2027, 4, 6).unwrap());
+        assert_eq!(tax_year.last_day().unwrap(), NaiveDate::from_ymd_opt(2028, 4, 5).unwrap());
+    }
+
+    #[test]
+    fn test_years_before() {
+        let tax_year = TaxYear::parse("2027/2028").unwrap();
+
+        assert_eq!(tax_year.years_before(3).unwrap().value(), "2024/2025");
+    }
+
+    #[test]
+    fn test_containing() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2026, month, day).unwrap();
+
+        assert_eq!(TaxYear::containing(date(4, 5)).value(), "2025/2026");
+        assert_eq!(TaxYear::containing(date(4, 6)).value(), "2026/2027");
+        assert_eq!(TaxYear::containing(date(1, 15)).value(), "2025/2026");
+    }
+
+    #[test]
+    fn test_past_tax_years() {
+        // The 2025/2026 tax year is still running in January 2026
+        let clock = FixedClock(NaiveDate::from_ymd_opt(2026, 1, 15).unwrap());
+
+        assert!(TaxYear::try_from(("2025/2026".to_string(), &clock as &dyn Clock)).is_ok());
+        assert!(TaxYear::try_from(("2024/2025".to_string(), &clock as &dyn Clock)).unwrap_err().contains("cannot be in the past"));
+        assert_eq!(TaxYear::parse(" 2020/2021 ").unwrap().value(), "2020/2021");
+        assert!(TaxYear::parse("2020/2022").unwrap_err().contains("first year + 1"));
+    }
+}
@@ -0,0 +1,17 @@
+use crate::driven::repository::{AdviserRepository, FindAdviser, RepoSelectError};
+
+use super::find_model_portfolio::FindOneError;
+use super::report::adviser_profile::AdviserProfile;
+
+pub async fn find_one_adviser<R: AdviserRepository<AdviserProfile>>(
+    repo: &R,
+    find_adviser: FindAdviser
+) -> Result<AdviserProfile, FindOneError> {
+
+    repo.find_one_adviser(find_adviser).await
+        .map_err(|e| match e {
+            RepoSelectError::Unknown(e) => FindOneError::Unknown(format!("Unknown error: {}", e)),
+            RepoSelectError::NotFound => FindOneError::NotFound
+        })
+
+}
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::driven::doc_generator::{generate_links, DocumentGenerator, OutputFormat};
+use crate::driven::repository::{FindGenerationJob, GenerationJobRepository};
+use crate::driving::data_transfer_object::report_type_data_transfer_object::adviser_data_transfer_object::AdviserDataTransferObject;
+
+use super::traits::Entity;
+
+/// Progress of a document generation job.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum JobStatus {
+    Pending,
+    /// `url` is the download link, or a link per format when formats were requested.
+    Done { url: Value },
+    Failed { error: String },
+}
+
+/// Document generation accepted by the API and carried out in the background.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerationJob {
+    #[serde(with = "uuid::serde::simple")]
+    id: Uuid,
+    /// The adviser named on the report, who may check on the job
+    adviser: AdviserDataTransferObject,
+    /// The document instructions, kept with the job so that only its id is handed over
+    instructions: Value,
+    output_formats: Option<Vec<OutputFormat>>,
+    #[serde(flatten)]
+    status: JobStatus,
+}
+
+impl Entity for GenerationJob {}
+
+impl GenerationJob {
+    pub fn new(adviser: AdviserDataTransferObject, instructions: Value, output_formats: Option<Vec<OutputFormat>>) -> Self {
+        GenerationJob { id: Uuid::new_v4(), adviser, instructions, output_formats, status: JobStatus::Pending }
+    }
+
+    pub fn id(&self) -> String {
+        self.id.simple().to_string()
+    }
+
+    pub fn adviser(&self) -> &AdviserDataTransferObject {
+        &self.adviser
+    }
+
+    pub fn instructions(&self) -> &Value {
+        &self.instructions
+    }
+
+    pub fn output_formats(&self) -> Option<&[OutputFormat]> {
+        self.output_formats.as_deref()
+    }
+
+    pub fn status(&self) -> &JobStatus {
+        &self.status
+    }
+
+    pub fn complete(self, url: Value) -> Self {
+        GenerationJob { status: JobStatus::Done { url }, ..self }
+    }
+
+    pub fn fail(self, error: String) -> Self {
+        GenerationJob { status: JobStatus::Failed { error }, ..self }
+    }
+}
+
+/// Generates the documents for a pending job from the instructions stored with it and records
+/// the outcome.
+pub async fn run_generation_job<J>(
+    job_id: String,
+    doc_gen: Arc<dyn DocumentGenerator + Send + Sync>,
+    job_repo: Arc<J>
+) where J: GenerationJobRepository<GenerationJob> + Send + Sync {
+
+    let job = match job_repo.find_one_job(FindGenerationJob { id: job_id.clone() }).await {
+        Ok(job) => job,
+        Err(e) => {
+            error!(job_id = %job_id, error = ?e, "Generation job could not be found");
+            return;
+        }
+    };
+
+    let outcome = generate_links(doc_gen.as_ref(), job.instructions(), job.output_formats()).await;
+    let job = match outcome {
+        Ok(url) => {
+            info!(job_id = %job_id, "Generation job finished");
+            job.complete(url)
+        }
+        Err(e) => {
+            error!(job_id = %job_id, error = %e, "Generation job failed");
+            job.fail(e.to_string())
+        }
+    };
+
+    if let Err(e) = job_repo.update_job(job).await {
+        error!(job_id = %job_id, error = ?e, "Failed to record generation job outcome");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driven::doc_generator::{layout::tests::sample_document, local::LocalDocGenerator};
+    use crate::driven::repository::in_memory::InMemoryGenerationJobRepo;
+    use crate::driven::storage::{filesystem::FilesystemStorage, ObjectNaming};
+
+    fn adviser() -> AdviserDataTransferObject {
+        AdviserDataTransferObject {
+            id: None,
+            adviser_first_name: "Alex".to_string(),
+            adviser_last_name: "Jones".to_string()
+        }
+    }
+
+    #[test]
+    fn test_serialized_status() {
+        let job = GenerationJob::new(adviser(), sample_document(), None);
+        let json = serde_json::to_value(job.clone().complete(Value::String("https://example.com/a1b2c3.docx".to_string()))).unwrap();
+        assert_eq!(json["status"], "done");
+        assert_eq!(json["url"], "https://example.com/a1b2c3.docx");
+
+        let json = serde_json::to_value(job.fail("timed out".to_string())).unwrap();
+        assert_eq!(json["status"], "failed");
+        assert_eq!(json["error"], "timed out");
+    }
+
+    #[tokio::test]
+    async fn test_run_generation_job_from_stored_instructions() {
+        let job_repo = Arc::new(InMemoryGenerationJobRepo::default());
+        let job = job_repo.create_job(GenerationJob::new(adviser(), sample_document(), Some(vec![OutputFormat::Markdown]))).await.unwrap();
+        let doc_gen: Arc<dyn DocumentGenerator + Send + Sync> = Arc::new(LocalDocGenerator::new(
+            FilesystemStorage::new(std::env::temp_dir().join("gcwm-generation-job-test")),
+            ObjectNaming::default(),
+            std::time::Duration::from_secs(60)
+        ));
+
+        run_generation_job(job.id(), doc_gen, job_repo.clone()).await;
+
+        let finished = job_repo.find_one_job(FindGenerationJob { id: job.id() }).await.unwrap();
+        match finished.status() {
+            JobStatus::Done { url } => assert!(url["markdown"].as_str().unwrap().ends_with("/a1b2c3.md")),
+            other => panic!("unexpected status {:?}", other)
+        }
+    }
+}
@@ -1,7 +1,12 @@
 pub mod report;
+pub mod clock;
 pub mod traits;
 pub mod constrained_types;
 pub mod error;
 pub mod find_model_portfolio;
+pub mod find_adviser;
+pub mod generation_job;
+pub mod tax;
+pub mod projection;
 
 pub use error::DomainError;
\ No newline at end of file
@@ -0,0 +1,142 @@
+/// What a cash-flow projection of an income objective starts from. Amounts are in today's money.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncomeProjectionBasis {
+    /// The current value of the products funding the income
+    pub starting_value: f64,
+    /// The income wanted a year, raised each year by inflation
+    pub annual_income: f64,
+    /// The first year income is taken, as a calendar year
+    pub income_from: i32,
+    /// The first year projected, normally the current year
+    pub first_year: i32,
+    /// The last year the income must be paid in
+    pub last_year: i32,
+    /// Growth a year before charges
+    pub growth_rate: f64,
+    /// Product charges a year, taken from the value after growth
+    pub charge_rate: f64,
+    pub inflation: f64
+}
+
+/// One year of a projection. Income is taken at the end of the year.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectedYear {
+    pub year: i32,
+    pub opening_value: f64,
+    pub growth: f64,
+    pub charges: f64,
+    /// The income wanted this year, after inflation
+    pub income_wanted: f64,
+    /// The income the products can pay, which falls short of `income_wanted` once they run out
+    pub income_paid: f64,
+    pub closing_value: f64
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncomeProjection {
+    pub years: Vec<ProjectedYear>
+}
+
+impl IncomeProjection {
+    /// The first year the products cannot pay the full income, if any.
+    pub fn runs_out_in(&self) -> Option<i32> {
+        self.years
+            .iter()
+            .find(|year| year.income_paid < year.income_wanted - 0.005)
+            .map(|year| year.year)
+    }
+
+    pub fn is_sustainable(&self) -> bool {
+        self.runs_out_in().is_none()
+    }
+
+    /// The value left at the end of the last year projected.
+    pub fn closing_value(&self) -> f64 {
+        self.years.last().map(|year| year.closing_value).unwrap_or(0.0)
+    }
+}
+
+/// Projects the products year by year from `first_year` to `last_year`, paying the income from
+/// `income_from` onwards while they can.
+pub fn project(basis: &IncomeProjectionBasis) -> IncomeProjection {
+    project_with_returns(basis, || basis.growth_rate)
+}
+
+/// Projects as [`project`] does, but with each year's growth rate taken from `returns` in turn,
+/// so markets can be simulated.
+pub(super) fn project_with_returns(basis: &IncomeProjectionBasis, mut returns: impl FnMut() -> f64) -> IncomeProjection {
+    let mut value = basis.starting_value;
+    let years = (basis.first_year..=basis.last_year)
+        .map(|year| {
+            let opening_value = value;
+            let growth = opening_value * returns();
+            let charges = (opening_value + growth) * basis.charge_rate;
+            let available = opening_value + growth - charges;
+            let income_wanted = if year >= basis.income_from {
+                basis.annual_income * (1.0 + basis.inflation).powi(year - basis.first_year)
+            } else {
+                0.0
+            };
+            let income_paid = income_wanted.min(available);
+            value = available - income_paid;
+
+            ProjectedYear { year, opening_value, growth, charges, income_wanted, income_paid, closing_value: value }
+        })
+        .collect();
+
+    IncomeProjection { years }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn basis() -> IncomeProjectionBasis {
+        IncomeProjectionBasis {
+            starting_value: 500_000.0,
+            annual_income: 20_000.0,
+            income_from: 2030,
+            first_year: 2026,
+            last_year: 2060,
+            growth_rate: 0.05,
+            charge_rate: 0.01,
+            inflation: 0.025
+        }
+    }
+
+    #[test]
+    fn test_value_grows_until_income_starts() {
+        let projection = project(&basis());
+        let first = &projection.years[0];
+
+        assert_eq!(projection.years.len(), 35);
+        assert_eq!(first.growth, 25_000.0);
+        assert_eq!(first.charges, 5_250.0);
+        assert_eq!(first.income_wanted, 0.0);
+        assert_eq!(first.closing_value, 519_750.0);
+        assert_eq!(projection.years[4].year, 2030);
+        // Four years of inflation on today's £20,000
+        assert!((projection.years[4].income_wanted - 22_076.26).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_sustainable_income() {
+        let projection = project(&basis());
+
+        assert!(projection.is_sustainable());
+        assert!(projection.closing_value() > 0.0);
+    }
+
+    #[test]
+    fn test_income_runs_out() {
+        let projection = project(&IncomeProjectionBasis { annual_income: 40_000.0, ..basis() });
+        let runs_out_in = projection.runs_out_in().unwrap();
+        let year = projection.years.iter().find(|year| year.year == runs_out_in).unwrap();
+
+        assert!(!projection.is_sustainable());
+        assert!(year.income_paid < year.income_wanted);
+        assert_eq!(year.closing_value, 0.0);
+        assert_eq!(projection.closing_value(), 0.0);
+        assert!(projection.years.iter().filter(|year| year.year < runs_out_in).all(|year| year.income_paid == year.income_wanted));
+    }
+}
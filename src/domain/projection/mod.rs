@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::report::investment_holdings::ModelPortfolioId;
+use crate::domain::report::risk_assessment::RiskProfile;
+use crate::driven::config::ProjectionAssumptionsConfig;
+
+pub mod cash_flow;
+pub mod monte_carlo;
+
+/// Assumptions shipped with the service, used unless a deployment supplies its own.
+const DEFAULT_PROJECTION_ASSUMPTIONS: &str = include_str!("../../../config/projection_assumptions.json");
+
+/// An assumed rate a year for each risk profile, as a fraction such as `0.05` for 5%.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RiskProfileRates {
+    pub cautious: f64,
+    pub cautious_to_moderate: f64,
+    pub moderate: f64,
+    pub moderate_to_adventurous: f64,
+    pub adventurous: f64
+}
+
+impl RiskProfileRates {
+    pub fn for_risk_profile(&self, risk_profile: &RiskProfile) -> f64 {
+        match risk_profile {
+            RiskProfile::Cautious => self.cautious,
+            RiskProfile::CautiousToModerate => self.cautious_to_moderate,
+            RiskProfile::Moderate => self.moderate,
+            RiskProfile::ModerateToAdventurous => self.moderate_to_adventurous,
+            RiskProfile::Adventurous => self.adventurous
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        check_rate("cautious", self.cautious)?;
+        check_rate("cautiousToModerate", self.cautious_to_moderate)?;
+        check_rate("moderate", self.moderate)?;
+        check_rate("moderateToAdventurous", self.moderate_to_adventurous)?;
+        check_rate("adventurous", self.adventurous)
+    }
+}
+
+/// Growth and volatility for one model portfolio, used instead of those for its risk profile.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ModelPortfolioAssumption {
+    pub portfolio: ModelPortfolioId,
+    pub growth_rate: f64,
+    pub volatility: f64
+}
+
+/// How investment returns are assumed to behave: the expected growth a year before charges and
+/// how widely returns vary around it from year to year.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarketAssumption {
+    pub growth_rate: f64,
+    pub volatility: f64
+}
+
+/// The growth, volatility, inflation and life expectancy projections are made on, and how many
+/// market scenarios are simulated from which seed.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "ProjectionAssumptionsConfig", into = "ProjectionAssumptionsConfig")]
+pub struct ProjectionAssumptions {
+    growth_rates: RiskProfileRates,
+    volatilities: RiskProfileRates,
+    model_portfolios: Vec<ModelPortfolioAssumption>,
+    inflation: f64,
+    life_expectancy: i32,
+    simulations: u32,
+    seed: u64,
+    withdrawal_years: i32
+}
+
+impl ProjectionAssumptions {
+    pub fn growth_rate(&self, risk_profile: &RiskProfile) -> f64 {
+        self.growth_rates.for_risk_profile(risk_profile)
+    }
+
+    /// The assumption for a model portfolio, if it has one of its own, otherwise that for the
+    /// risk profile it is managed to.
+    pub fn market_assumption(&self, risk_profile: &RiskProfile, portfolio: Option<&ModelPortfolioId>) -> MarketAssumption {
+        let own = portfolio.and_then(|portfolio| self.model_portfolios.iter().find(|assumption| &assumption.portfolio == portfolio));
+        match own {
+            Some(assumption) => MarketAssumption { growth_rate: assumption.growth_rate, volatility: assumption.volatility },
+            None => {
+                let risk_profile = portfolio.map(|portfolio| &portfolio.risk_profile).unwrap_or(risk_profile);
+                MarketAssumption {
+                    growth_rate: self.growth_rates.for_risk_profile(risk_profile),
+                    volatility: self.volatilities.for_risk_profile(risk_profile)
+                }
+            }
+        }
+    }
+
+    pub fn inflation(&self) -> f64 {
+        self.inflation
+    }
+
+    /// The age income objectives must be met until.
+    pub fn life_expectancy(&self) -> i32 {
+        self.life_expectancy
+    }
+
+    pub fn simulations(&self) -> u32 {
+        self.simulations
+    }
+
+    /// Seeds the simulations, so the same report always shows the same range of outcomes.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// How many years withdrawals without an end date are projected for.
+    pub fn withdrawal_years(&self) -> i32 {
+        self.withdrawal_years
+    }
+}
+
+impl Default for ProjectionAssumptions {
+    fn default() -> Self {
+        let config: ProjectionAssumptionsConfig = serde_json::from_str(DEFAULT_PROJECTION_ASSUMPTIONS)
+            .expect("Bundled projection assumptions are valid JSON");
+        Self::try_from(config)
+            .expect("Bundled projection assumptions are valid")
+    }
+}
+
+impl TryFrom<ProjectionAssumptionsConfig> for ProjectionAssumptions {
+    type Error = String;
+
+    fn try_from(config: ProjectionAssumptionsConfig) -> Result<Self, Self::Error> {
+        config.growth_rates.validate().map_err(|e| format!("growthRates: {}", e))?;
+        config.volatilities.validate().map_err(|e| format!("volatilities: {}", e))?;
+        for assumption in &config.model_portfolios {
+            check_rate("modelPortfolios growthRate", assumption.growth_rate)?;
+            check_rate("modelPortfolios volatility", assumption.volatility)?;
+        }
+        check_rate("inflation", config.inflation)?;
+        if !(60..=120).contains(&config.life_expectancy) {
+            return Err("lifeExpectancy must be between 60 and 120".to_string());
+        }
+        if !(100..=100_000).contains(&config.simulations) {
+            return Err("simulations must be between 100 and 100000".to_string());
+        }
+        if !(1..=60).contains(&config.withdrawal_years) {
+            return Err("withdrawalYears must be between 1 and 60".to_string());
+        }
+
+        Ok(Self {
+            growth_rates: config.growth_rates,
+            volatilities: config.volatilities,
+            model_portfolios: config.model_portfolios,
+            inflation: config.inflation,
+            life_expectancy: config.life_expectancy,
+            simulations: config.simulations,
+            seed: config.seed,
+            withdrawal_years: config.withdrawal_years
+        })
+    }
+}
+
+impl From<ProjectionAssumptions> for ProjectionAssumptionsConfig {
+    fn from(assumptions: ProjectionAssumptions) -> Self {
+        Self {
+            growth_rates: assumptions.growth_rates,
+            volatilities: assumptions.volatilities,
+            model_portfolios: assumptions.model_portfolios,
+            inflation: assumptions.inflation,
+            life_expectancy: assumptions.life_expectancy,
+            simulations: assumptions.simulations,
+            seed: assumptions.seed,
+            withdrawal_years: assumptions.withdrawal_years
+        }
+    }
+}
+
+/// Checks that an assumed rate is a fraction a year, such as `0.025` for 2.5%.
+fn check_rate(name: &str, rate: f64) -> Result<(), String> {
+    if (0.0..=1.0).contains(&rate) {
+        Ok(())
+    } else {
+        Err(format!("{} must be between 0 and 1", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::report::investment_holdings::{InvestmentStrategyProductType, InvestmentStrategyProvider, InvestmentStrategyServiceProposition};
+
+    fn config() -> ProjectionAssumptionsConfig {
+        ProjectionAssumptions::default().into()
+    }
+
+    fn portfolio(risk_profile: RiskProfile) -> ModelPortfolioId {
+        ModelPortfolioId {
+            provider: InvestmentStrategyProvider::Transact,
+            service_proposition: InvestmentStrategyServiceProposition::Prime,
+            sri: false,
+            risk_profile,
+            product_type: InvestmentStrategyProductType::Sipp
+        }
+    }
+
+    #[test]
+    fn test_bundled_assumptions_are_valid() {
+        let assumptions = ProjectionAssumptions::default();
+
+        assert_eq!(assumptions.growth_rate(&RiskProfile::Moderate), 0.05);
+        assert_eq!(assumptions.market_assumption(&RiskProfile::Moderate, None), MarketAssumption { growth_rate: 0.05, volatility: 0.1 });
+        assert_eq!(assumptions.life_expectancy(), 95);
+        assert_eq!(assumptions.simulations(), 1000);
+    }
+
+    #[test]
+    fn test_model_portfolio_assumptions() {
+        let mut config = config();
+        config.model_portfolios.push(ModelPortfolioAssumption { portfolio: portfolio(RiskProfile::Moderate), growth_rate: 0.052, volatility: 0.11 });
+        let assumptions = ProjectionAssumptions::try_from(config).unwrap();
+
+        // A model portfolio of its own
+        assert_eq!(
+            assumptions.market_assumption(&RiskProfile::Cautious, Some(&portfolio(RiskProfile::Moderate))),
+            MarketAssumption { growth_rate: 0.052, volatility: 0.11 }
+        );
+        // Other model portfolios follow the risk profile they are managed to
+        assert_eq!(
+            assumptions.market_assumption(&RiskProfile::Cautious, Some(&portfolio(RiskProfile::Adventurous))),
+            MarketAssumption { growth_rate: 0.06, volatility: 0.14 }
+        );
+    }
+
+    #[test]
+    fn test_invalid_assumptions_are_rejected() {
+        let mut percentage = config();
+        percentage.growth_rates.adventurous = 6.0;
+        let mut volatility = config();
+        volatility.volatilities.cautious = -0.1;
+        let mut inflation = config();
+        inflation.inflation = -0.01;
+        let mut life_expectancy = config();
+        life_expectancy.life_expectancy = 200;
+        let mut simulations = config();
+        simulations.simulations = 0;
+
+        assert!(ProjectionAssumptions::try_from(percentage).unwrap_err().contains("adventurous"));
+        assert!(ProjectionAssumptions::try_from(volatility).unwrap_err().contains("volatilities"));
+        assert!(ProjectionAssumptions::try_from(inflation).unwrap_err().contains("inflation"));
+        assert!(ProjectionAssumptions::try_from(life_expectancy).unwrap_err().contains("lifeExpectancy"));
+        assert!(ProjectionAssumptions::try_from(simulations).unwrap_err().contains("simulations"));
+    }
+}
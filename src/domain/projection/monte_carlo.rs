@@ -0,0 +1,207 @@
+use std::f64::consts::PI;
+
+use super::MarketAssumption;
+use super::cash_flow::{self, IncomeProjectionBasis};
+
+/// The percentiles of projected value reported as poor, median and good outcomes.
+const POOR: f64 = 0.1;
+const MEDIAN: f64 = 0.5;
+const GOOD: f64 = 0.9;
+
+/// The spread of projected values at the end of one year across all simulations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutcomeRange {
+    pub year: i32,
+    /// The 10th percentile, so one outcome in ten was worse
+    pub poor: f64,
+    pub median: f64,
+    /// The 90th percentile, so one outcome in ten was better
+    pub good: f64
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatedOutcomes {
+    pub simulations: u32,
+    /// The fraction of simulations in which the products paid the full income every year
+    pub probability_of_success: f64,
+    pub years: Vec<OutcomeRange>
+}
+
+impl SimulatedOutcomes {
+    /// The ranges for every `step`th year from the first, and for the last year.
+    pub fn every(&self, step: usize) -> Vec<OutcomeRange> {
+        let last = self.years.len().saturating_sub(1);
+        self.years
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| index % step.max(1) == 0 || *index == last)
+            .map(|(_, range)| *range)
+            .collect()
+    }
+}
+
+/// Projects the basis through `simulations` market scenarios drawn from `seed`, in place of its
+/// fixed growth rate. Each year's return is drawn from a lognormal distribution with the
+/// assumed growth rate as its mean and the volatility as its standard deviation, so a year can
+/// lose a great deal but never more than everything.
+pub fn simulate(basis: &IncomeProjectionBasis, market: MarketAssumption, simulations: u32, seed: u64) -> SimulatedOutcomes {
+    let mut rng = SplitMix64(seed);
+    let (mu, sigma) = log_return_parameters(market);
+    let year_count = (basis.last_year - basis.first_year + 1).max(0) as usize;
+
+    let mut successes = 0;
+    let mut values_by_year = vec![Vec::with_capacity(simulations as usize); year_count];
+    for _ in 0..simulations {
+        let projection = cash_flow::project_with_returns(basis, || (mu + sigma * rng.standard_normal()).exp() - 1.0);
+        if projection.is_sustainable() {
+            successes += 1;
+        }
+        for (values, year) in values_by_year.iter_mut().zip(&projection.years) {
+            values.push(year.closing_value);
+        }
+    }
+
+    let years = values_by_year
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut values)| {
+            values.sort_by(f64::total_cmp);
+            OutcomeRange {
+                year: basis.first_year + index as i32,
+                poor: percentile(&values, POOR),
+                median: percentile(&values, MEDIAN),
+                good: percentile(&values, GOOD)
+            }
+        })
+        .collect();
+
+    SimulatedOutcomes {
+        simulations,
+        probability_of_success: if simulations == 0 { 0.0 } else { f64::from(successes) / f64::from(simulations) },
+        years
+    }
+}
+
+/// The mean and standard deviation of the log of one plus the return, for returns with the
+/// assumed mean and volatility.
+fn log_return_parameters(market: MarketAssumption) -> (f64, f64) {
+    let mean = 1.0 + market.growth_rate;
+    let variance = (1.0 + (market.volatility / mean).powi(2)).ln();
+    (mean.ln() - variance / 2.0, variance.sqrt())
+}
+
+/// The nearest-rank percentile of values sorted in ascending order.
+fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (fraction * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// The SplitMix64 generator: small, fast and, unlike the operating system's, reproducible from
+/// its seed.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in (0, 1], so it is always safe to take the log of.
+    fn next_f64(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 1.0) / (1u64 << 53) as f64
+    }
+
+    /// A standard normal draw, by the Box-Muller transform.
+    fn standard_normal(&mut self) -> f64 {
+        let radius = (-2.0 * self.next_f64().ln()).sqrt();
+        radius * (2.0 * PI * self.next_f64()).cos()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn basis() -> IncomeProjectionBasis {
+        IncomeProjectionBasis {
+            starting_value: 500_000.0,
+            annual_income: 25_000.0,
+            income_from: 2026,
+            first_year: 2026,
+            last_year: 2055,
+            growth_rate: 0.05,
+            charge_rate: 0.01,
+            inflation: 0.025
+        }
+    }
+
+    fn market(volatility: f64) -> MarketAssumption {
+        MarketAssumption { growth_rate: 0.05, volatility }
+    }
+
+    #[test]
+    fn test_same_seed_gives_same_outcomes() {
+        let outcomes = simulate(&basis(), market(0.1), 500, 42);
+
+        assert_eq!(outcomes, simulate(&basis(), market(0.1), 500, 42));
+        assert_ne!(outcomes, simulate(&basis(), market(0.1), 500, 43));
+        assert_eq!(outcomes.years.len(), 30);
+    }
+
+    #[test]
+    fn test_no_volatility_matches_cash_flow_projection() {
+        let projection = cash_flow::project(&basis());
+        let outcomes = simulate(&basis(), market(0.0), 100, 1);
+        let last = outcomes.years.last().unwrap();
+
+        assert!((last.poor - projection.closing_value()).abs() < 0.01);
+        assert!((last.good - projection.closing_value()).abs() < 0.01);
+        assert_eq!(outcomes.probability_of_success, if projection.is_sustainable() { 1.0 } else { 0.0 });
+    }
+
+    #[test]
+    fn test_volatility_spreads_outcomes() {
+        let outcomes = simulate(&basis(), market(0.12), 2000, 7);
+        let tenth = outcomes.years[9];
+
+        assert!(tenth.poor < tenth.median && tenth.median < tenth.good);
+        assert!(outcomes.probability_of_success > 0.0 && outcomes.probability_of_success < 1.0);
+        assert!(outcomes.years.iter().all(|range| range.poor >= 0.0));
+    }
+
+    #[test]
+    fn test_every() {
+        let outcomes = simulate(&basis(), market(0.1), 100, 1);
+        let years: Vec<i32> = outcomes.every(5).iter().map(|range| range.year).collect();
+
+        assert_eq!(years, vec![2026, 2031, 2036, 2041, 2046, 2051, 2055]);
+    }
+
+    #[test]
+    fn test_percentile() {
+        let values: Vec<f64> = (1..=10).map(f64::from).collect();
+
+        assert_eq!(percentile(&values, POOR), 1.0);
+        assert_eq!(percentile(&values, MEDIAN), 5.0);
+        assert_eq!(percentile(&values, GOOD), 9.0);
+        assert_eq!(percentile(&[], MEDIAN), 0.0);
+    }
+
+    #[test]
+    fn test_returns_have_assumed_mean_and_volatility() {
+        let (mu, sigma) = log_return_parameters(market(0.1));
+        let mut rng = SplitMix64(3);
+        let returns: Vec<f64> = (0..100_000).map(|_| (mu + sigma * rng.standard_normal()).exp() - 1.0).collect();
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let deviation = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / returns.len() as f64).sqrt();
+
+        assert!((mean - 0.05).abs() < 0.002);
+        assert!((deviation - 0.1).abs() < 0.002);
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{domain::constrained_types::{constrained_string_1000::ConstrainedString1000, constrained_string_20::ConstrainedString20}, driving::data_transfer_object::report_type_data_transfer_object::advice_areas::{AdviceAreaDto, AdviceAreasDto}};
+use crate::{domain::constrained_types::{constrained_money_amount_large::ConstrainedMoneyAmountLarge, constrained_string_1000::ConstrainedString1000, constrained_string_20::ConstrainedString20}, driving::data_transfer_object::report_type_data_transfer_object::advice_areas::{AdviceAreaDto, AdviceAreasDto, EmergencyFundCalculationDto}};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -54,9 +54,14 @@ impl TryFrom<&AdviceAreaDto> for AdviceArea {
     fn try_from(unvalidated_advice_area: &AdviceAreaDto) -> Result<Self, Self::Error> {
         match unvalidated_advice_area {
             AdviceAreaDto::EmergencyFund(unvalidated_emergency_fund_advice) => {
+                if unvalidated_emergency_fund_advice.advice.is_none() && unvalidated_emergency_fund_advice.calculation.is_none() {
+                    return Err("Emergency fund advice must include either advice or a calculation.".to_string());
+                }
+
                 Ok(Self::EmergencyFund(
                     EmergencyFundAdvice { 
-                        advice: ConstrainedString1000::try_from(unvalidated_emergency_fund_advice.advice.as_str())? 
+                        advice: unvalidated_emergency_fund_advice.advice.as_deref().map(ConstrainedString1000::try_from).transpose()?,
+                        calculation: unvalidated_emergency_fund_advice.calculation.clone().map(EmergencyFundCalculation::try_from).transpose()?
                     }
                 ))
             }
@@ -108,7 +113,77 @@ pub struct WillAdvice {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct EmergencyFundAdvice {
-    pub advice: ConstrainedString1000
+    /// The adviser's own advice, used in place of the standard paragraph
+    pub advice: Option<ConstrainedString1000>,
+    pub calculation: Option<EmergencyFundCalculation>
+}
+
+/// What the client needs to keep in cash for emergencies, and the cash they hold outside Cash ISAs.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EmergencyFundCalculation {
+    monthly_essential_expenditure: ConstrainedMoneyAmountLarge,
+    target_months: u32,
+    other_cash: ConstrainedMoneyAmountLarge
+}
+
+impl TryFrom<EmergencyFundCalculationDto> for EmergencyFundCalculation {
+    type Error = String;
+
+    fn try_from(dto: EmergencyFundCalculationDto) -> Result<Self, Self::Error> {
+        if !(1..=24).contains(&dto.target_months) {
+            return Err("An emergency fund must cover between 1 and 24 months.".to_string());
+        }
+
+        Ok(Self {
+            monthly_essential_expenditure: dto.monthly_essential_expenditure.try_into()?,
+            target_months: dto.target_months,
+            other_cash: dto.other_cash.try_into()?
+        })
+    }
+}
+
+impl EmergencyFundCalculation {
+    pub fn monthly_essential_expenditure(&self) -> f64 {
+        self.monthly_essential_expenditure.value()
+    }
+
+    pub fn target_months(&self) -> u32 {
+        self.target_months
+    }
+
+    /// Compares the cash the client needs with what they hold, including the value of their
+    /// Cash ISAs.
+    pub fn assess(&self, cash_isas: f64) -> EmergencyFundAssessment {
+        EmergencyFundAssessment {
+            target: self.monthly_essential_expenditure.value() * f64::from(self.target_months),
+            cash_isas,
+            other_cash: self.other_cash.value()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmergencyFundAssessment {
+    pub target: f64,
+    pub cash_isas: f64,
+    pub other_cash: f64
+}
+
+impl EmergencyFundAssessment {
+    pub fn held(&self) -> f64 {
+        self.cash_isas + self.other_cash
+    }
+
+    /// How far the cash held falls short of the target, or nothing if it is covered.
+    pub fn shortfall(&self) -> f64 {
+        (self.target - self.held()).max(0.0)
+    }
+
+    /// How much the cash held exceeds the target by, or nothing if it falls short.
+    pub fn excess(&self) -> f64 {
+        (self.held() - self.target).max(0.0)
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -122,4 +197,39 @@ pub struct PoaAdvice {
 pub struct OtherAdvice {
     pub advice_description: ConstrainedString20,
     pub advice: ConstrainedString1000
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driving::data_transfer_object::report_type_data_transfer_object::advice_areas::EmergencyFundAdvice as EmergencyFundAdviceDto;
+
+    fn calculation(target_months: u32) -> EmergencyFundCalculationDto {
+        EmergencyFundCalculationDto { monthly_essential_expenditure: 2500.0, target_months, other_cash: 4000.0 }
+    }
+
+    #[test]
+    fn test_emergency_fund_shortfall_and_excess() {
+        let calculation = EmergencyFundCalculation::try_from(calculation(6)).unwrap();
+
+        let short = calculation.assess(6000.0);
+        assert_eq!(short.target, 15000.0);
+        assert_eq!(short.held(), 10000.0);
+        assert_eq!(short.shortfall(), 5000.0);
+        assert_eq!(short.excess(), 0.0);
+
+        let covered = calculation.assess(20000.0);
+        assert_eq!(covered.shortfall(), 0.0);
+        assert_eq!(covered.excess(), 9000.0);
+    }
+
+    #[test]
+    fn test_emergency_fund_needs_advice_or_calculation() {
+        let neither = AdviceAreaDto::EmergencyFund(EmergencyFundAdviceDto { advice: None, calculation: None });
+        let calculated = AdviceAreaDto::EmergencyFund(EmergencyFundAdviceDto { advice: None, calculation: Some(calculation(6)) });
+
+        assert!(AdviceArea::try_from(&neither).unwrap_err().contains("either advice or a calculation"));
+        assert!(AdviceArea::try_from(&calculated).is_ok());
+        assert!(EmergencyFundCalculation::try_from(calculation(0)).is_err());
+    }
+}
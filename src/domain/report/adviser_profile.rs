@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::constrained_types::{constrained_string_200::ConstrainedString200, email_address::EmailAddress, fca_reference_number::FcaReferenceNumber, name_string::NameString, phone_number::PhoneNumber};
+use crate::domain::traits::Entity;
+use crate::driving::data_transfer_object::report_type_data_transfer_object::adviser_data_transfer_object::AdviserProfileDto;
+
+/// An adviser's directory entry: the credentials and contact details printed on reports.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AdviserProfile {
+    id: String,
+    first_name: NameString,
+    last_name: NameString,
+    qualifications: Vec<ConstrainedString200>,
+    fca_reference_number: FcaReferenceNumber,
+    phone: PhoneNumber,
+    email: EmailAddress,
+    office: ConstrainedString200,
+    signature_image_location: Option<ConstrainedString200>
+}
+
+impl Entity for AdviserProfile {}
+
+impl AdviserProfile {
+    pub fn id(&self) -> &String { &self.id }
+    pub fn first_name(&self) -> &NameString { &self.first_name }
+    pub fn last_name(&self) -> &NameString { &self.last_name }
+    pub fn qualifications(&self) -> &Vec<ConstrainedString200> { &self.qualifications }
+    pub fn fca_reference_number(&self) -> &FcaReferenceNumber { &self.fca_reference_number }
+    pub fn phone(&self) -> &PhoneNumber { &self.phone }
+    pub fn email(&self) -> &EmailAddress { &self.email }
+    pub fn office(&self) -> &ConstrainedString200 { &self.office }
+    pub fn signature_image_location(&self) -> Option<&ConstrainedString200> { self.signature_image_location.as_ref() }
+
+    /// Post-nominal qualifications as printed after the adviser's name, e.g. "Chartered Financial Planner, APFS".
+    pub fn qualifications_line(&self) -> Option<String> {
+        if self.qualifications.is_empty() {
+            None
+        } else {
+            Some(self.qualifications.iter().map(|q| q.value().as_str()).collect::<Vec<_>>().join(", "))
+        }
+    }
+
+    pub fn is_named(&self, first_name: &NameString, last_name: &NameString) -> bool {
+        self.first_name.value().eq_ignore_ascii_case(first_name.value())
+            && self.last_name.value().eq_ignore_ascii_case(last_name.value())
+    }
+}
+
+impl TryFrom<AdviserProfileDto> for AdviserProfile {
+    type Error = String;
+
+    fn try_from(dto: AdviserProfileDto) -> Result<Self, Self::Error> {
+        if dto.id.trim().is_empty() {
+            return Err("An adviser id cannot be empty".to_string());
+        }
+
+        let qualifications = dto.qualifications
+            .into_iter()
+            .map(ConstrainedString200::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            id: dto.id,
+            first_name: NameString::try_from(dto.first_name)?,
+            last_name: NameString::try_from(dto.last_name)?,
+            qualifications,
+            fca_reference_number: FcaReferenceNumber::try_from(dto.fca_reference_number)?,
+            phone: PhoneNumber::try_from(dto.phone)?,
+            email: EmailAddress::try_from(dto.email)?,
+            office: ConstrainedString200::try_from(dto.office)?,
+            signature_image_location: dto.signature_image_location.map(ConstrainedString200::try_from).transpose()?
+        })
+    }
+}
@@ -1,6 +1,9 @@
 use std::fmt;
 use serde::{Deserialize, Serialize};
 
+use crate::driven::doc_generator::instructions::BackgroundInstructions;
+use super::contents_section::SectionId;
+
 use crate::domain::constrained_types::{constrained_string_200::ConstrainedString200, name_string::NameString};
 use super::{couple_annual_review_report::couple_annual_review_report_background_section::CoupleAnnualReviewReportBackgroundSection, couple_new_report::couple_new_report_background_section::CoupleNewReportBackgroundSection};
 use super::individual_annual_review_report::individual_annual_review_report_background_section::IndividualAnnualReviewReportBackgroundSection;
@@ -14,6 +17,26 @@ pub enum BackgroundSection {
     CoupleNewReportBackgroundSection(CoupleNewReportBackgroundSection)
 }
 
+impl BackgroundSection {
+    pub fn instructions(&self, title: String) -> BackgroundInstructions {
+        let text = match self {
+            BackgroundSection::CoupleAnnualReviewReportBackgroundSection(background) => background.text(),
+            BackgroundSection::IndividualAnnualReviewBackgroundSection(background) => background.text(),
+            BackgroundSection::CoupleNewReportBackgroundSection(background) => background.text()
+        };
+
+        BackgroundInstructions {
+            anchor: SectionId::Background.anchor().to_string(),
+            title,
+            paragraphs: text
+                .split('\n')
+                .filter(|paragraph| !paragraph.trim().is_empty())
+                .map(str::to_string)
+                .collect()
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(tag = "type")]
 pub enum MeetingLocation{
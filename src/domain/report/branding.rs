@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+use crate::driven::doc_generator::instructions::{BrandingInstructions, ColourThemeInstructions};
+
+use crate::domain::constrained_types::{constrained_string_1000::ConstrainedString1000, constrained_string_200::ConstrainedString200, hex_colour::HexColour, s3_location::S3Location};
+use crate::driven::config::{ColourThemeConfig, FirmBrandingConfig};
+
+/// Firm identity printed on every report so documents can be white-labelled per firm.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FirmBranding {
+    logo_location: S3Location,
+    firm_name: ConstrainedString200,
+    registered_address: Vec<ConstrainedString200>,
+    regulatory_disclosure: ConstrainedString1000,
+    colour_theme: ColourTheme
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ColourTheme {
+    primary: HexColour,
+    secondary: HexColour,
+    text: HexColour
+}
+
+impl FirmBranding {
+    pub fn logo_location(&self) -> &S3Location { &self.logo_location }
+    pub fn firm_name(&self) -> &ConstrainedString200 { &self.firm_name }
+    pub fn registered_address(&self) -> &Vec<ConstrainedString200> { &self.registered_address }
+    pub fn regulatory_disclosure(&self) -> &ConstrainedString1000 { &self.regulatory_disclosure }
+    pub fn colour_theme(&self) -> &ColourTheme { &self.colour_theme }
+
+    pub fn instructions(&self) -> BrandingInstructions {
+        BrandingInstructions {
+            logo_location: self.logo_location.value().to_string(),
+            firm_name: self.firm_name.to_string(),
+            registered_address: self.registered_address.iter().map(|line| line.to_string()).collect(),
+            regulatory_disclosure: self.regulatory_disclosure.to_string(),
+            colour_theme: ColourThemeInstructions {
+                primary: self.colour_theme.primary.to_string(),
+                secondary: self.colour_theme.secondary.to_string(),
+                text: self.colour_theme.text.to_string()
+            }
+        }
+    }
+}
+
+impl ColourTheme {
+    pub fn primary(&self) -> &HexColour { &self.primary }
+    pub fn secondary(&self) -> &HexColour { &self.secondary }
+    pub fn text(&self) -> &HexColour { &self.text }
+}
+
+impl TryFrom<ColourThemeConfig> for ColourTheme {
+    type Error = String;
+
+    fn try_from(config: ColourThemeConfig) -> Result<Self, Self::Error> {
+        Ok(Self {
+            primary: HexColour::try_from(config.primary).map_err(|e| format!("Primary colour: {}", e))?,
+            secondary: HexColour::try_from(config.secondary).map_err(|e| format!("Secondary colour: {}", e))?,
+            text: HexColour::try_from(config.text).map_err(|e| format!("Text colour: {}", e))?
+        })
+    }
+}
+
+impl TryFrom<FirmBrandingConfig> for FirmBranding {
+    type Error = String;
+
+    fn try_from(config: FirmBrandingConfig) -> Result<Self, Self::Error> {
+        if config.registered_address.is_empty() {
+            return Err("Registered address must have at least one line".to_string());
+        }
+
+        let registered_address = config.registered_address
+            .into_iter()
+            .map(ConstrainedString200::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Registered address: {}", e))?;
+
+        Ok(Self {
+            logo_location: S3Location::try_from(config.logo_location).map_err(|e| format!("Logo location: {}", e))?,
+            firm_name: ConstrainedString200::try_from(config.firm_name).map_err(|e| format!("Firm name: {}", e))?,
+            registered_address,
+            regulatory_disclosure: ConstrainedString1000::try_from(config.regulatory_disclosure).map_err(|e| format!("Regulatory disclosure: {}", e))?,
+            colour_theme: ColourTheme::try_from(config.colour_theme)?
+        })
+    }
+}
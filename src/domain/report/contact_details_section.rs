@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::driven::doc_generator::instructions::ContactDetailsInstructions;
+
+use super::adviser_profile::AdviserProfile;
+use super::contents_section::SectionId;
+
+/// Closing section giving the adviser's credentials and how to get in touch.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactDetailsSection {
+    title: String,
+    introduction_paragraph: String,
+    adviser_name: String,
+    adviser_qualifications: Option<String>,
+    fca_reference_paragraph: String,
+    phone: String,
+    email: String,
+    office: String,
+    signature_image_location: Option<String>
+}
+
+impl ContactDetailsSection {
+    pub fn new(adviser_profile: &AdviserProfile, title: String) -> Self {
+        Self {
+            title,
+            introduction_paragraph: "If you have any questions about this report or your circumstances change, please do not hesitate to contact me.".to_string(),
+            adviser_name: format!("{} {}", adviser_profile.first_name(), adviser_profile.last_name()),
+            adviser_qualifications: adviser_profile.qualifications_line(),
+            fca_reference_paragraph: format!("FCA reference number: {}", adviser_profile.fca_reference_number()),
+            phone: adviser_profile.phone().to_string(),
+            email: adviser_profile.email().to_string(),
+            office: adviser_profile.office().to_string(),
+            signature_image_location: adviser_profile.signature_image_location().map(|location| location.to_string())
+        }
+    }
+
+    pub fn instructions(&self) -> ContactDetailsInstructions {
+        ContactDetailsInstructions {
+            anchor: SectionId::ContactDetails.anchor().to_string(),
+            title: self.title.clone(),
+            introduction_paragraph: self.introduction_paragraph.clone(),
+            adviser_name: self.adviser_name.clone(),
+            adviser_qualifications: self.adviser_qualifications.clone(),
+            fca_reference_paragraph: self.fca_reference_paragraph.clone(),
+            phone: self.phone.clone(),
+            email: self.email.clone(),
+            office: self.office.clone(),
+            signature_image_location: self.signature_image_location.clone()
+        }
+    }
+}
@@ -1,5 +1,9 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::driven::doc_generator::instructions::{ContentsEntryInstructions, ContentsInstructions};
+
 use super::ReportError;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -9,63 +13,190 @@ pub enum ContentsSection {
     NewReportContentsSection(NewReportContentsSection)
 }
 
-pub enum ContentsType {
-    AnnualReviewReportContentsSection,
-    NewReportContentsSection
+/// Stable identifier for a report section. The id doubles as the section's anchor in rendered
+/// documents, so it must not change once published.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum SectionId {
+    Background,
+    CurrentCircumstances,
+    Recommendations,
+    ContactDetails
+}
+
+impl SectionId {
+    pub fn anchor(&self) -> &'static str {
+        match self {
+            SectionId::Background => "background",
+            SectionId::CurrentCircumstances => "current-circumstances",
+            SectionId::Recommendations => "recommendations",
+            SectionId::ContactDetails => "contact-details"
+        }
+    }
+
+    pub fn default_title(&self) -> &'static str {
+        match self {
+            SectionId::Background => "Background",
+            SectionId::CurrentCircumstances => "Current circumstances and objectives",
+            SectionId::Recommendations => "Recommendations",
+            SectionId::ContactDetails => "Contact details"
+        }
+    }
+}
+
+/// Firm overrides for section titles and the order sections are listed in.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentsConfig {
+    #[serde(default)]
+    titles: HashMap<SectionId, String>,
+    #[serde(default)]
+    order: Vec<SectionId>
 }
 
-fn fetch_contents(content_section_type: ContentsType) -> Result<Vec<String>, String>{
-    
-    // TODO - will query database to retrieve contents held externally.
-    
-    match content_section_type {
-        ContentsType::AnnualReviewReportContentsSection => {
-            Ok(vec![
-                "Executive summary".to_string(),
-                "Background".to_string(),
-                "Current circumstances and objectives".to_string(),
-                "Recommendations".to_string(),
-                "Investment strategy".to_string(),
-                "Risks and disadvantages".to_string(),
-                "Charges".to_string(),
-                "Next steps".to_string()
-            ])
+impl ContentsConfig {
+    pub fn new(titles: HashMap<SectionId, String>, order: Vec<SectionId>) -> Result<Self, String> {
+        if let Some((id, _)) = titles.iter().find(|(_, title)| title.trim().is_empty()) {
+            return Err(format!("Title override for section '{}' cannot be empty", id.anchor()));
         }
-        ContentsType::NewReportContentsSection => {
-            Ok(vec![
-                "Executive summary".to_string(),
-                "Background".to_string(),
-                "Current Circumstances".to_string(),
-                "Objectives".to_string(),
-                "Investment risk assessment".to_string(),
-                "Recommendations".to_string(),
-                "Investment strategy".to_string(),
-                "Risks and disadvantages".to_string(),
-                "Charges".to_string(),
-                "Next steps".to_string()
-            ])
+
+        for (index, id) in order.iter().enumerate() {
+            if order[..index].contains(id) {
+                return Err(format!("Section '{}' appears more than once in the contents order", id.anchor()));
+            }
+        }
+
+        Ok(Self { titles, order })
+    }
+
+    pub fn title(&self, id: SectionId) -> String {
+        self.titles
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| id.default_title().to_string())
+    }
+
+    /// Sections named in the configured order come first, followed by the rest in report order.
+    fn ordered(&self, present: &[SectionId]) -> Vec<SectionId> {
+        let mut ordered: Vec<SectionId> = self.order
+            .iter()
+            .filter(|id| present.contains(id))
+            .copied()
+            .collect();
+
+        ordered.extend(present.iter().filter(|id| !self.order.contains(id)));
+        ordered
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentsEntry {
+    id: SectionId,
+    title: String,
+    anchor: String
+}
+
+fn build_contents(present: &[SectionId], config: &ContentsConfig) -> Result<Vec<ContentsEntry>, String> {
+    if present.is_empty() {
+        return Err("A report must contain at least one section".to_string());
+    }
+
+    Ok(config
+        .ordered(present)
+        .into_iter()
+        .map(|id| ContentsEntry {
+            id,
+            title: config.title(id),
+            anchor: id.anchor().to_string()
+        })
+        .collect())
+}
+
+impl ContentsSection {
+    fn entries(&self) -> &[ContentsEntry] {
+        match self {
+            ContentsSection::AnnualReviewReportContentsSection(contents) => &contents.0,
+            ContentsSection::NewReportContentsSection(contents) => &contents.0
+        }
+    }
+
+    /// The title the section is listed under.
+    pub fn title(&self, id: SectionId) -> String {
+        self.entries()
+            .iter()
+            .find(|entry| entry.id == id)
+            .map(|entry| entry.title.clone())
+            .unwrap_or_else(|| id.default_title().to_string())
+    }
+
+    pub fn instructions(&self) -> ContentsInstructions {
+        ContentsInstructions {
+            entries: self.entries()
+                .iter()
+                .map(|entry| ContentsEntryInstructions {
+                    id: entry.id.anchor().to_string(),
+                    title: entry.title.clone(),
+                    anchor: entry.anchor.clone()
+                })
+                .collect()
         }
     }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct AnnualReviewReportContentsSection(Vec<String>);
+pub struct AnnualReviewReportContentsSection(Vec<ContentsEntry>);
 
 impl AnnualReviewReportContentsSection {
-    pub fn new() -> Result<Self, ReportError> {
-        Ok(Self(fetch_contents(ContentsType::AnnualReviewReportContentsSection)
+    pub fn new(present: &[SectionId], config: &ContentsConfig) -> Result<Self, ReportError> {
+        Ok(Self(build_contents(present, config)
             .map_err(|e|ReportError::SectionValidationError("Contents Section".to_string(), e))?
         ))
     }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct NewReportContentsSection(Vec<String>);
+pub struct NewReportContentsSection(Vec<ContentsEntry>);
 
 impl NewReportContentsSection {
-    pub fn new() -> Result<Self, ReportError> {
-        Ok(Self(fetch_contents(ContentsType::NewReportContentsSection)
+    pub fn new(present: &[SectionId], config: &ContentsConfig) -> Result<Self, ReportError> {
+        Ok(Self(build_contents(present, config)
             .map_err(|e|ReportError::SectionValidationError("Contents Section".to_string(), e))?
         ))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contents_lists_only_present_sections() {
+        let contents = build_contents(&[SectionId::Background, SectionId::ContactDetails], &ContentsConfig::default()).unwrap();
+        assert_eq!(contents, vec![
+            ContentsEntry { id: SectionId::Background, title: "Background".to_string(), anchor: "background".to_string() },
+            ContentsEntry { id: SectionId::ContactDetails, title: "Contact details".to_string(), anchor: "contact-details".to_string() }
+        ]);
+    }
+
+    #[test]
+    fn test_contents_applies_overrides() {
+        let config = ContentsConfig::new(
+            HashMap::from([(SectionId::Recommendations, "Our advice".to_string())]),
+            vec![SectionId::Recommendations, SectionId::ContactDetails]
+        ).unwrap();
+
+        let contents = build_contents(&[SectionId::Background, SectionId::CurrentCircumstances, SectionId::Recommendations], &config).unwrap();
+        let ids: Vec<SectionId> = contents.iter().map(|entry| entry.id).collect();
+        assert_eq!(ids, vec![SectionId::Recommendations, SectionId::Background, SectionId::CurrentCircumstances]);
+        assert_eq!(contents[0].title, "Our advice");
+        assert_eq!(contents[0].anchor, "recommendations");
+    }
+
+    #[test]
+    fn test_invalid_contents_config() {
+        assert!(ContentsConfig::new(HashMap::new(), vec![SectionId::Background, SectionId::Background]).is_err());
+        assert!(ContentsConfig::new(HashMap::from([(SectionId::Background, " ".to_string())]), vec![]).is_err());
+        assert!(build_contents(&[], &ContentsConfig::default()).is_err());
+    }
+}
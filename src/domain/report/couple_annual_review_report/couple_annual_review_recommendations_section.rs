@@ -1,36 +1,51 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
+use chrono::{Datelike, Months, NaiveDate};
 use serde::{Deserialize, Serialize};
 
+use crate::domain::clock::Clock;
 use crate::domain::constrained_types::name_string::NameString; 
 use crate::domain::constrained_types::tax_year;
-use crate::domain::report::advice_areas::{AdviceArea, OtherAdvice};
-use crate::domain::report::investment_holdings::InvestmentPortfolio;
+use crate::domain::projection::{MarketAssumption, ProjectionAssumptions};
+use crate::domain::projection::cash_flow::IncomeProjectionBasis;
+use crate::domain::projection::monte_carlo;
+use crate::domain::report::advice_areas::{AdviceArea, EmergencyFundAdvice, OtherAdvice};
+use crate::domain::report::contents_section::SectionId;
 use crate::domain::report::objectives::{self, CoupleObjectivesAnnualReview, ObjectiveType};
-use crate::domain::report::product::{AccountOrReferenceNumberType, AccountType, CanBeJointlyOwnedAccountType, ExistingJointlyOwnedProduct, ExistingNewJointSingleProduct, ExistingProduct, NewProduct, PlatformAccountNumberType, ProductRetention, Provider, Providers, RecommendedAction, Replace, SingleContribution};
-use crate::domain::report::recommendations_section::{AdviceAreasAndProducts, CoupleAdviceAreasAndProducts};
+use crate::domain::report::product::{AccountOrReferenceNumberType, AccountType, CanBeJointlyOwnedAccountType, ExistingJointlyOwnedProduct, ExistingNewJointSingleProduct, ExistingProduct, ChargeableGainsBasis, ExistingSingleOwnedProduct, GeneralInvestmentAccount, NewProduct, PlatformAccountNumberType, ProductRetention, Products, Provider, Providers, RecommendedAction, RegularWithdrawal, Replace, SingleContribution};
+use crate::domain::report::recommendations_section::{AdviceAreasAndProducts, CoupleAdviceAreasAndProducts, IhtEstateBasis};
+use crate::domain::report::wording_templates::{TemplateName, WordingTemplates};
 use crate::domain::report::{advice_areas, ReportError};
-use crate::driven::repository::InvestmentPortfoliosRepository;
-use crate::driving::data_transfer_object::report_type_data_transfer_object::advice_areas_and_products_dto::CoupleAdviceAreasAndProductsDto;
+use crate::domain::constrained_types::tax_year::TaxYear;
+use crate::domain::tax::TaxRules;
+use crate::domain::tax::chargeable_gains::{BondSitus, ChargeableGainCalculation};
+use crate::domain::tax::inheritance_tax::{self, Estate, Gift, InheritanceTaxEstimate};
+use crate::domain::tax::isa_allowance::{IsaAllowance, IsaAllowanceLedger};
+use crate::domain::tax::pension_allowance::PensionAllowanceCheck;
+use crate::driven::doc_generator::instructions::{AccountNumberInstructions, AdviceInstructions, ClientAdviceInstructions, ClientProductsInstructions, ExistingProductInstructions, NewProductInstructions, ProductActionInstructions, ProductInstructions, RecommendationsInstructions, TableInstructions};
+use crate::helpers::general_helpers::{money, probability};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CoupleAnnualReviewReportRecommendationsSection {
     introductory_paragraph: String,
     product_recommendations: HashMap<String, Vec<ProductRecommendationsText>>,
-    other_advice_areas: HashMap<String, Vec<(String, String)>> 
+    other_advice_areas: HashMap<String, Vec<OtherAdviceText>> 
 }
 
 impl CoupleAnnualReviewReportRecommendationsSection {
-    pub async fn new<R>(
+    pub fn new(
         client_1_first_name: &NameString,
         client_2_first_name: &NameString,
         client_1_last_name: &NameString,
         client_2_last_name: &NameString,
-        unvalidated_couple_advice_areas_products: CoupleAdviceAreasAndProductsDto,
+        validated_couple_advice_areas_and_products: CoupleAdviceAreasAndProducts,
         objectives: &CoupleObjectivesAnnualReview,
-        repo: &R
-    ) -> Result<CoupleAnnualReviewReportRecommendationsSection, (String, String)> where R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync  {
+        templates: &WordingTemplates,
+        tax_rules: &TaxRules,
+        projection_assumptions: &ProjectionAssumptions,
+        clock: &dyn Clock
+    ) -> Result<CoupleAnnualReviewReportRecommendationsSection, (String, String)> {
 
         let error_section_string = "Recommendations".to_string();
 
@@ -39,8 +54,8 @@ impl CoupleAnnualReviewReportRecommendationsSection {
         }
 
         let objectives_by_id = objectives.objectives_by_id();
-        let introductory_paragraph = String::from("This section will present my recommendations for each of your accounts, as well as other advice areas we discussed and those I have subsequently reviewed.");
-        let validated_couple_advice_areas_and_products = CoupleAdviceAreasAndProducts::from_dto(unvalidated_couple_advice_areas_products, repo).await.map_err(|error| (error_section_string.to_string(), error))?;
+        let married = validated_couple_advice_areas_and_products.married_or_in_civil_partnership == Some(true);
+        let introductory_paragraph = templates.render(TemplateName::RecommendationsIntroduction, &[]);
         let client_1_advice_area_products = validated_couple_advice_areas_and_products.client_1;
         let client_2_advice_area_products = validated_couple_advice_areas_and_products.client_2;
         let joint_advice_area_products = validated_couple_advice_areas_and_products.joint;
@@ -51,15 +66,68 @@ impl CoupleAnnualReviewReportRecommendationsSection {
         let client_1_key = format!("{} {}", client_1_first_name, client_1_last_name);
         let client_2_key = format!("{} {}", client_2_first_name, client_2_last_name);
 
-        create_other_advice_areas(&mut other_advice_areas, &client_1_key, &client_1_advice_area_products);
-        create_other_advice_areas(&mut other_advice_areas, &client_2_key, &client_2_advice_area_products);
-        create_other_advice_areas(&mut other_advice_areas, "Joint", &joint_advice_area_products);
+        create_other_advice_areas(&mut other_advice_areas, &client_1_key, &client_1_advice_area_products, templates);
+        create_other_advice_areas(&mut other_advice_areas, &client_2_key, &client_2_advice_area_products, templates);
+        create_other_advice_areas(&mut other_advice_areas, "Joint", &joint_advice_area_products, templates);
+
+        let current_tax_year = TaxYear::containing(clock.today());
+        let isa_allowance_texts = create_isa_allowance_texts(
+            [(&client_1_key, &client_1_advice_area_products), (&client_2_key, &client_2_advice_area_products)],
+            templates,
+            tax_rules,
+            &current_tax_year
+        )
+            .map_err(|error| (error_section_string.clone(), error))?;
+        for (client_key, isa_allowance_text) in isa_allowance_texts {
+            other_advice_areas
+                .entry(client_key)
+                .or_insert_with(Vec::new)
+                .push(isa_allowance_text);
+        }
+
+        for (client_key, advice_area_products) in [(&client_1_key, &client_1_advice_area_products), (&client_2_key, &client_2_advice_area_products)] {
+            let pension_allowance_texts = create_pension_allowance_texts(advice_area_products, templates, tax_rules, &current_tax_year)
+                .map_err(|error| (error_section_string.clone(), error))?;
+            if !pension_allowance_texts.is_empty() {
+                other_advice_areas
+                    .entry(client_key.to_string())
+                    .or_insert_with(Vec::new)
+                    .extend(pension_allowance_texts);
+            }
+        }
+
+        for (client_key, advice_area_products) in [(client_1_key.as_str(), &client_1_advice_area_products), (client_2_key.as_str(), &client_2_advice_area_products), ("Joint", &joint_advice_area_products)] {
+            let range_of_outcomes_texts = create_range_of_outcomes_texts(advice_area_products, &objectives_by_id, templates, projection_assumptions, clock.today().year());
+            if !range_of_outcomes_texts.is_empty() {
+                other_advice_areas
+                    .entry(client_key.to_string())
+                    .or_insert_with(Vec::new)
+                    .extend(range_of_outcomes_texts);
+            }
+        }
+
+        let inheritance_tax_texts = create_inheritance_tax_texts(
+            [(&client_1_key, &client_1_advice_area_products), (&client_2_key, &client_2_advice_area_products)],
+            &joint_advice_area_products,
+            married,
+            templates,
+            tax_rules
+        )
+            .map_err(|error| (error_section_string.clone(), error))?;
+        for (client_key, inheritance_tax_text) in inheritance_tax_texts {
+            other_advice_areas
+                .entry(client_key)
+                .or_insert_with(Vec::new)
+                .push(inheritance_tax_text);
+        }
 
         create_product_recommendations(
             &mut product_recommendations_text_by_client, 
             &client_1_key, 
             &client_1_advice_area_products,
-            &objectives_by_id
+            &objectives_by_id,
+            templates,
+            tax_rules
         )
             .map_err(|error| (error_section_string.clone(), error))?;
         
@@ -67,7 +135,9 @@ impl CoupleAnnualReviewReportRecommendationsSection {
             &mut product_recommendations_text_by_client, 
             &client_2_key, 
             &client_2_advice_area_products,
-            &objectives_by_id
+            &objectives_by_id,
+            templates,
+            tax_rules
         )
             .map_err(|error| (error_section_string.clone(), error))?;
 
@@ -75,7 +145,9 @@ impl CoupleAnnualReviewReportRecommendationsSection {
             &mut product_recommendations_text_by_client, 
             "Joint", 
             &joint_advice_area_products,
-            &objectives_by_id
+            &objectives_by_id,
+            templates,
+            tax_rules
         )
             .map_err(|error| (error_section_string.clone(), error))?;
         
@@ -86,6 +158,66 @@ impl CoupleAnnualReviewReportRecommendationsSection {
             other_advice_areas
         })
     }
+
+    pub fn instructions(&self, title: String) -> RecommendationsInstructions {
+        RecommendationsInstructions {
+            anchor: SectionId::Recommendations.anchor().to_string(),
+            title,
+            introductory_paragraph: self.introductory_paragraph.clone(),
+            products: clients_in_order(&self.product_recommendations)
+                .into_iter()
+                .map(|client| ClientProductsInstructions {
+                    client: client.clone(),
+                    products: self.product_recommendations[client].iter().map(ProductRecommendationsText::instructions).collect()
+                })
+                .collect(),
+            other_advice: clients_in_order(&self.other_advice_areas)
+                .into_iter()
+                .map(|client| ClientAdviceInstructions {
+                    client: client.clone(),
+                    advice: self.other_advice_areas[client]
+                        .iter()
+                        .map(OtherAdviceText::instructions)
+                        .collect()
+                })
+                .collect()
+        }
+    }
+}
+
+/// Clients by name, with joint recommendations last.
+fn clients_in_order<T>(by_client: &HashMap<String, T>) -> Vec<&String> {
+    let mut clients: Vec<&String> = by_client.keys().collect();
+    clients.sort_by_key(|client| (client.as_str() == "Joint", client.as_str()));
+    clients
+}
+
+fn account_number_instructions(scheme: &str, number: String) -> AccountNumberInstructions {
+    AccountNumberInstructions { scheme: scheme.to_string(), number }
+}
+
+/// Advice outside the client's products, with any table of figures supporting it.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct OtherAdviceText {
+    title: String,
+    paragraph: String,
+    table: Option<AdviceTable>
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AdviceTable {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>
+}
+
+impl OtherAdviceText {
+    fn instructions(&self) -> AdviceInstructions {
+        AdviceInstructions {
+            title: self.title.clone(),
+            paragraph: self.paragraph.clone(),
+            table: self.table.as_ref().map(|table| TableInstructions { header: table.header.clone(), rows: table.rows.clone() })
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -94,6 +226,31 @@ pub enum ProductRecommendationsText {
    New(NewProductRecommendationsText) 
 }
 
+impl ProductRecommendationsText {
+    fn instructions(&self) -> ProductInstructions {
+        match self {
+            ProductRecommendationsText::Existing(product) => ProductInstructions::Existing(ExistingProductInstructions {
+                product_title: product.product_title.clone(),
+                platform_number: product.platform_number.as_ref().map(|number| account_number_instructions(number.scheme(), number.to_string())),
+                account_or_reference_number: account_number_instructions(product.account_or_refence_number.scheme(), product.account_or_refence_number.to_string()),
+                retention_sentence: product.product_retention_sentence.clone(),
+                rationale: product.rationale.clone(),
+                actions: product.actions
+                    .iter()
+                    .flatten()
+                    .map(|action| ProductActionInstructions { title: action.action_title.clone(), paragraph: action.action_paragraph.clone() })
+                    .collect()
+            }),
+            ProductRecommendationsText::New(product) => ProductInstructions::New(NewProductInstructions {
+                product_title: product.product_title.clone(),
+                platform_number: product.platform_number.as_ref().map(|number| account_number_instructions(number.scheme(), number.to_string())),
+                initial_sentence: product.new_product_initial_sentence.clone(),
+                rationale: product.rationale.clone()
+            })
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ExistingProductRecommendationsText {
     platform_number: Option<PlatformAccountNumberType>,
@@ -121,38 +278,611 @@ pub struct ProductActionsText {
 }
 
 fn create_other_advice_areas(
-    other_advice_areas: &mut HashMap<String, Vec<(String, String)>>,
+    other_advice_areas: &mut HashMap<String, Vec<OtherAdviceText>>,
     key: &str,
-    advice_areas_and_products: &Option<AdviceAreasAndProducts>
+    advice_areas_and_products: &Option<AdviceAreasAndProducts>,
+    templates: &WordingTemplates
 ) {
     if let Some(advice_areas_and_products) = advice_areas_and_products {
         if let Some(advice_areas) = &advice_areas_and_products.advice_areas {
+            let cash_isas = cash_isa_holdings(&advice_areas_and_products.products);
             for advice_area in advice_areas.value() {
                 other_advice_areas
                     .entry(key.to_string())
                     .or_insert_with(Vec::new)
-                    .push(create_other_advice_area(advice_area));
+                    .push(create_other_advice_area(advice_area, cash_isas, templates));
+            }
+        }
+    }
+}
+
+/// The current value of the client's Cash ISAs, which count towards their emergency fund.
+fn cash_isa_holdings(products: &Option<Products>) -> f64 {
+    products
+        .iter()
+        .flat_map(Products::existing_products)
+        .filter(|product| matches!(product, ExistingProduct::SingleOwned(product) if matches!(product.account_type(), AccountType::CashIsa(_))))
+        .map(|product| product.current_value().value().value())
+        .sum()
+}
+
+
+/// States how much of each ISA allowance the recommended subscriptions leave in each tax year,
+/// rejecting recommendations that would exceed an allowance. Each client's own allowance is
+/// stated under their key. A child's Junior ISAs share one allowance whichever parent subscribes,
+/// so they are totalled across the couple and stated under the joint key.
+fn create_isa_allowance_texts(
+    clients: [(&str, &Option<AdviceAreasAndProducts>); 2],
+    templates: &WordingTemplates,
+    tax_rules: &TaxRules,
+    current_tax_year: &TaxYear
+) -> Result<Vec<(String, OtherAdviceText)>, String> {
+    let mut texts = Vec::new();
+    let mut junior_ledger = IsaAllowanceLedger::default();
+    for (key, advice_areas_and_products) in clients {
+        let Some(products) = advice_areas_and_products.as_ref().and_then(|advice_areas_and_products| advice_areas_and_products.products.as_ref()) else {
+            continue;
+        };
+
+        let mut ledger = IsaAllowanceLedger::default();
+        for (account_type, _, actions) in recommended_actions_by_product(products) {
+            let Some(allowance) = isa_allowance(&account_type) else {
+                continue;
+            };
+            let target = match allowance {
+                IsaAllowance::Adult => &mut ledger,
+                IsaAllowance::Junior(_) => &mut junior_ledger
+            };
+            for (tax_year, amount) in contributions_by_tax_year(&actions, current_tax_year)? {
+                target.subscribe(tax_year, allowance.clone(), amount);
+            }
+        }
+        if let Some(text) = create_isa_allowance_text(&ledger, "ISA Allowance", templates, tax_rules)? {
+            texts.push((key.to_string(), text));
+        }
+    }
+    if let Some(text) = create_isa_allowance_text(&junior_ledger, "Junior ISA Allowance", templates, tax_rules)? {
+        texts.push(("Joint".to_string(), text));
+    }
+
+    Ok(texts)
+}
+
+fn create_isa_allowance_text(
+    ledger: &IsaAllowanceLedger,
+    title: &str,
+    templates: &WordingTemplates,
+    tax_rules: &TaxRules
+) -> Result<Option<OtherAdviceText>, String> {
+    let paragraphs: Vec<String> = ledger
+        .usage(tax_rules)?
+        .into_iter()
+        .map(|usage| {
+            let tax_year = usage.tax_year.to_string();
+            let subscribed = money(usage.subscribed);
+            let limit = money(usage.limit);
+            let remaining = money(usage.remaining);
+            match &usage.allowance {
+                IsaAllowance::Adult => templates.render(
+                    TemplateName::IsaAllowanceRemaining,
+                    &[("tax_year", &tax_year), ("subscribed", &subscribed), ("allowance", &limit), ("remaining", &remaining)]
+                ),
+                IsaAllowance::Junior(child) => templates.render(
+                    TemplateName::JuniorIsaAllowanceRemaining,
+                    &[("child", child), ("tax_year", &tax_year), ("subscribed", &subscribed), ("allowance", &limit), ("remaining", &remaining)]
+                )
+            }
+        })
+        .collect();
+
+    if paragraphs.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(OtherAdviceText { title: title.to_string(), paragraph: paragraphs.join(" "), table: None }))
+}
+
+/// The allowance subscriptions to an account count against, if it is an ISA. A child's Junior
+/// ISAs share one allowance, whichever provider holds them.
+fn isa_allowance(account_type: &AccountType) -> Option<IsaAllowance> {
+    match account_type {
+        AccountType::IsaStocksAndShares(_) | AccountType::CashIsa(_) => Some(IsaAllowance::Adult),
+        AccountType::JuniorIsaStocksAndShares(jisa) => Some(IsaAllowance::Junior(jisa.child().to_string())),
+        _ => None
+    }
+}
+
+/// Checks the client's recommended pension contributions in each tax year against the
+/// allowances in their pension allowance basis for that year, with a table of any unused
+/// allowance carried forward. Tax years without a basis are not checked.
+fn create_pension_allowance_texts(
+    advice_areas_and_products: &Option<AdviceAreasAndProducts>,
+    templates: &WordingTemplates,
+    tax_rules: &TaxRules,
+    current_tax_year: &TaxYear
+) -> Result<Vec<OtherAdviceText>, String> {
+    let Some(advice_areas_and_products) = advice_areas_and_products else {
+        return Ok(Vec::new());
+    };
+    let (Some(products), Some(bases)) = (&advice_areas_and_products.products, &advice_areas_and_products.pension_allowances) else {
+        return Ok(Vec::new());
+    };
+
+    let mut contributions: BTreeMap<TaxYear, f64> = BTreeMap::new();
+    for (account_type, _, actions) in recommended_actions_by_product(products) {
+        if matches!(account_type, AccountType::SelfInvestedPersonalPension(_) | AccountType::PersonalPension(_)) {
+            for (tax_year, amount) in contributions_by_tax_year(&actions, current_tax_year)? {
+                *contributions.entry(tax_year).or_default() += amount;
+            }
+        }
+    }
+
+    contributions
+        .iter()
+        .filter_map(|(tax_year, amount)| bases.iter().find(|basis| basis.tax_year() == tax_year).map(|basis| (basis, amount)))
+        .map(|(basis, amount)| {
+            let check = basis.check(*amount, tax_rules)?;
+            create_pension_allowance_text(&check, basis.tax_year(), templates)
+        })
+        .collect()
+}
+
+fn create_pension_allowance_text(
+    check: &PensionAllowanceCheck,
+    tax_year: &TaxYear,
+    templates: &WordingTemplates
+) -> Result<OtherAdviceText, String> {
+    let tax_year_text = tax_year.to_string();
+    let contributions = money(check.contributions);
+    let allowance = money(check.annual_allowance);
+    let remaining = money(check.remaining);
+
+    let (paragraph, table) = if check.money_purchase_annual_allowance_applies {
+        (templates.render(
+            TemplateName::PensionMoneyPurchaseAllowanceRemaining,
+            &[("tax_year", &tax_year_text), ("contributions", &contributions), ("allowance", &allowance), ("remaining", &remaining)]
+        ), None)
+    } else if check.uses_carry_forward() {
+        let carried_forward = money(check.carry_forward.iter().map(|year| year.used).sum());
+        let rows = check.carry_forward
+            .iter()
+            .map(|year| Ok(vec![
+                tax_year.years_before(year.years_before as i32)?.to_string(),
+                money(year.unused),
+                money(year.used),
+                money(year.unused - year.used)
+            ]))
+            .collect::<Result<_, String>>()?;
+        (templates.render(
+            TemplateName::PensionAllowanceCarryForward,
+            &[("tax_year", &tax_year_text), ("contributions", &contributions), ("allowance", &allowance), ("carried_forward", &carried_forward), ("remaining", &remaining)]
+        ), Some(AdviceTable {
+            header: vec!["Tax year".to_string(), "Unused allowance".to_string(), "Carried forward".to_string(), "Still unused".to_string()],
+            rows
+        }))
+    } else {
+        (templates.render(
+            TemplateName::PensionAllowanceRemaining,
+            &[("tax_year", &tax_year_text), ("contributions", &contributions), ("allowance", &allowance), ("remaining", &remaining)]
+        ), None)
+    };
+
+    Ok(OtherAdviceText { title: "Pension Annual Allowance".to_string(), paragraph, table })
+}
+
+/// Simulates a range of outcomes for each regular withdrawal recommended from a product the
+/// client keeps, from its value in `current_year` after its recommended charges. Returns follow
+/// the assumptions for the product's recommended model portfolio or, for a bespoke portfolio,
+/// the risk profile of the first objective it is linked to; products with neither are left out.
+fn create_range_of_outcomes_texts(
+    advice_areas_and_products: &Option<AdviceAreasAndProducts>,
+    objectives_by_id: &HashMap<String, ObjectiveType>,
+    templates: &WordingTemplates,
+    assumptions: &ProjectionAssumptions,
+    current_year: i32
+) -> Vec<OtherAdviceText> {
+    let Some(products) = advice_areas_and_products.as_ref().and_then(|advice_areas_and_products| advice_areas_and_products.products.as_ref()) else {
+        return Vec::new();
+    };
+
+    let mut texts = Vec::new();
+    for product in products.existing_products() {
+        let ProductRetention::Retain(retain) = product.product_retention() else {
+            continue;
+        };
+        let portfolio = retain.recommended_investment_strategy().model_portfolio_id();
+        let risk_profile = portfolio.map(|portfolio| &portfolio.risk_profile).or_else(|| {
+            product.linked_objectives()?
+                .iter()
+                .find_map(|objective_id| objectives_by_id.get(&objective_id.to_string()))
+                .map(ObjectiveType::linked_risk_profile)
+        });
+        let Some(risk_profile) = risk_profile else {
+            continue;
+        };
+
+        let account = format!("{} {}", product.provider_as_string(), product.account_or_reference_number_as_string());
+        let starting_value = product.current_value().value().value();
+        let charge_rate = retain.recommended_product_charges().annual_rate();
+        let market = assumptions.market_assumption(risk_profile, portfolio);
+        for action in retain.recommendation_actions().iter().flatten() {
+            if let RecommendedAction::RegularWithdrawal(withdrawal) = action {
+                texts.extend(create_range_of_outcomes_text(&account, starting_value, charge_rate, market, withdrawal, templates, assumptions, current_year));
             }
         }
     }
+    texts
 }
 
+/// Withdrawals are level, and are projected for the assumed number of years unless they end
+/// sooner. The product is simulated from `current_year`, so it grows untouched until a later
+/// withdrawal starts; withdrawals that ended before then are left out.
+#[allow(clippy::too_many_arguments)]
+fn create_range_of_outcomes_text(
+    account: &str,
+    starting_value: f64,
+    charge_rate: f64,
+    market: MarketAssumption,
+    withdrawal: &RegularWithdrawal,
+    templates: &WordingTemplates,
+    assumptions: &ProjectionAssumptions,
+    current_year: i32
+) -> Option<OtherAdviceText> {
+    let annual_withdrawal = withdrawal.value().value() * f64::from(withdrawal.frequency().per_year());
+    let from_year = withdrawal.start_date_of_action().value().year();
+    let until_year = withdrawal
+        .end_date_of_action()
+        .map(|end_date| end_date.value().year())
+        .unwrap_or(from_year + assumptions.withdrawal_years() - 1);
+    if until_year < from_year || until_year < current_year {
+        return None;
+    }
+
+    let outcomes = monte_carlo::simulate(
+        &IncomeProjectionBasis {
+            starting_value,
+            annual_income: annual_withdrawal,
+            income_from: from_year,
+            first_year: current_year,
+            last_year: until_year,
+            growth_rate: market.growth_rate,
+            charge_rate,
+            inflation: 0.0
+        },
+        market,
+        assumptions.simulations(),
+        assumptions.seed()
+    );
+
+    let paragraph = templates.render(
+        TemplateName::WithdrawalRangeOfOutcomes,
+        &[
+            ("account", account),
+            ("withdrawal", &money(annual_withdrawal)),
+            ("from_year", &from_year.to_string()),
+            ("until_year", &until_year.to_string()),
+            ("probability", &probability(outcomes.probability_of_success)),
+            ("simulations", &outcomes.simulations.to_string())
+        ]
+    );
+    let rows = outcomes
+        .every(5)
+        .into_iter()
+        .map(|range| vec![range.year.to_string(), money(range.poor), money(range.median), money(range.good)])
+        .collect();
+
+    Some(OtherAdviceText {
+        title: "Range of Outcomes".to_string(),
+        paragraph,
+        table: Some(AdviceTable {
+            header: vec!["Year".to_string(), "Poor".to_string(), "Median".to_string(), "Good".to_string()],
+            rows
+        })
+    })
+}
+
+/// The actions recommended for each single-owned product the client keeps or opens, with the
+/// product's account type and the provider and account number it is known by.
+fn recommended_actions_by_product(products: &Products) -> Vec<(AccountType, String, Vec<RecommendedAction>)> {
+    let mut actions_by_product = Vec::new();
+
+    for existing_product in products.existing_products() {
+        if let ExistingProduct::SingleOwned(product) = &existing_product {
+            if let ProductRetention::Retain(retain) = product.product_retention() {
+                let account = format!("{} {}", product.provider_as_string(), product.account_or_reference_number_as_string());
+                actions_by_product.push((product.account_type().clone(), account, retain.recommendation_actions().clone().unwrap_or_default()));
+            }
+        }
+    }
+    for new_product in products.new_products() {
+        let account = match new_product.account_or_reference_number() {
+            Some(account_number) => format!("{} {}", new_product.provider_as_string(), account_number),
+            None => format!("new {}", new_product.provider_as_string())
+        };
+        actions_by_product.push((new_product.account_type().clone(), account, new_product.recommendation_actions().clone()));
+    }
+
+    actions_by_product
+}
+
+/// The single and regular contributions among `actions`, with how much each pays in during each
+/// tax year. A single contribution falls in its tax year of action, or the tax year of its date
+/// when it names none, and is rejected when it has neither. A regular contribution is spread over
+/// the tax years it is paid in, continuing past the current tax year when it has no end date.
+fn contributions_by_tax_year(actions: &[RecommendedAction], current_tax_year: &TaxYear) -> Result<Vec<(TaxYear, f64)>, String> {
+    let mut contributions = Vec::new();
+
+    for action in actions {
+        match action {
+            RecommendedAction::SingleContribution(single_contribution) => {
+                let tax_year = match (single_contribution.tax_year_of_action(), single_contribution.date_of_action()) {
+                    (Some(tax_year), _) => tax_year.clone(),
+                    (None, Some(date)) => TaxYear::containing(*date.value()),
+                    (None, None) => return Err(format!(
+                        "The single contribution \"{}\" needs a tax year or date of action to be checked against its allowance",
+                        single_contribution.executive_summary_description()
+                    ))
+                };
+                contributions.push((tax_year, single_contribution.value().value()));
+            }
+            RecommendedAction::RegularContribution(regular_contribution) => {
+                contributions.extend(regular_contribution.totals_by_tax_year(current_tax_year)?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(contributions)
+}
+
+fn create_other_advice_area(advice_area: &AdviceArea, cash_isas: f64, templates: &WordingTemplates) -> OtherAdviceText {
+    let paragraph = match advice_area {
+        AdviceArea::EmergencyFund(emergency_fund_advice) => return create_emergency_fund_text(advice_area.title(), emergency_fund_advice, cash_isas, templates),
+        AdviceArea::Iht(iht_advice) => iht_advice.advice.to_string(),
+        AdviceArea::Other(other_advice) => other_advice.advice.to_string(),
+        AdviceArea::Will(will_advice) => will_advice.advice.to_string(),
+        AdviceArea::Poa(poa_advice) => poa_advice.advice.to_string()
+    };
+
+    OtherAdviceText { title: advice_area.title(), paragraph, table: None }
+}
+
+/// The standard emergency fund paragraph with a table of the figures behind it, when there is a
+/// calculation. The adviser's own advice, when given, replaces the standard paragraph.
+fn create_emergency_fund_text(title: String, emergency_fund_advice: &EmergencyFundAdvice, cash_isas: f64, templates: &WordingTemplates) -> OtherAdviceText {
+    let advice = emergency_fund_advice.advice.as_ref().map(|advice| advice.to_string());
+    let Some(calculation) = &emergency_fund_advice.calculation else {
+        return OtherAdviceText { title, paragraph: advice.unwrap_or_default(), table: None };
+    };
+
+    let assessment = calculation.assess(cash_isas);
+    let months = calculation.target_months().to_string();
+    let expenditure = money(calculation.monthly_essential_expenditure());
+    let target = money(assessment.target);
+    let held = money(assessment.held());
+    let cash_isas = money(assessment.cash_isas);
+    let (difference_label, paragraph) = if assessment.shortfall() > 0.0 {
+        ("Shortfall", templates.render(
+            TemplateName::EmergencyFundShortfall,
+            &[("months", &months), ("expenditure", &expenditure), ("target", &target), ("held", &held), ("cash_isas", &cash_isas), ("shortfall", &money(assessment.shortfall()))]
+        ))
+    } else {
+        ("Excess", templates.render(
+            TemplateName::EmergencyFundCovered,
+            &[("months", &months), ("expenditure", &expenditure), ("target", &target), ("held", &held), ("cash_isas", &cash_isas), ("excess", &money(assessment.excess()))]
+        ))
+    };
+    let difference = money(assessment.shortfall().max(assessment.excess()));
+
+    OtherAdviceText {
+        title,
+        paragraph: advice.unwrap_or(paragraph),
+        table: Some(AdviceTable {
+            header: vec!["Emergency fund".to_string(), "Amount".to_string()],
+            rows: vec![
+                vec!["Monthly essential expenditure".to_string(), expenditure],
+                vec!["Months of cover".to_string(), months],
+                vec!["Target".to_string(), target],
+                vec!["Cash ISAs".to_string(), cash_isas],
+                vec!["Other cash".to_string(), money(assessment.other_cash)],
+                vec![difference_label.to_string(), difference]
+            ]
+        })
+    }
+}
+
+/// Estimates Inheritance Tax for each client with an estate basis, counting the products they
+/// hold at current value alongside their declared assets. A married couple or civil partners are
+/// estimated together on the second death, under the joint key; otherwise each client is
+/// estimated alone, with half of any joint products. Pensions count only in tax years whose
+/// rules bring them into the estate, and Junior ISAs belong to the child so never count.
+fn create_inheritance_tax_texts(
+    clients: [(&str, &Option<AdviceAreasAndProducts>); 2],
+    joint: &Option<AdviceAreasAndProducts>,
+    married: bool,
+    templates: &WordingTemplates,
+    tax_rules: &TaxRules
+) -> Result<Vec<(String, OtherAdviceText)>, String> {
+    let joint_products = joint.as_ref().and_then(|joint| joint.products.as_ref());
+    let bases: Vec<(&str, &IhtEstateBasis, Option<&Products>)> = clients
+        .iter()
+        .filter_map(|(key, advice_areas_and_products)| {
+            let advice_areas_and_products = advice_areas_and_products.as_ref()?;
+            Some((*key, advice_areas_and_products.iht_estate.as_ref()?, advice_areas_and_products.products.as_ref()))
+        })
+        .collect();
+
+    if married {
+        let [(_, first, first_products), (_, second, second_products)] = bases[..] else {
+            return Ok(Vec::new());
+        };
+        let holdings = [(first_products, 1.0), (second_products, 1.0), (joint_products, 1.0)];
+        let text = create_inheritance_tax_text(&holdings, |products| first.estate(products).with_spouse(second.estate(0.0)), first.as_at(), TemplateName::InheritanceTaxEstimateCouple, templates, tax_rules)?;
+        return Ok(vec![("Joint".to_string(), text)]);
+    }
+
+    bases
+        .into_iter()
+        .map(|(key, basis, products)| {
+            let holdings = [(products, 1.0), (joint_products, 0.5)];
+            let text = create_inheritance_tax_text(&holdings, |products| basis.estate(products), basis.as_at(), TemplateName::InheritanceTaxEstimate, templates, tax_rules)?;
+            Ok((key.to_string(), text))
+        })
+        .collect()
+}
+
+/// The estimate today, tabled with the figures behind it. Where withdrawals from the estate's
+/// products are recommended to be gifted over the next seven years, a second column estimates the
+/// estate seven years after the last of them, less the gifts, on today's values and rules.
+fn create_inheritance_tax_text(
+    holdings: &[(Option<&Products>, f64)],
+    estate_with_products: impl Fn(f64) -> Estate,
+    as_at: NaiveDate,
+    template: TemplateName,
+    templates: &WordingTemplates,
+    tax_rules: &TaxRules
+) -> Result<OtherAdviceText, String> {
+    let rules = &tax_rules.for_date(as_at)?.inheritance_tax;
+    let products = products_in_estate(holdings, rules.pensions_in_estate);
+    let estate = estate_with_products(products);
+    let today = inheritance_tax::estimate(&estate, as_at, rules);
+
+    let paragraph = templates.render(template, &[("estate", &money(today.net_estate)), ("tax", &money(today.total_tax()))]);
+    let mut header = vec!["Inheritance Tax".to_string(), "Today".to_string()];
+    let mut columns = vec![inheritance_tax_column(&today, &estate, products)];
+
+    let seven_years = as_at.checked_add_months(Months::new(84)).ok_or("Seven years from the report date is out of range")?;
+    let gifts = gifted_withdrawals(holdings, as_at, seven_years.pred_opt().unwrap_or(seven_years), rules.pensions_in_estate)?;
+    let (paragraph, columns) = match gifts.iter().map(|gift| gift.date).max() {
+        Some(last_gift) => {
+            let gifted: f64 = gifts.iter().map(|gift| gift.value).sum();
+            let after_gifts = Estate {
+                assets: (estate.assets - gifted).max(0.0),
+                gifts: estate.gifts.iter().cloned().chain(gifts).collect(),
+                ..estate.clone()
+            };
+            let death = last_gift.checked_add_months(Months::new(84)).ok_or("Seven years from the last gift is out of range")?;
+            let after = inheritance_tax::estimate(&after_gifts, death, rules);
+            header.push("After gifts".to_string());
+            columns.push(inheritance_tax_column(&after, &after_gifts, (products - gifted).max(0.0)));
+            let after_gifts_paragraph = templates.render(TemplateName::InheritanceTaxAfterGifts, &[("gifted", &money(gifted)), ("tax", &money(after.total_tax()))]);
+            (format!("{} {}", paragraph, after_gifts_paragraph), columns)
+        }
+        None => (paragraph, columns)
+    };
+
+    let labels = [
+        "Products", "Home and other assets", "Liabilities", "Net estate", "Gifts within seven years", "Nil-rate band",
+        "Residence nil-rate band", "Taxable estate", "Tax on gifts", "Tax on estate", "Total Inheritance Tax"
+    ];
+    let rows = labels
+        .iter()
+        .enumerate()
+        .map(|(index, label)| std::iter::once(label.to_string()).chain(columns.iter().map(|column| money(column[index]))).collect())
+        .collect();
+
+    Ok(OtherAdviceText { title: "Inheritance Tax Estimate".to_string(), paragraph, table: Some(AdviceTable { header, rows }) })
+}
+
+fn inheritance_tax_column(estimate: &InheritanceTaxEstimate, estate: &Estate, products: f64) -> [f64; 11] {
+    [
+        products,
+        estate.assets - products,
+        estate.liabilities,
+        estimate.net_estate,
+        estimate.gifts_within_seven_years,
+        estimate.nil_rate_band,
+        estimate.residence_nil_rate_band,
+        estimate.taxable_estate,
+        estimate.tax_on_gifts,
+        estimate.tax_on_estate,
+        estimate.total_tax()
+    ]
+}
+
+fn counts_in_estate(account_type: &AccountType, pensions_in_estate: bool) -> bool {
+    match account_type {
+        AccountType::SelfInvestedPersonalPension(_) | AccountType::PersonalPension(_) => pensions_in_estate,
+        AccountType::JuniorIsaStocksAndShares(_) => false,
+        _ => true
+    }
+}
+
+/// Jointly owned products are never pensions or Junior ISAs, so always count.
+fn existing_product_counts_in_estate(product: &ExistingProduct, pensions_in_estate: bool) -> bool {
+    match product {
+        ExistingProduct::SingleOwned(product) => counts_in_estate(product.account_type(), pensions_in_estate),
+        ExistingProduct::JointlyOwned(_) => true
+    }
+}
+
+/// The current value of the existing products in the estate, each by the share the estate owns.
+fn products_in_estate(holdings: &[(Option<&Products>, f64)], pensions_in_estate: bool) -> f64 {
+    holdings
+        .iter()
+        .filter_map(|(products, share)| products.map(|products| (products, share)))
+        .flat_map(|(products, share)| products.existing_products().into_iter().map(move |product| (product, share)))
+        .filter(|(product, _)| existing_product_counts_in_estate(product, pensions_in_estate))
+        .map(|(product, share)| product.current_value().value().value() * share)
+        .sum()
+}
+
+/// The withdrawals recommended to be gifted between `first_day` and `last_day` from the
+/// products in the estate, each by the share the estate owns. A single withdrawal with only a
+/// tax year is taken to be made at the start of it, or today if that has passed.
+fn gifted_withdrawals(
+    holdings: &[(Option<&Products>, f64)],
+    first_day: NaiveDate,
+    last_day: NaiveDate,
+    pensions_in_estate: bool
+) -> Result<Vec<Gift>, String> {
+    let mut gifts = Vec::new();
+
+    for (products, share) in holdings.iter().filter_map(|(products, share)| products.map(|products| (products, *share))) {
+        let mut actions = Vec::new();
+        for product in products.existing_products() {
+            if let (true, ProductRetention::Retain(retain)) = (existing_product_counts_in_estate(&product, pensions_in_estate), product.product_retention()) {
+                actions.extend(retain.recommendation_actions().iter().flatten().cloned());
+            }
+        }
+        for product in products.new_products() {
+            if counts_in_estate(product.account_type(), pensions_in_estate) {
+                actions.extend(product.recommendation_actions().iter().cloned());
+            }
+        }
 
-fn create_other_advice_area(advice_area: &AdviceArea) -> (String, String) {
-    match advice_area {
-        AdviceArea::EmergencyFund(emergency_fund_advice) => (advice_area.title(), emergency_fund_advice.advice.to_string()),
-        AdviceArea::Iht(iht_advice) => (advice_area.title(), iht_advice.advice.to_string()),
-        AdviceArea::Other(other_advice) => (advice_area.title(), other_advice.advice.to_string()),
-        AdviceArea::Will(will_advice) => (advice_area.title(), will_advice.advice.to_string()),
-        AdviceArea::Poa(poa_advice) => (advice_area.title(), poa_advice.advice.to_string())
+        for action in &actions {
+            match action {
+                RecommendedAction::SingleWithdrawal(withdrawal) if withdrawal.gifted() => {
+                    let date = match (withdrawal.date_of_action(), withdrawal.tax_year_of_action()) {
+                        (Some(date), _) => *date.value(),
+                        (None, Some(tax_year)) => tax_year.first_day()?.max(first_day),
+                        (None, None) => first_day
+                    };
+                    if (first_day..=last_day).contains(&date) {
+                        gifts.push(Gift { date, value: withdrawal.value().value() * share });
+                    }
+                }
+                RecommendedAction::RegularWithdrawal(withdrawal) if withdrawal.gifted() => {
+                    gifts.extend(withdrawal
+                        .dates_between(first_day, last_day)
+                        .into_iter()
+                        .map(|date| Gift { date, value: withdrawal.value().value() * share }));
+                }
+                _ => {}
+            }
+        }
     }
+
+    Ok(gifts)
 }
 
 fn create_product_recommendations(
     product_recommendations: &mut HashMap<String, Vec<ProductRecommendationsText>>,
     client_key: &str,
     advice_areas_and_products: &Option<AdviceAreasAndProducts>,
-    objectives: &HashMap<String, ObjectiveType>
+    objectives: &HashMap<String, ObjectiveType>,
+    templates: &WordingTemplates,
+    tax_rules: &TaxRules
 ) -> Result<(), String> {
     if let Some(advice_areas_and_products) = advice_areas_and_products {
         if let Some(products) = &advice_areas_and_products.products {
@@ -165,7 +895,9 @@ fn create_product_recommendations(
             let existing_product_recommendations: Vec<ProductRecommendationsText> = create_existing_products_recommendation_text(
                 &all_products_by_account_number, 
                 &existing_products,
-                objectives
+                objectives,
+                templates,
+                tax_rules
             )?;
 
             // Insert recommendations only if successful
@@ -175,7 +907,8 @@ fn create_product_recommendations(
                 .extend(existing_product_recommendations);
 
             let new_product_recommendations: Vec<ProductRecommendationsText> = create_new_products_recommendation_text(
-                &new_products
+                &new_products,
+                templates
             )?;
 
             product_recommendations
@@ -193,7 +926,9 @@ fn create_product_recommendations(
 fn create_existing_products_recommendation_text(
     all_products_by_account_number: &HashMap<String, &ExistingNewJointSingleProduct>,
     existing_products: &Vec<ExistingProduct>,
-    objectives: &HashMap<String, ObjectiveType>
+    objectives: &HashMap<String, ObjectiveType>,
+    templates: &WordingTemplates,
+    tax_rules: &TaxRules
 ) -> Result<Vec<ProductRecommendationsText>, String> {
     existing_products
         .iter()
@@ -201,7 +936,9 @@ fn create_existing_products_recommendation_text(
             (
                 &all_products_by_account_number, 
                 existing_product,
-                objectives
+                objectives,
+                templates,
+                tax_rules
             )
         )
         .collect()
@@ -209,11 +946,13 @@ fn create_existing_products_recommendation_text(
 
 fn create_new_products_recommendation_text(
     new_products: &Vec<NewProduct>,
+    templates: &WordingTemplates
 ) -> Result<Vec<ProductRecommendationsText>, String> {
     new_products
         .iter()
         .map(|new_product| create_new_product_recommendation_text(
-            new_product)
+            new_product,
+            templates)
         )
         .collect()
 }   
@@ -221,29 +960,34 @@ fn create_new_products_recommendation_text(
 fn create_existing_product_recommendation_text(
     all_products_by_account_number_or_reference_number: &HashMap<String, &ExistingNewJointSingleProduct>,
     existing_product_requiring_text: &ExistingProduct,
-    objectives_by_id: &HashMap<String, ObjectiveType>
+    objectives_by_id: &HashMap<String, ObjectiveType>,
+    templates: &WordingTemplates,
+    tax_rules: &TaxRules
 ) -> Result<ProductRecommendationsText, String> {
     Ok(ProductRecommendationsText::Existing( ExistingProductRecommendationsText {
         platform_number: existing_product_requiring_text.platform_account_number().clone(),
         account_or_refence_number: existing_product_requiring_text.account_or_reference_number().clone(),
         product_title: create_existing_product_recommendation_text_title(existing_product_requiring_text),
-        product_retention_sentence: create_product_retention_sentence(all_products_by_account_number_or_reference_number, existing_product_requiring_text)?,
+        product_retention_sentence: create_product_retention_sentence(all_products_by_account_number_or_reference_number, existing_product_requiring_text, templates)?,
         rationale: existing_product_requiring_text.rationale().to_string(),
         actions: create_existing_product_actions_text(
             all_products_by_account_number_or_reference_number, 
             existing_product_requiring_text,
-            objectives_by_id
+            objectives_by_id,
+            templates,
+            tax_rules
         )?
     }))
 }
 
 fn create_new_product_recommendation_text(
-    new_product_requiring_text: &NewProduct
+    new_product_requiring_text: &NewProduct,
+    templates: &WordingTemplates
 ) -> Result<ProductRecommendationsText, String> {
     Ok(ProductRecommendationsText::New( NewProductRecommendationsText {
         platform_number: new_product_requiring_text.platform_account_number().clone(),
         product_title: create_new_product_recommendation_title_text(new_product_requiring_text),
-        new_product_initial_sentence: create_new_product_initial_sentence(new_product_requiring_text),
+        new_product_initial_sentence: create_new_product_initial_sentence(new_product_requiring_text, templates),
         rationale: new_product_requiring_text.rationale().to_string()
     }))
 }
@@ -288,165 +1032,111 @@ fn create_new_product_recommendation_title_text(
 
 fn create_product_retention_sentence(
     all_products_by_account_number: &HashMap<String, &ExistingNewJointSingleProduct>,
-    existing_product: &ExistingProduct
+    existing_product: &ExistingProduct,
+    templates: &WordingTemplates
 ) -> Result<String, String> {
 
+    let provider = existing_product.provider().value().alt_name();
+
     match existing_product.product_retention() {
         ProductRetention::Retain(_) => {
-            Ok(format!(
-                "I recommend you continue to retain the {} {}.",
-                existing_product.provider().value().alt_name(), 
-                existing_product.account_type_as_full_name_brackets_string_short_name(),
+            Ok(templates.render(
+                TemplateName::ProductRetain,
+                &[("provider", &provider), ("product", &existing_product.account_type_as_full_name_brackets_string_short_name())]
             ))
         }
         ProductRetention::FullyEncash(_) => {
-            Ok(format!(
-                "I recommend you fully encash the {} {}.",
-                existing_product.provider().value().alt_name(), 
-                existing_product.account_type_as_full_name_brackets_string_short_name()
+            Ok(templates.render(
+                TemplateName::ProductFullyEncash,
+                &[("provider", &provider), ("product", &existing_product.account_type_as_full_name_brackets_string_short_name())]
             ))
         }
         ProductRetention::Replace(replace) => {
-           match replace {
-                Replace::FullyReplace(fully_replace) => {
-                    let fully_replace_to_account_number = fully_replace.replace_to_details().transfer_to_account_or_reference_number();
-                    let product_to_be_transferred_to = all_products_by_account_number.get(&fully_replace_to_account_number.to_string());
-
-                    let start_text = format!(
-                        "I recommend you transfer in full the {} {} to ",
-                        existing_product.provider().value().alt_name(), 
-                        existing_product.account_type_as_string()
-                    );
-
-                    match product_to_be_transferred_to {
-                        Some(product) => {
-                            let transfer_text = match product {
-                                ExistingNewJointSingleProduct::ExistingJointlyOwnedProduct(transfer_to_existing_product) => {
-                                    format!(
-                                        "the existing jointly owned {} {}.",
-                                        transfer_to_existing_product.provider().value().alt_name(),
-                                        transfer_to_existing_product.tax_wrapper_type_as_string()
-                                    )
-                                },
-                                ExistingNewJointSingleProduct::ExistingSingleOwnedProduct(transfer_to_existing_product) => {
-                                    format!(
-                                        "your existing {} {}.",
-                                        transfer_to_existing_product.provider().value().alt_name(),
-                                        transfer_to_existing_product.tax_wrapper_type_as_string()
-                                    )
-                                },
-                                ExistingNewJointSingleProduct::NewSingleOwnedProduct(new_product) => {
-                                    format!(
-                                        "a new {} {}.",
-                                        new_product.provider().value().alt_name(),
-                                        new_product.tax_wrapper_type_as_string()
-                                    )
-                                }
-                            };
-                            Ok(format!("{}{}", start_text, transfer_text))
-                        }
-                        None => {
-                            Err(format!(
-                                "No matching recommended product to be transferred to found for the product being fully replaced. Account or reference number provided was {}",
-                                fully_replace_to_account_number.to_string()
-                            ))
-                        }
-                    }
-                }
-                Replace::PartiallyReplace(partially_replace) => {
-                    let partially_replace_to_account_number = partially_replace.partially_replace_to_details().transfer_to_account_or_reference_number();
-                    let product_to_be_transferred_to = all_products_by_account_number.get(&partially_replace_to_account_number.to_string());
-
-                    let start_text = format!(
-                        "I recommend you partially transfer the {} {} to ",
-                        existing_product.provider().value().alt_name(), 
-                        existing_product.account_type_as_string()
-                    );
-
-                    match product_to_be_transferred_to {
-                        Some(product) => {
-                            let transfer_text = match product {
-                                ExistingNewJointSingleProduct::ExistingJointlyOwnedProduct(transfer_to_existing_product) => {
-                                    format!(
-                                        "the existing jointly owned {} {}.",
-                                        transfer_to_existing_product.provider().value().alt_name(),
-                                        transfer_to_existing_product.tax_wrapper_type_as_string()
-                                    )
-                                },
-                                ExistingNewJointSingleProduct::ExistingSingleOwnedProduct(transfer_to_existing_product) => {
-                                    format!(
-                                        "your existing {} {}.",
-                                        transfer_to_existing_product.provider().value().alt_name(),
-                                        transfer_to_existing_product.tax_wrapper_type_as_string()
-                                    )
-                                },
-                                ExistingNewJointSingleProduct::NewSingleOwnedProduct(new_product) => {
-                                    format!(
-                                        "a new {} {}.",
-                                        new_product.provider().value().alt_name(),
-                                        new_product.tax_wrapper_type_as_string()
-                                    )
-                                }
-                            };
-                            Ok(format!("{}{}", start_text, transfer_text))
-                        }
-                        None => {
-                            Err(format!(
-                                "No matching recommended product to be transferred to found for the product being partially replaced. Account or reference number provided was {}",
-                                partially_replace_to_account_number.to_string()
-                            ))
-                        }
-                    }
-                }
-            }
+            let (template, transfer_to_account_number, replacement_description) = match replace {
+                Replace::FullyReplace(fully_replace) => (
+                    TemplateName::ProductFullyReplace,
+                    fully_replace.replace_to_details().transfer_to_account_or_reference_number(),
+                    "fully replaced"
+                ),
+                Replace::PartiallyReplace(partially_replace) => (
+                    TemplateName::ProductPartiallyReplace,
+                    partially_replace.partially_replace_to_details().transfer_to_account_or_reference_number(),
+                    "partially replaced"
+                )
+            };
+
+            let product_to_be_transferred_to = all_products_by_account_number
+                .get(&transfer_to_account_number.to_string())
+                .ok_or_else(|| format!(
+                    "No matching recommended product to be transferred to found for the product being {}. Account or reference number provided was {}",
+                    replacement_description,
+                    transfer_to_account_number.to_string()
+                ))?;
+
+            let destination = create_transfer_destination_text(product_to_be_transferred_to, templates);
+
+            Ok(templates.render(
+                template,
+                &[("provider", &provider), ("product", &existing_product.account_type_as_string()), ("destination", &destination)]
+            ))
         }
     }
 }
 
+fn create_transfer_destination_text(
+    product: &ExistingNewJointSingleProduct,
+    templates: &WordingTemplates
+) -> String {
+    match product {
+        ExistingNewJointSingleProduct::ExistingJointlyOwnedProduct(transfer_to_existing_product) => templates.render(
+            TemplateName::DestinationExistingJoint,
+            &[
+                ("provider", &transfer_to_existing_product.provider().value().alt_name()),
+                ("tax_wrapper", &transfer_to_existing_product.tax_wrapper_type_as_string())
+            ]
+        ),
+        ExistingNewJointSingleProduct::ExistingSingleOwnedProduct(transfer_to_existing_product) => templates.render(
+            TemplateName::DestinationExistingSingle,
+            &[
+                ("provider", &transfer_to_existing_product.provider().value().alt_name()),
+                ("tax_wrapper", &transfer_to_existing_product.tax_wrapper_type_as_string())
+            ]
+        ),
+        ExistingNewJointSingleProduct::NewSingleOwnedProduct(new_product) => templates.render(
+            TemplateName::DestinationNew,
+            &[
+                ("provider", &new_product.provider().value().alt_name()),
+                ("tax_wrapper", &new_product.tax_wrapper_type_as_string())
+            ]
+        )
+    }
+}
+
 
 
 fn create_new_product_initial_sentence(
-    new_product_requiring_text: &NewProduct
+    new_product_requiring_text: &NewProduct,
+    templates: &WordingTemplates
 ) -> String {
 
-    match new_product_requiring_text.provider().value() {
-        Providers::Abrdn => {
-            match new_product_requiring_text.platform_account_number() {
-                Some(_) => {
-                    format!(
-                        "I recommend you open a new {} within your existing {} wrap account.",
-                        new_product_requiring_text.tax_wrapper_type_as_string(),
-                        new_product_requiring_text.provider_as_string()
-                    )
-                },
-                None => {
-                    format!(
-                        "I recommend you open a new {} wrap account and within this account open a {}",
-                        new_product_requiring_text.provider_as_string(),
-                        new_product_requiring_text.tax_wrapper_type_as_string()
-                    )
-                }
-            }
-        }
-        _ => {
-            match new_product_requiring_text.platform_account_number() {
-                Some(_) => {
-                    format!(
-                        "I recommend you open a new {} within your existing {} platform account.",
-                        new_product_requiring_text.tax_wrapper_type_as_string(),
-                        new_product_requiring_text.provider_as_string()
-                    )
-                },
-                None => {
-                    format!(
-                        "I recommend you open a new {} platform account and within this account open a {}",
-                        new_product_requiring_text.provider_as_string(),
-                        new_product_requiring_text.tax_wrapper_type_as_string()
-                    )
-                }
-            }
-        }
-    }
+    let account_kind = match new_product_requiring_text.provider().value() {
+        Providers::Abrdn => "wrap account",
+        _ => "platform account"
+    };
+
+    let template = match new_product_requiring_text.platform_account_number() {
+        Some(_) => TemplateName::NewProductExistingPlatform,
+        None => TemplateName::NewProductNewPlatform
+    };
+
+    templates.render(
+        template,
+        &[
+            ("provider", &new_product_requiring_text.provider_as_string()),
+            ("tax_wrapper", &new_product_requiring_text.tax_wrapper_type_as_string()),
+            ("account_kind", account_kind)
+        ]
+    )
 
 }
 
@@ -454,7 +1144,9 @@ fn create_new_product_initial_sentence(
 fn create_existing_product_actions_text(
     all_products_by_account_number_or_reference_number: &HashMap<String, &ExistingNewJointSingleProduct>, 
     existing_product_requiring_text: &ExistingProduct,
-    objectives_by_id: &HashMap<String, ObjectiveType>
+    objectives_by_id: &HashMap<String, ObjectiveType>,
+    templates: &WordingTemplates,
+    tax_rules: &TaxRules
 ) -> Result<Option<Vec<ProductActionsText>>, String> {
     // We only support SingleOwned products for now.
     if let ExistingProduct::SingleOwned(existing_product) = existing_product_requiring_text {
@@ -470,7 +1162,9 @@ fn create_existing_product_actions_text(
                             all_products_by_account_number_or_reference_number, 
                             existing_product_requiring_text, 
                             action,
-                            objectives_by_id
+                            objectives_by_id,
+                            templates,
+                            tax_rules
                         )?;
                         Ok(ProductActionsText {
                             action_title: action.description().to_string(),
@@ -483,19 +1177,174 @@ fn create_existing_product_actions_text(
                 return Ok(None);
             }
         } else {
-            return Ok(None);
+            if let Some(text) = create_disposal_capital_gains_tax_text(existing_product, templates, tax_rules)? {
+                return Ok(Some(vec![text]));
+            }
+            return Ok(create_surrender_chargeable_gain_text(existing_product, templates, tax_rules)?.map(|text| vec![text]));
         }
     }
     Ok(None)
 }
 
+/// Quotes the Capital Gains Tax on encashing or partially transferring a General Investment
+/// Account, when the adviser has supplied its base cost.
+fn create_disposal_capital_gains_tax_text(
+    existing_product: &ExistingSingleOwnedProduct,
+    templates: &WordingTemplates,
+    tax_rules: &TaxRules
+) -> Result<Option<ProductActionsText>, String> {
+    let AccountType::GeneralInvestmentAccount(gia) = existing_product.account_type() else {
+        return Ok(None);
+    };
+
+    let value = gia.current_value().value().value();
+    let (disposal, proceeds) = match existing_product.product_retention() {
+        ProductRetention::FullyEncash(_) => ("fully encashing the account".to_string(), value),
+        ProductRetention::Replace(Replace::PartiallyReplace(partially_replace)) => {
+            let proceeds = value - partially_replace.partially_replace_to_details().amount_to_be_left_in_existing_product().value();
+            (format!("the partial transfer of {}", money(proceeds)), proceeds)
+        }
+        _ => return Ok(None)
+    };
+
+    Ok(create_capital_gains_tax_paragraph(gia, &disposal, proceeds, None, templates, tax_rules)?
+        .map(|paragraph| ProductActionsText { action_title: "Capital Gains Tax".to_string(), action_paragraph: paragraph }))
+}
+
+/// Renders the gain and tax on taking `proceeds` from the account, in `tax_year` when the action
+/// names one and otherwise the tax year the adviser gave with the base cost.
+fn create_capital_gains_tax_paragraph(
+    gia: &GeneralInvestmentAccount,
+    disposal: &str,
+    proceeds: f64,
+    tax_year: Option<&TaxYear>,
+    templates: &WordingTemplates,
+    tax_rules: &TaxRules
+) -> Result<Option<String>, String> {
+    let Some(basis) = gia.capital_gains_tax_basis() else {
+        return Ok(None);
+    };
+
+    let tax_year = tax_year.unwrap_or(basis.tax_year());
+    let calculation = basis.calculate(gia.current_value().value().value(), proceeds, tax_year, tax_rules)?;
+    let outcome = if calculation.gain < 0.0 {
+        format!("a loss of {}", money(-calculation.gain))
+    } else {
+        format!("a gain of {}", money(calculation.gain))
+    };
+    let base_cost = basis.base_cost().to_string();
+    let tax_year = tax_year.to_string();
+
+    Ok(Some(if calculation.tax_due > 0.0 {
+        templates.render(
+            TemplateName::CapitalGainsTaxPayable,
+            &[("disposal", disposal), ("base_cost", &base_cost), ("outcome", &outcome), ("tax_year", &tax_year), ("tax", &money(calculation.tax_due))]
+        )
+    } else {
+        templates.render(
+            TemplateName::CapitalGainsTaxNotPayable,
+            &[("disposal", disposal), ("base_cost", &base_cost), ("outcome", &outcome), ("tax_year", &tax_year)]
+        )
+    }))
+}
+
+/// Returns the chargeable gains basis of a bond, with its current value and where it was issued,
+/// when the adviser has supplied the bond's history.
+fn bond_chargeable_gains_basis(existing_product: &ExistingSingleOwnedProduct) -> Option<(&ChargeableGainsBasis, f64, BondSitus)> {
+    match existing_product.account_type() {
+        AccountType::OnshoreInvestmentBond(bond) => bond
+            .chargeable_gains_basis()
+            .map(|basis| (basis, bond.current_value().value().value(), BondSitus::Onshore)),
+        AccountType::OffshoreInvestmentBond(bond) => bond
+            .chargeable_gains_basis()
+            .map(|basis| (basis, bond.current_value().value().value(), BondSitus::Offshore)),
+        _ => None
+    }
+}
+
+/// Quotes the income tax on encashing or partially transferring an investment bond, when the
+/// adviser has supplied its history.
+fn create_surrender_chargeable_gain_text(
+    existing_product: &ExistingSingleOwnedProduct,
+    templates: &WordingTemplates,
+    tax_rules: &TaxRules
+) -> Result<Option<ProductActionsText>, String> {
+    let Some((basis, value, situs)) = bond_chargeable_gains_basis(existing_product) else {
+        return Ok(None);
+    };
+
+    let paragraph = match existing_product.product_retention() {
+        ProductRetention::FullyEncash(_) => {
+            let calculation = basis.full_surrender(value, situs, basis.tax_year(), tax_rules)?;
+            create_chargeable_gain_paragraph("Fully encashing the bond", &calculation, basis.tax_year(), templates)
+        }
+        ProductRetention::Replace(Replace::PartiallyReplace(partially_replace)) => {
+            let proceeds = value - partially_replace.partially_replace_to_details().amount_to_be_left_in_existing_product().value();
+            let disposal = format!("The partial transfer of {}", money(proceeds));
+            create_partial_surrender_paragraph(basis, situs, &disposal, proceeds, None, templates, tax_rules)?
+        }
+        _ => return Ok(None)
+    };
+
+    Ok(Some(ProductActionsText { action_title: "Chargeable Event".to_string(), action_paragraph: paragraph }))
+}
+
+/// Renders the outcome of withdrawing `amount` from a bond, in `tax_year` when the action names one
+/// and otherwise the tax year the adviser gave with the bond's history.
+fn create_partial_surrender_paragraph(
+    basis: &ChargeableGainsBasis,
+    situs: BondSitus,
+    disposal: &str,
+    amount: f64,
+    tax_year: Option<&TaxYear>,
+    templates: &WordingTemplates,
+    tax_rules: &TaxRules
+) -> Result<String, String> {
+    let tax_year = tax_year.unwrap_or(basis.tax_year());
+    let calculation = basis.partial_surrender(amount, situs, tax_year, tax_rules)?;
+
+    if calculation.gain > 0.0 {
+        Ok(create_chargeable_gain_paragraph(disposal, &calculation, tax_year, templates))
+    } else {
+        Ok(templates.render(
+            TemplateName::ChargeableGainWithinAllowance,
+            &[("disposal", disposal), ("allowance", &money(calculation.allowance_remaining))]
+        ))
+    }
+}
+
+fn create_chargeable_gain_paragraph(
+    disposal: &str,
+    calculation: &ChargeableGainCalculation,
+    tax_year: &TaxYear,
+    templates: &WordingTemplates
+) -> String {
+    let gain = money(calculation.gain);
+    let tax_year = tax_year.to_string();
+    let relief = money(calculation.top_slicing_relief);
+
+    if calculation.tax_due > 0.0 {
+        templates.render(
+            TemplateName::ChargeableGainPayable,
+            &[("disposal", disposal), ("gain", &gain), ("tax_year", &tax_year), ("relief", &relief), ("tax", &money(calculation.tax_due))]
+        )
+    } else {
+        templates.render(
+            TemplateName::ChargeableGainNotPayable,
+            &[("disposal", disposal), ("gain", &gain), ("tax_year", &tax_year), ("relief", &relief)]
+        )
+    }
+}
+
 
 
 fn create_action_paragraph(
     all_products_by_account_number_or_reference_number: &HashMap<String, &ExistingNewJointSingleProduct>, 
     existing_product_requiring_text: &ExistingProduct,
     recommended_action: &RecommendedAction,
-    objectives: &HashMap<String, ObjectiveType>
+    objectives: &HashMap<String, ObjectiveType>,
+    templates: &WordingTemplates,
+    tax_rules: &TaxRules
 ) -> Result<String, String> {
     let mut action_paragraph = String::new();
 
@@ -598,10 +1447,482 @@ fn create_action_paragraph(
             
             
             
+        }
+        RecommendedAction::SingleWithdrawal(single_withdrawal) => {
+            if let ExistingProduct::SingleOwned(product) = existing_product_requiring_text {
+                if let AccountType::GeneralInvestmentAccount(gia) = product.account_type() {
+                    let disposal = format!("the withdrawal of {}", single_withdrawal.value());
+                    if let Some(paragraph) = create_capital_gains_tax_paragraph(
+                        gia,
+                        &disposal,
+                        single_withdrawal.value().value(),
+                        single_withdrawal.tax_year_of_action(),
+                        templates,
+                        tax_rules
+                    )? {
+                        action_paragraph.push_str(&paragraph);
+                    }
+                }
+                if let Some((basis, _, situs)) = bond_chargeable_gains_basis(product) {
+                    action_paragraph.push_str(&create_partial_surrender_paragraph(
+                        basis,
+                        situs,
+                        &format!("The withdrawal of {}", single_withdrawal.value()),
+                        single_withdrawal.value().value(),
+                        single_withdrawal.tax_year_of_action(),
+                        templates,
+                        tax_rules
+                    )?);
+                }
+            }
+        }
+        RecommendedAction::RegularWithdrawal(regular_withdrawal) => {
+            if let ExistingProduct::SingleOwned(product) = existing_product_requiring_text {
+                if let Some((basis, _, situs)) = bond_chargeable_gains_basis(product) {
+                    // Withdrawals are assessed against the allowance by their total for the year
+                    let annual_amount = regular_withdrawal.value().value() * regular_withdrawal.frequency().per_year() as f64;
+                    let disposal = format!(
+                        "Withdrawals of {} {} ({} a year)",
+                        regular_withdrawal.value(),
+                        regular_withdrawal.frequency(),
+                        money(annual_amount)
+                    );
+                    action_paragraph.push_str(&create_partial_surrender_paragraph(
+                        basis,
+                        situs,
+                        &disposal,
+                        annual_amount,
+                        regular_withdrawal.tax_year_of_action(),
+                        templates,
+                        tax_rules
+                    )?);
+                }
+            }
         }
         _ => {}
     }
     
     Ok(action_paragraph.to_string())
 
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::domain::report::product::JuniorIsaStocksAndShares;
+    use crate::domain::report::risk_assessment::RiskProfile;
+    use crate::domain::tax::pension_allowance::{self, PensionAllowanceCircumstances};
+
+    fn general_investment_account(basis: Option<serde_json::Value>) -> GeneralInvestmentAccount {
+        let mut gia = serde_json::to_value(GeneralInvestmentAccount::default()).unwrap();
+        gia["currentValue"]["value"] = json!(50000.0);
+        gia["capitalGainsTaxBasis"] = json!(basis);
+        serde_json::from_value(gia).unwrap()
+    }
+
+    fn basis() -> serde_json::Value {
+        json!({ "baseCost": 20000.0, "taxYear": "2026/2027", "taxableIncome": 60000.0, "otherGains": 0.0, "otherLosses": 0.0 })
+    }
+
+    fn chargeable_gains_basis() -> ChargeableGainsBasis {
+        serde_json::from_value(json!({
+            "premiumsPaid": 100000.0,
+            "policyYears": 10,
+            "previousWithdrawals": 0.0,
+            "previousChargeableGains": 0.0,
+            "taxYear": "2026/2027",
+            "taxableIncome": 30000.0
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_capital_gains_tax_paragraph() {
+        let gia = general_investment_account(Some(basis()));
+        let templates = WordingTemplates::default();
+        let tax_rules = TaxRules::default();
+
+        assert_eq!(
+            create_capital_gains_tax_paragraph(&gia, "fully encashing the account", 50000.0, None, &templates, &tax_rules).unwrap().unwrap(),
+            "Based on a base cost of £20,000.00, fully encashing the account would realise a gain of £30,000.00 in the 2026/2027 tax year, on which I estimate Capital Gains Tax of £6,480.00 would be payable."
+        );
+        assert_eq!(
+            create_capital_gains_tax_paragraph(&gia, "the withdrawal of £4,000.00", 4000.0, None, &templates, &tax_rules).unwrap().unwrap(),
+            "Based on a base cost of £20,000.00, the withdrawal of £4,000.00 would realise a gain of £2,400.00 in the 2026/2027 tax year. After your annual exempt amount and any losses, no Capital Gains Tax would be payable."
+        );
+    }
+
+    #[test]
+    fn test_capital_gains_tax_needs_basis_and_configured_year() {
+        let templates = WordingTemplates::default();
+        let tax_rules = TaxRules::default();
+
+        assert_eq!(create_capital_gains_tax_paragraph(&general_investment_account(None), "fully encashing the account", 50000.0, None, &templates, &tax_rules), Ok(None));
+
+        let mut unconfigured = basis();
+        unconfigured["taxYear"] = json!("2040/2041");
+        assert!(create_capital_gains_tax_paragraph(&general_investment_account(Some(unconfigured)), "fully encashing the account", 50000.0, None, &templates, &tax_rules)
+            .unwrap_err()
+            .contains("2040/2041"));
+    }
+
+    #[test]
+    fn test_full_surrender_chargeable_gain_paragraph() {
+        let basis = chargeable_gains_basis();
+        let calculation = basis.full_surrender(150000.0, BondSitus::Offshore, basis.tax_year(), &TaxRules::default()).unwrap();
+
+        assert_eq!(
+            create_chargeable_gain_paragraph("Fully encashing the bond", &calculation, basis.tax_year(), &WordingTemplates::default()),
+            "Fully encashing the bond would give rise to a chargeable gain of £50,000.00 in the 2026/2027 tax year. After top-slicing relief of £8,460.00, I estimate income tax of £10,000.00 would be payable."
+        );
+    }
+
+    #[test]
+    fn test_partial_surrender_paragraph() {
+        let basis = chargeable_gains_basis();
+        let templates = WordingTemplates::default();
+        let tax_rules = TaxRules::default();
+
+        assert_eq!(
+            create_partial_surrender_paragraph(&basis, BondSitus::Offshore, "The withdrawal of £30,000.00", 30000.0, None, &templates, &tax_rules).unwrap(),
+            "The withdrawal of £30,000.00 would fall within the £50,000.00 of your cumulative 5% tax-deferred allowance remaining, so no chargeable gain would arise."
+        );
+        assert_eq!(
+            create_partial_surrender_paragraph(&basis, BondSitus::Offshore, "Withdrawals of £5,000.00 monthly (£60,000.00 a year)", 60000.0, None, &templates, &tax_rules).unwrap(),
+            "Withdrawals of £5,000.00 monthly (£60,000.00 a year) would give rise to a chargeable gain of £10,000.00 in the 2026/2027 tax year. After top-slicing relief of £460.00, I estimate income tax of £2,000.00 would be payable."
+        );
+        assert_eq!(
+            create_partial_surrender_paragraph(&basis, BondSitus::Onshore, "Withdrawals of £5,000.00 monthly (£60,000.00 a year)", 60000.0, None, &templates, &tax_rules).unwrap(),
+            "Withdrawals of £5,000.00 monthly (£60,000.00 a year) would give rise to a chargeable gain of £10,000.00 in the 2026/2027 tax year. After top-slicing relief of £460.00 and any tax treated as already paid within the bond, no further income tax would be payable."
+        );
+    }
+
+    fn contribution(action: serde_json::Value) -> RecommendedAction {
+        let mut action = action;
+        action["executiveSummaryDescription"] = json!("Subscribe to the ISA");
+        action["rationale"] = json!("To use your allowance");
+        if action.get("taxYearOfAction").is_none() {
+            action["taxYearOfAction"] = json!("2026/2027");
+        }
+        serde_json::from_value(action).unwrap()
+    }
+
+    fn current_tax_year() -> TaxYear {
+        TaxYear::parse("2026/2027").unwrap()
+    }
+
+    #[test]
+    fn test_contributions_are_recorded_against_isa_allowance() {
+        let actions = [
+            contribution(json!({ "type": "singleContribution", "value": 9500.0 })),
+            contribution(json!({ "type": "regularContribution", "value": 1500.0, "frequency": "Monthly", "startDateOfAction": "2026-10-01" }))
+        ];
+        let mut ledger = IsaAllowanceLedger::default();
+        for (tax_year, amount) in contributions_by_tax_year(&actions, &current_tax_year()).unwrap() {
+            ledger.subscribe(tax_year, IsaAllowance::Adult, amount);
+        }
+
+        // Seven monthly subscriptions fall between 1st October and the end of the tax year on 5th April
+        let usage = ledger.usage(&TaxRules::default()).unwrap();
+        assert_eq!(usage[0].subscribed, 20000.0);
+        assert_eq!(usage[0].remaining, 0.0);
+
+        for (tax_year, amount) in contributions_by_tax_year(&actions[..1], &current_tax_year()).unwrap() {
+            ledger.subscribe(tax_year, IsaAllowance::Adult, amount);
+        }
+        assert!(ledger.usage(&TaxRules::default()).unwrap_err().contains("£20000.00 ISA allowance"));
+    }
+
+    #[test]
+    fn test_regular_contribution_is_spread_over_its_tax_years() {
+        let ending = contribution(json!({
+            "type": "regularContribution", "value": 1000.0, "frequency": "Monthly", "startDateOfAction": "2026-10-01", "endDateOfAction": "2027-09-30"
+        }));
+        assert_eq!(
+            contributions_by_tax_year(&[ending], &current_tax_year()).unwrap(),
+            vec![(TaxYear::parse("2026/2027").unwrap(), 7000.0), (TaxYear::parse("2027/2028").unwrap(), 5000.0)]
+        );
+
+        // Without an end date the next tax year is checked too, where twelve payments exceed the allowance
+        let open_ended = contribution(json!({
+            "type": "regularContribution", "value": 2000.0, "frequency": "Monthly", "startDateOfAction": "2026-10-01"
+        }));
+        let mut ledger = IsaAllowanceLedger::default();
+        for (tax_year, amount) in contributions_by_tax_year(&[open_ended], &current_tax_year()).unwrap() {
+            ledger.subscribe(tax_year, IsaAllowance::Adult, amount);
+        }
+        assert!(ledger.usage(&TaxRules::default()).unwrap_err().contains("£24000.00 exceed the £20000.00 ISA allowance in the 2027/2028 tax year"));
+    }
+
+    #[test]
+    fn test_undated_single_contribution() {
+        let dated = contribution(json!({ "type": "singleContribution", "value": 5000.0, "taxYearOfAction": null, "dateOfAction": "2027-04-06" }));
+        assert_eq!(contributions_by_tax_year(&[dated], &current_tax_year()).unwrap(), vec![(TaxYear::parse("2027/2028").unwrap(), 5000.0)]);
+
+        let undated = contribution(json!({ "type": "singleContribution", "value": 5000.0, "taxYearOfAction": null }));
+        assert_eq!(
+            contributions_by_tax_year(&[undated], &current_tax_year()).unwrap_err(),
+            "The single contribution \"Subscribe to the ISA\" needs a tax year or date of action to be checked against its allowance"
+        );
+    }
+
+    fn junior_isa_products(child: &str, subscription: f64) -> Option<AdviceAreasAndProducts> {
+        let mut jisa = serde_json::to_value(JuniorIsaStocksAndShares::default()).unwrap();
+        jisa["child"] = json!(child);
+        jisa["recommendations"]["productRetention"] = json!({
+            "type": "retain",
+            "rationale": "Still suitable",
+            "recommendedProductCharges": jisa["charges"].clone(),
+            "recommendedInvestmentStrategy": { "rebalance": { "rationale": "Keep to the portfolio", "recommendedInvestmentStrategy": jisa["currentInvestmentStrategy"].clone() } },
+            "linkedObjectives": [],
+            "recommendationActions": [{
+                "type": "singleContribution", "value": subscription, "executiveSummaryDescription": "Subscribe to the JISA", "rationale": null,
+                "dateOfAction": null, "taxYearOfAction": "2026/2027"
+            }]
+        });
+        let products = serde_json::from_value(json!([{
+            "type": "existingSingleOwnedProduct",
+            "id": "7c1e9b2a-4d3f-4a6e-8b5c-3e2f1a0d9c8b",
+            "platform_or_account_number": null,
+            "account_or_reference_number": { "type": "other", "content": "JISA123" },
+            "account_type": { "JuniorIsaStocksAndShares": jisa }
+        }])).unwrap();
+        Some(AdviceAreasAndProducts { advice_areas: None, products: Some(products), pension_allowances: None, iht_estate: None })
+    }
+
+    #[test]
+    fn test_parents_share_their_childs_junior_isa_allowance() {
+        let within = create_isa_allowance_texts(
+            [("Jane Smith", &junior_isa_products("Sam", 4000.0)), ("John Smith", &junior_isa_products("Sam", 5000.0))],
+            &WordingTemplates::default(),
+            &TaxRules::default(),
+            &current_tax_year()
+        ).unwrap();
+        assert_eq!(within.len(), 1);
+        assert_eq!(within[0].0, "Joint");
+        assert_eq!(within[0].1.title, "Junior ISA Allowance");
+        assert!(within[0].1.paragraph.contains("total £9,000.00"));
+
+        let breach = create_isa_allowance_texts(
+            [("Jane Smith", &junior_isa_products("Sam", 5000.0)), ("John Smith", &junior_isa_products("Sam", 5000.0))],
+            &WordingTemplates::default(),
+            &TaxRules::default(),
+            &current_tax_year()
+        ).unwrap_err();
+        assert!(breach.contains("£10000.00 exceed the £9000.00 Junior ISA allowance for Sam"));
+    }
+
+    fn junior_isa(child: &str) -> AccountType {
+        let mut jisa = serde_json::to_value(JuniorIsaStocksAndShares::default()).unwrap();
+        jisa["child"] = json!(child);
+        AccountType::JuniorIsaStocksAndShares(serde_json::from_value(jisa).unwrap())
+    }
+
+    #[test]
+    fn test_junior_isas_share_their_childs_allowance() {
+        let tax_year = TaxYear::parse("2026/2027").unwrap();
+        let mut ledger = IsaAllowanceLedger::default();
+        ledger.subscribe(tax_year.clone(), isa_allowance(&junior_isa("Sam")).unwrap(), 6000.0);
+        ledger.subscribe(tax_year.clone(), isa_allowance(&junior_isa("Sam")).unwrap(), 2000.0);
+        ledger.subscribe(tax_year.clone(), isa_allowance(&junior_isa("Alex")).unwrap(), 9000.0);
+
+        let usage = ledger.usage(&TaxRules::default()).unwrap();
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[1].allowance, IsaAllowance::Junior("Sam".to_string()));
+        assert_eq!(usage[1].subscribed, 8000.0);
+
+        ledger.subscribe(tax_year, isa_allowance(&junior_isa("Sam")).unwrap(), 1500.0);
+        assert!(ledger.usage(&TaxRules::default()).unwrap_err().contains("Junior ISA allowance for Sam"));
+    }
+
+    #[test]
+    fn test_pension_carry_forward_is_tabled() {
+        let circumstances = PensionAllowanceCircumstances {
+            relevant_earnings: 150000.0,
+            threshold_income: 120000.0,
+            adjusted_income: 120000.0,
+            flexibly_accessed: false,
+            unused_allowance: [20000.0, 15000.0, 10000.0]
+        };
+        let tax_rules = TaxRules::default();
+        let tax_year = TaxYear::parse("2026/2027").unwrap();
+        let check = pension_allowance::check(90000.0, &circumstances, &tax_rules.for_tax_year(&tax_year).unwrap().pension).unwrap();
+
+        let text = create_pension_allowance_text(&check, &tax_year, &WordingTemplates::default()).unwrap();
+
+        assert!(text.paragraph.contains("£30,000.00"));
+        let table = text.table.unwrap();
+        assert_eq!(table.rows[0], vec!["2023/2024", "£20,000.00", "£20,000.00", "£0.00"]);
+        assert_eq!(table.rows[2][0], "2025/2026");
+    }
+
+    #[test]
+    fn test_regular_withdrawal_range_of_outcomes_is_tabled() {
+        let RecommendedAction::RegularWithdrawal(withdrawal) = contribution(json!({
+            "type": "regularWithdrawal", "value": 1500.0, "frequency": "Monthly", "startDateOfAction": "2027-01-01", "endDateOfAction": null
+        })) else {
+            panic!("Expected a regular withdrawal");
+        };
+        let assumptions = ProjectionAssumptions::default();
+        let market = assumptions.market_assumption(&RiskProfile::Moderate, None);
+
+        let text = create_range_of_outcomes_text("Transact SIPP123", 400000.0, 0.01, market, &withdrawal, &WordingTemplates::default(), &assumptions, 2027).unwrap();
+
+        assert_eq!(text.title, "Range of Outcomes");
+        assert!(text.paragraph.contains("withdrawals of £18,000.00 a year from 2027 to 2056"));
+        let table = text.table.unwrap();
+        let years: Vec<&str> = table.rows.iter().map(|row| row[0].as_str()).collect();
+        assert_eq!(years, vec!["2027", "2032", "2037", "2042", "2047", "2052", "2056"]);
+        // The same seed gives the same table every time
+        let again = create_range_of_outcomes_text("Transact SIPP123", 400000.0, 0.01, market, &withdrawal, &WordingTemplates::default(), &assumptions, 2027).unwrap();
+        assert_eq!(again.table.unwrap().rows, table.rows);
+    }
+
+    #[test]
+    fn test_later_withdrawal_is_simulated_from_the_as_at_year() {
+        let RecommendedAction::RegularWithdrawal(withdrawal) = contribution(json!({
+            "type": "regularWithdrawal", "value": 1500.0, "frequency": "Monthly", "startDateOfAction": "2029-01-01", "endDateOfAction": "2038-12-31"
+        })) else {
+            panic!("Expected a regular withdrawal");
+        };
+        let assumptions = ProjectionAssumptions::default();
+        let market = assumptions.market_assumption(&RiskProfile::Moderate, None);
+
+        let text = create_range_of_outcomes_text("Transact SIPP123", 400000.0, 0.01, market, &withdrawal, &WordingTemplates::default(), &assumptions, 2026).unwrap();
+
+        assert!(text.paragraph.contains("withdrawals of £18,000.00 a year from 2029 to 2038"));
+        let table = text.table.unwrap();
+        let years: Vec<&str> = table.rows.iter().map(|row| row[0].as_str()).collect();
+        assert_eq!(years, vec!["2026", "2031", "2036", "2038"]);
+        // Nothing is withdrawn before 2029, so the median grows in the first year
+        let median: f64 = table.rows[0][2].trim_start_matches('£').replace(',', "").parse().unwrap();
+        assert!(median > 400000.0);
+
+        let ended = create_range_of_outcomes_text("Transact SIPP123", 400000.0, 0.01, market, &withdrawal, &WordingTemplates::default(), &assumptions, 2039);
+        assert!(ended.is_none());
+    }
+
+    fn emergency_fund_advice(advice: Option<&str>) -> EmergencyFundAdvice {
+        serde_json::from_value(json!({
+            "advice": advice,
+            "calculation": { "monthlyEssentialExpenditure": 2500.0, "targetMonths": 6, "otherCash": 4000.0 }
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_emergency_fund_is_calculated() {
+        let advice_area = AdviceArea::EmergencyFund(emergency_fund_advice(None));
+        let templates = WordingTemplates::default();
+
+        let short = create_other_advice_area(&advice_area, 6000.0, &templates);
+        assert_eq!(short.title, "Emergency Fund");
+        assert!(short.paragraph.contains("an emergency fund of £15,000.00 in cash. You currently hold £10,000.00, including £6,000.00 in Cash ISAs, a shortfall of £5,000.00."));
+        let table = short.table.unwrap();
+        assert_eq!(table.rows[1], vec!["Months of cover", "6"]);
+        assert_eq!(table.rows[5], vec!["Shortfall", "£5,000.00"]);
+
+        let covered = create_other_advice_area(&advice_area, 20000.0, &templates);
+        assert!(covered.paragraph.contains("with £9,000.00 to spare"));
+        assert_eq!(covered.table.unwrap().rows[5], vec!["Excess", "£9,000.00"]);
+    }
+
+    #[test]
+    fn test_adviser_can_override_emergency_fund_paragraph() {
+        let advice_area = AdviceArea::EmergencyFund(emergency_fund_advice(Some("Keep your premium bonds as your emergency fund.")));
+
+        let text = create_other_advice_area(&advice_area, 6000.0, &WordingTemplates::default());
+
+        assert_eq!(text.paragraph, "Keep your premium bonds as your emergency fund.");
+        assert_eq!(text.table.unwrap().rows[5], vec!["Shortfall", "£5,000.00"]);
+    }
+
+    fn iht_estate(main_residence: Option<f64>, other_assets: f64) -> IhtEstateBasis {
+        serde_json::from_value(json!({
+            "asAt": "2026-06-01",
+            "mainResidence": main_residence.map(|value| json!({ "value": value, "passesToDirectDescendants": true })),
+            "otherAssets": [{ "description": "Cash and chattels", "value": other_assets }],
+            "liabilities": 0.0,
+            "gifts": [],
+            "transferredNilRateBand": 0.0,
+            "transferredResidenceNilRateBand": 0.0
+        })).unwrap()
+    }
+
+    /// A general investment account kept with a gifted monthly withdrawal, and a SIPP.
+    fn gifting_products() -> Products {
+        let mut gia = serde_json::to_value(GeneralInvestmentAccount::default()).unwrap();
+        gia["currentValue"]["value"] = json!(500000.0);
+        gia["recommendations"]["productRetention"] = json!({
+            "type": "retain",
+            "rationale": "Still suitable",
+            "recommendedProductCharges": gia["charges"].clone(),
+            "recommendedInvestmentStrategy": { "rebalance": { "rationale": "Keep to the portfolio", "recommendedInvestmentStrategy": gia["currentInvestmentStrategy"].clone() } },
+            "linkedObjectives": [],
+            "recommendationActions": [{
+                "type": "regularWithdrawal", "value": 2000.0, "executiveSummaryDescription": "Gift to the children", "rationale": "To reduce your estate",
+                "frequency": "Monthly", "startDateOfAction": "2026-07-01", "taxYearOfAction": null, "endDateOfAction": null, "gifted": true
+            }]
+        });
+        let mut sipp = serde_json::to_value(AccountType::SelfInvestedPersonalPension(Default::default())).unwrap();
+        sipp["SelfInvestedPersonalPension"]["currentValue"]["value"] = json!(200000.0);
+
+        serde_json::from_value(json!([
+            {
+                "type": "existingSingleOwnedProduct",
+                "id": "5f0c7a3e-2b1d-4c8e-9a6f-1e2d3c4b5a69",
+                "platform_or_account_number": null,
+                "account_or_reference_number": { "type": "other", "content": "GIA123" },
+                "account_type": { "GeneralInvestmentAccount": gia }
+            },
+            {
+                "type": "existingSingleOwnedProduct",
+                "id": "0b6a4c1e-7d2f-4e3a-8c5b-2f1e0d9c8b7a",
+                "platform_or_account_number": null,
+                "account_or_reference_number": { "type": "other", "content": "SIPP123" },
+                "account_type": sipp
+            }
+        ])).unwrap()
+    }
+
+    fn advice_areas_and_products(products: Option<Products>, iht_estate: IhtEstateBasis) -> Option<AdviceAreasAndProducts> {
+        Some(AdviceAreasAndProducts { advice_areas: None, products, pension_allowances: None, iht_estate: Some(iht_estate) })
+    }
+
+    #[test]
+    fn test_inheritance_tax_estimate_models_gifts() {
+        let client = advice_areas_and_products(Some(gifting_products()), iht_estate(Some(400000.0), 300000.0));
+
+        let texts = create_inheritance_tax_texts([("Jane Smith", &client), ("John Smith", &None)], &None, false, &WordingTemplates::default(), &TaxRules::default()).unwrap();
+
+        assert_eq!(texts.len(), 1);
+        let (key, text) = &texts[0];
+        assert_eq!(key, "Jane Smith");
+        assert_eq!(text.title, "Inheritance Tax Estimate");
+        assert!(text.paragraph.starts_with("If you were to die today, your estate of £1,200,000.00 could face an estimated Inheritance Tax bill of £280,000.00"));
+        // 83 monthly gifts of £2,000 before seven years are up, the last on 1st May 2033
+        assert!(text.paragraph.contains("withdrawals totalling £166,000.00"));
+        assert!(text.paragraph.contains("would fall to £213,600.00"));
+        let table = text.table.as_ref().unwrap();
+        assert_eq!(table.header, vec!["Inheritance Tax", "Today", "After gifts"]);
+        // The SIPP is outside the estate in 2026/2027
+        assert_eq!(table.rows[0], vec!["Products", "£500,000.00", "£334,000.00"]);
+        assert_eq!(table.rows[4], vec!["Gifts within seven years", "£0.00", "£0.00"]);
+        assert_eq!(table.rows[10], vec!["Total Inheritance Tax", "£280,000.00", "£213,600.00"]);
+    }
+
+    #[test]
+    fn test_married_couple_are_estimated_together() {
+        let client_1 = advice_areas_and_products(None, iht_estate(Some(400000.0), 200000.0));
+        let client_2 = advice_areas_and_products(None, iht_estate(None, 700000.0));
+
+        let texts = create_inheritance_tax_texts([("Jane Smith", &client_1), ("John Smith", &client_2)], &None, true, &WordingTemplates::default(), &TaxRules::default()).unwrap();
+
+        assert_eq!(texts.len(), 1);
+        let (key, text) = &texts[0];
+        assert_eq!(key, "Joint");
+        assert!(text.paragraph.contains("If the second of you were to die today, your combined estate of £1,300,000.00 could face an estimated Inheritance Tax bill of £120,000.00"));
+        let table = text.table.as_ref().unwrap();
+        assert_eq!(table.header, vec!["Inheritance Tax", "Today"]);
+        assert_eq!(table.rows[5], vec!["Nil-rate band", "£650,000.00"]);
+        assert_eq!(table.rows[6], vec!["Residence nil-rate band", "£350,000.00"]);
+    }
+}
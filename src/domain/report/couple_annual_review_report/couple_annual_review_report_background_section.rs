@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::domain::constrained_types::meeting_date::MeetingDate;
 use crate::domain::report::ReportError;
+use crate::domain::report::wording_templates::WordingTemplates;
 use crate::driving::data_transfer_object::report_type_data_transfer_object::couple_annual_review_data_transfer_object::couple_annual_review_report_background_section_dto::CoupleAnnualReviewBackgroundSectionDataTransferObject;
 use crate::domain::report::background_section::{MeetingLocation, AdditionalMeetingAttendee, AdditionalCompanyMeetingAttendee, RelationshipToClient};
 
@@ -19,13 +20,16 @@ pub struct CoupleAnnualReviewReportBackgroundSection(String);
 
 impl CoupleAnnualReviewReportBackgroundSection {
 
-    pub fn new(dto: CoupleAnnualReviewBackgroundSectionDataTransferObject) -> Result<Self, ReportError> {
+    pub fn new(dto: CoupleAnnualReviewBackgroundSectionDataTransferObject, templates: &WordingTemplates) -> Result<Self, ReportError> {
         let background = create_background_text(
             dto,
-            "It was lovely to see you",
+            templates,
         )?;
         Ok(Self(background))
     }
 
+    pub fn text(&self) -> &str {
+        &self.0
+    }
 }
 
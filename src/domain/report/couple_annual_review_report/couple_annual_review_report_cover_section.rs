@@ -1,17 +1,25 @@
 use serde::{Deserialize, Serialize};
 
+use crate::driven::doc_generator::instructions::CoverInstructions;
+
+use crate::domain::report::adviser_profile::AdviserProfile;
+use crate::domain::report::branding::FirmBranding;
+
 use crate::{domain::constrained_types::name_string::NameString, driving::data_transfer_object::report_type_data_transfer_object::couple_annual_review_data_transfer_object::couple_annual_review_report_sections_data_transfer_object::CoupleAnnualReviewReportSectionsDataTransferObject};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CoupleAnnualReviewReportCoverSection {
-    // logo_location: ValidAWSS3Location,
+    branding: FirmBranding,
     report_title: String,
     client_names_paragraph: String,
-    adviser_name_paragraph: String 
+    adviser_name_paragraph: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    adviser_qualifications_paragraph: Option<String>
 }
 
 impl CoupleAnnualReviewReportCoverSection {
+
     pub fn new(
         validated_individual_one_first_name: &NameString,
         validated_individual_one_last_name: &NameString,
@@ -19,9 +27,10 @@ impl CoupleAnnualReviewReportCoverSection {
         validated_individual_two_last_name: &NameString,
         validated_adviser_first_name: &NameString,
         validated_adviser_last_name: &NameString,
+        validated_adviser_profile: Option<&AdviserProfile>,
+        branding: &FirmBranding,
     ) -> Result<Self, (String, String)> {
 
-        //let logo_location = LogoLocation
         let report_title = "Annual Review Report".to_string();
         
 
@@ -45,11 +54,23 @@ impl CoupleAnnualReviewReportCoverSection {
         }
 
         Ok(Self {
+            branding: branding.clone(),
             report_title,
             client_names_paragraph,
-            adviser_name_paragraph: format!("{} {}", validated_adviser_first_name, validated_adviser_last_name)
+            adviser_name_paragraph: format!("{} {}", validated_adviser_first_name, validated_adviser_last_name),
+            adviser_qualifications_paragraph: validated_adviser_profile.and_then(|profile| profile.qualifications_line())
         })
 
 
     }
+
+    pub fn instructions(&self) -> CoverInstructions {
+        CoverInstructions {
+            branding: self.branding.instructions(),
+            report_title: self.report_title.clone(),
+            client_names_paragraph: self.client_names_paragraph.clone(),
+            adviser_name_paragraph: self.adviser_name_paragraph.clone(),
+            adviser_qualifications_paragraph: self.adviser_qualifications_paragraph.clone()
+        }
+    }
 }
\ No newline at end of file
@@ -1,13 +1,23 @@
 use std::collections::HashMap;
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
+use crate::domain::report::contents_section::SectionId;
+use crate::driven::doc_generator::instructions::{CurrentCircumstancesInstructions, LabelledBulletsInstructions};
+use crate::domain::clock::Clock;
+use crate::domain::constrained_types::date::Date;
 use crate::domain::constrained_types::last_review_report_date::LastReviewReportAndMeetingDate;
 use crate::domain::constrained_types::name_string::NameString;
 use crate::domain::report::current_circumstances_section::CoupleIsChangeRiskTolerance;
 use crate::domain::report::current_circumstances_section::IsChangeInCircumstances;
 use crate::domain::report::current_circumstances_section::IsChangeRiskTolerance;
+use crate::domain::projection::ProjectionAssumptions;
+use crate::domain::projection::cash_flow::{self, IncomeProjectionBasis};
+use crate::domain::projection::monte_carlo;
 use crate::domain::report::objectives::ChangeInObjectives;
-use crate::domain::report::objectives::CoupleObjectivesAnnualReview;
+use crate::domain::report::objectives::{ClientFromAge, CoupleObjectivesAnnualReview, ObjectiveType};
+use crate::domain::report::product::ExistingProduct;
+use crate::domain::report::recommendations_section::{AdviceAreasAndProducts, CoupleAdviceAreasAndProducts};
+use crate::domain::report::wording_templates::{TemplateName, WordingTemplates};
 use crate::driving::data_transfer_object::report_type_data_transfer_object::current_circumstances_section_dto::CoupleIsChangeRiskToleranceDto;
 use crate::driving::data_transfer_object::report_type_data_transfer_object::current_circumstances_section_dto::IsChangeInCircumstancesDto;
 use crate::driving::data_transfer_object::report_type_data_transfer_object::objectives_dto::CoupleObjectivesAnnualReviewDto;
@@ -15,6 +25,8 @@ use crate::helpers::general_helpers::construct_objective_bullet_points;
 use crate::helpers::general_helpers::construct_objective_to_risk_profile_couple_client_1_or_2_bullet_points;
 use crate::helpers::general_helpers::construct_objective_to_risk_profile_couple_shared_bullet_points;
 use crate::helpers::general_helpers::extract_objectives_from_couple_objectives_annual_review;
+use crate::helpers::general_helpers::money;
+use crate::helpers::general_helpers::probability;
 
 
 
@@ -33,6 +45,10 @@ pub struct CoupleAnnualReviewReportCurrentCircumstancesSection {
     objective_to_risk_profile_bullets_client_1: Option<HashMap<String, Vec<String>>>,
     objective_to_risk_profile_bullets_client_2: Option<HashMap<String, Vec<String>>>,
     objective_to_risk_profile_bullets_shared: Option<HashMap<String, Vec<String>>>,
+    income_projection_introduction: Option<String>,
+    income_projection_bullets_client_1: Option<HashMap<String, Vec<String>>>,
+    income_projection_bullets_client_2: Option<HashMap<String, Vec<String>>>,
+    income_projection_bullets_shared: Option<HashMap<String, Vec<String>>>,
     if_circumstances_have_changed_paragraph: String,
     previous_review_paragraph: String
 }
@@ -46,14 +62,25 @@ impl CoupleAnnualReviewReportCurrentCircumstancesSection {
         is_change_in_circumstances: IsChangeInCircumstancesDto,
         couple_objectives: &CoupleObjectivesAnnualReview,
         couple_is_risk_tolerance_change: CoupleIsChangeRiskToleranceDto,
+        client_1_date_of_birth: Option<String>,
+        client_2_date_of_birth: Option<String>,
+        couple_advice_areas_and_products: &CoupleAdviceAreasAndProducts,
+        has_contact_details: bool,
+        templates: &WordingTemplates,
+        projection_assumptions: &ProjectionAssumptions,
+        clock: &dyn Clock
     ) -> Result<Self, (String, String)> {
 
         let section_error_str = "Current Circumstances";
-        let last_annual_review_report = LastReviewReportAndMeetingDate::try_from(last_review_report_date).map_err(|e|(section_error_str.to_string(), e))?.formatted_day_month_year();
+        let last_annual_review_report = LastReviewReportAndMeetingDate::try_from((last_review_report_date, clock)).map_err(|e|(section_error_str.to_string(), e))?.formatted_day_month_year();
         let is_change_in_circumstances = IsChangeInCircumstances::try_from(is_change_in_circumstances).map_err(|e|(section_error_str.to_string(), e))?;
         let couple_is_risk_tolerance_change = CoupleIsChangeRiskTolerance::try_from(couple_is_risk_tolerance_change).map_err(|e|(section_error_str.to_string(), e))?;
         let extracted_objectives = extract_objectives_from_couple_objectives_annual_review(&couple_objectives);
-        let objectives_bullet_points_introduction = String::from("To confirm, those objectives are as follows:");
+        let objectives_bullet_points_introduction = templates.render(TemplateName::ObjectivesIntroduction, &[]);
+        let birth_years = BirthYears {
+            client_1: birth_year(client_1_date_of_birth, clock).map_err(|e|(section_error_str.to_string(), e))?,
+            client_2: birth_year(client_2_date_of_birth, clock).map_err(|e|(section_error_str.to_string(), e))?
+        };
 
         if extracted_objectives.client_1_objectives.is_empty() && extracted_objectives.client_2_objectives.is_empty() && extracted_objectives.shared_objectives.is_empty() {
             
@@ -63,7 +90,8 @@ impl CoupleAnnualReviewReportCurrentCircumstancesSection {
 
             let first_paragraph = construct_first_paragraph(
                 &is_change_in_circumstances,
-                &LastReviewReportAndMeetingDate::try_from(last_meeting_date).map_err(|e|(section_error_str.to_string(), e))?
+                &LastReviewReportAndMeetingDate::try_from((last_meeting_date, clock)).map_err(|e|(section_error_str.to_string(), e))?,
+                templates
             );
 
             let circumstances_bullet_points_introduction = construct_circumstances_bullet_points_introduction(&is_change_in_circumstances);
@@ -137,11 +165,42 @@ impl CoupleAnnualReviewReportCurrentCircumstancesSection {
                 )
             };
 
-            let if_circumstances_have_changed_paragraph = String::from("If your circumstances have changed in any way since we last spoke, or you feel that you would benefit from further discussion, please contact me using the details at the end of this report.");
+            let projector = IncomeProjector {
+                birth_years,
+                products: couple_advice_areas_and_products,
+                assumptions: projection_assumptions,
+                templates,
+                current_year: clock.today().year()
+            };
 
-            let previous_review_paragraph = format!(
-                "As part of our ongoing service, we review your overall circumstances and financial arrangements to ensure that you remain on track to achieve the objectives identified. My previous review was completed on the {}",
-                last_annual_review_report
+            let income_projection_bullets_client_1 = projector
+                .bullets(ObjectiveOwner::Client1, &extracted_objectives.client_1_objectives)
+                .map(|bullets| HashMap::from([(client_1_first_name.to_string(), bullets)]));
+            let income_projection_bullets_client_2 = projector
+                .bullets(ObjectiveOwner::Client2, &extracted_objectives.client_2_objectives)
+                .map(|bullets| HashMap::from([(client_2_first_name.to_string(), bullets)]));
+            let income_projection_bullets_shared = projector
+                .bullets(ObjectiveOwner::Shared, &extracted_objectives.shared_objectives)
+                .map(|bullets| HashMap::from([("Shared".to_string(), bullets)]));
+
+            let any_income_projections = income_projection_bullets_client_1.is_some()
+                || income_projection_bullets_client_2.is_some()
+                || income_projection_bullets_shared.is_some();
+            let income_projection_introduction = any_income_projections.then(|| templates.render(
+                TemplateName::IncomeProjectionIntroduction,
+                &[
+                    ("life_expectancy", &projection_assumptions.life_expectancy().to_string()),
+                    ("inflation", &percent(projection_assumptions.inflation()))
+                ]
+            ));
+
+            // Only point the clients to the adviser's details when the report closes with them
+            let contact_template = if has_contact_details { TemplateName::CircumstancesContact } else { TemplateName::CircumstancesContactWithoutDetails };
+            let if_circumstances_have_changed_paragraph = templates.render(contact_template, &[]);
+
+            let previous_review_paragraph = templates.render(
+                TemplateName::PreviousReview,
+                &[("last_review_date", &last_annual_review_report)]
             );
 
 
@@ -158,36 +217,247 @@ impl CoupleAnnualReviewReportCurrentCircumstancesSection {
                 objective_to_risk_profile_bullets_client_1,
                 objective_to_risk_profile_bullets_client_2,
                 objective_to_risk_profile_bullets_shared,
+                income_projection_introduction,
+                income_projection_bullets_client_1,
+                income_projection_bullets_client_2,
+                income_projection_bullets_shared,
                 if_circumstances_have_changed_paragraph,
                 previous_review_paragraph
             })
 
         }
-    }  
+    }
+
+    pub fn instructions(&self, title: String) -> CurrentCircumstancesInstructions {
+        CurrentCircumstancesInstructions {
+            anchor: SectionId::CurrentCircumstances.anchor().to_string(),
+            title,
+            first_paragraph: self.first_paragraph.clone(),
+            circumstances_introduction: self.circumstances_bullet_points_introduction.clone(),
+            circumstances: self.circumstances_bullet_points.clone().unwrap_or_default(),
+            change_in_objectives_paragraph: self.change_in_objectives_paragraph.clone(),
+            objectives_introduction: self.objectives_bullet_points_introduction.clone(),
+            objectives: labelled_bullets(&[
+                &self.objectives_bullet_points_client_1,
+                &self.objectives_bullet_points_client_2,
+                &self.objectives_bullet_points_shared
+            ]),
+            risk_review_paragraph: self.risk_review_paragraph.clone(),
+            objective_risk_profiles: labelled_bullets(&[
+                &self.objective_to_risk_profile_bullets_client_1,
+                &self.objective_to_risk_profile_bullets_client_2,
+                &self.objective_to_risk_profile_bullets_shared
+            ]),
+            income_projections_introduction: self.income_projection_introduction.clone(),
+            income_projections: labelled_bullets(&[
+                &self.income_projection_bullets_client_1,
+                &self.income_projection_bullets_client_2,
+                &self.income_projection_bullets_shared
+            ]),
+            if_circumstances_have_changed_paragraph: self.if_circumstances_have_changed_paragraph.clone(),
+            previous_review_paragraph: self.previous_review_paragraph.clone()
+        }
+    }
 }
 
 
-fn construct_first_paragraph(is_change_in_circumstances: &IsChangeInCircumstances, last_meeting_date: &LastReviewReportAndMeetingDate) -> String {
-    
-    let mut first_paragraph = String::from("In our review meeting we ascertained");
-    match is_change_in_circumstances {
-        IsChangeInCircumstances::ChangeInCircumstances(_) => {
-            first_paragraph.push_str(
-                    &format!("that your circumstances have changed since our previous meeting of the {}.", 
-                    last_meeting_date.formatted_day_month_year()
-                )
-            )
+/// Each map holds one client's, or the shared, bullets under their label.
+fn labelled_bullets(groups: &[&Option<HashMap<String, Vec<String>>>]) -> Vec<LabelledBulletsInstructions> {
+    groups
+        .iter()
+        .filter_map(|group| group.as_ref())
+        .flat_map(|group| {
+            let mut labels: Vec<&String> = group.keys().collect();
+            labels.sort();
+            labels
+                .into_iter()
+                .map(|label| LabelledBulletsInstructions { label: label.clone(), bullets: group[label].clone() })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Whose objectives are being projected, and so whose products fund them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ObjectiveOwner {
+    Client1,
+    Client2,
+    Shared
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct BirthYears {
+    client_1: Option<i32>,
+    client_2: Option<i32>
+}
+
+impl BirthYears {
+    /// The year the client reaches `age`.
+    fn year_at_age(&self, client: &ClientFromAge) -> Option<i32> {
+        match client {
+            ClientFromAge::Client1(age) => self.client_1.map(|year| year + age.value()),
+            ClientFromAge::Client2(age) => self.client_2.map(|year| year + age.value())
         }
-        IsChangeInCircumstances::SomeChangeInCircumstances(_) | IsChangeInCircumstances::NoChangeInCircumstances => {
-            first_paragraph.push_str(
-                &format!("that there have been no major changes in your circumstances since our previous meeting on the {}.",
-                    last_meeting_date.formatted_day_month_year()
-                )
-            );
+    }
+}
+
+fn birth_year(date_of_birth: Option<String>, clock: &dyn Clock) -> Result<Option<i32>, String> {
+    date_of_birth
+        .map(|date_of_birth| {
+            let date_of_birth = Date::try_from(date_of_birth).map_err(|e| format!("Date of birth:{}", e))?;
+            if *date_of_birth.value() >= clock.today() {
+                return Err("Date of birth must be in the past.".to_string());
+            }
+            Ok(date_of_birth.value().year())
+        })
+        .transpose()
+}
+
+/// Projects each income objective against the products that fund it, to say whether it is on track.
+struct IncomeProjector<'a> {
+    birth_years: BirthYears,
+    products: &'a CoupleAdviceAreasAndProducts,
+    assumptions: &'a ProjectionAssumptions,
+    templates: &'a WordingTemplates,
+    current_year: i32
+}
+
+impl IncomeProjector<'_> {
+    /// One bullet per income objective that can be projected, or `None` if there are none.
+    fn bullets(&self, owner: ObjectiveOwner, objectives: &[ObjectiveType]) -> Option<Vec<String>> {
+        let bullets: Vec<String> = objectives
+            .iter()
+            .filter_map(|objective| self.bullet(owner, objective))
+            .collect();
+
+        (!bullets.is_empty()).then_some(bullets)
+    }
+
+    fn bullet(&self, owner: ObjectiveOwner, objective: &ObjectiveType) -> Option<String> {
+        let (annual_income, risk_profile, income_from) = match objective {
+            ObjectiveType::IncomeObjective(objective) => {
+                let income_from = match (&objective.from_year, &objective.from_age, owner) {
+                    (Some(year), _, _) => Some(year.value()),
+                    (None, Some(age), ObjectiveOwner::Client1) => self.birth_years.year_at_age(&ClientFromAge::Client1(age.clone())),
+                    (None, Some(age), ObjectiveOwner::Client2) => self.birth_years.year_at_age(&ClientFromAge::Client2(age.clone())),
+                    // A shared objective's age could be either client's
+                    _ => None
+                };
+                (objective.annual_income.value(), &objective.linked_risk_profile, income_from?)
+            }
+            ObjectiveType::CoupleIncomeObjective(objective) => {
+                let income_from = match (&objective.from_year, &objective.from_age) {
+                    (Some(year), _) => Some(year.value()),
+                    (None, Some(client)) => self.birth_years.year_at_age(client),
+                    (None, None) => None
+                };
+                (objective.annual_income.value(), &objective.linked_risk_profile, income_from?)
+            }
+            ObjectiveType::InRetirementIncomeObjective(objective) => {
+                (objective.annual_income.value(), &objective.linked_risk_profile, self.current_year)
+            }
+            _ => return None
+        };
+
+        let life_expectancy = self.assumptions.life_expectancy();
+        let last_year = match owner {
+            ObjectiveOwner::Client1 => self.birth_years.client_1? + life_expectancy,
+            ObjectiveOwner::Client2 => self.birth_years.client_2? + life_expectancy,
+            // Shared income lasts for as long as either client is expected to live
+            ObjectiveOwner::Shared => self.birth_years.client_1?.max(self.birth_years.client_2?) + life_expectancy
+        };
+
+        let products = self.funding_products(owner, &objective.id());
+        let starting_value: f64 = products.iter().map(|product| product.current_value().value().value()).sum();
+        if starting_value <= 0.0 {
+            return None;
         }
+        // Weighted by value, so larger products' charges count for more
+        let charge_rate = products
+            .iter()
+            .map(|product| product.current_value().value().value() * product.charges().map(|charges| charges.annual_rate()).unwrap_or(0.0))
+            .sum::<f64>() / starting_value;
+
+        let income_from = income_from.max(self.current_year);
+        let basis = IncomeProjectionBasis {
+            starting_value,
+            annual_income: f64::from(annual_income),
+            income_from,
+            first_year: self.current_year,
+            last_year,
+            growth_rate: self.assumptions.growth_rate(risk_profile),
+            charge_rate,
+            inflation: self.assumptions.inflation()
+        };
+        let projection = cash_flow::project(&basis);
+        let outcomes = monte_carlo::simulate(
+            &basis,
+            self.assumptions.market_assumption(risk_profile, None),
+            self.assumptions.simulations(),
+            self.assumptions.seed()
+        );
+
+        let income = money(f64::from(annual_income));
+        let from_year = income_from.to_string();
+        let until_year = last_year.to_string();
+
+        let outlook = match projection.runs_out_in() {
+            None => self.templates.render(
+                TemplateName::IncomeProjectionOnTrack,
+                &[("income", &income), ("from_year", &from_year), ("until_year", &until_year), ("remaining", &money(projection.closing_value()))]
+            ),
+            Some(runs_out_in) => self.templates.render(
+                TemplateName::IncomeProjectionShortfall,
+                &[("income", &income), ("from_year", &from_year), ("until_year", &until_year), ("runs_out_in", &runs_out_in.to_string())]
+            )
+        };
+        let range = self.templates.render(
+            TemplateName::IncomeProjectionProbability,
+            &[("probability", &probability(outcomes.probability_of_success)), ("simulations", &outcomes.simulations.to_string())]
+        );
+
+        Some(format!("{} {}", outlook, range))
     }
 
-    first_paragraph
+    /// The pension and investment products linked to the objective or, when none are, all of
+    /// the owner's. Shared objectives draw on both clients' products and those held jointly.
+    fn funding_products(&self, owner: ObjectiveOwner, objective_id: &str) -> Vec<ExistingProduct> {
+        let owned: Vec<&Option<AdviceAreasAndProducts>> = match owner {
+            ObjectiveOwner::Client1 => vec![&self.products.client_1],
+            ObjectiveOwner::Client2 => vec![&self.products.client_2],
+            ObjectiveOwner::Shared => vec![&self.products.client_1, &self.products.client_2, &self.products.joint]
+        };
+        let existing: Vec<ExistingProduct> = owned
+            .into_iter()
+            .filter_map(|advice_area_products| advice_area_products.as_ref()?.products.as_ref())
+            .flat_map(|products| products.existing_products())
+            .filter(ExistingProduct::is_pension_or_investment)
+            .collect();
+
+        let linked: Vec<ExistingProduct> = existing
+            .iter()
+            .filter(|product| product.linked_objectives().is_some_and(|ids| ids.iter().any(|id| id.to_string() == objective_id)))
+            .cloned()
+            .collect();
+
+        if linked.is_empty() { existing } else { linked }
+    }
+}
+
+/// A fraction as a percentage, such as `0.025` as "2.5%".
+fn percent(rate: f64) -> String {
+    let percent = format!("{:.2}", rate * 100.0);
+    format!("{}%", percent.trim_end_matches('0').trim_end_matches('.'))
+}
+
+fn construct_first_paragraph(is_change_in_circumstances: &IsChangeInCircumstances, last_meeting_date: &LastReviewReportAndMeetingDate, templates: &WordingTemplates) -> String {
+    
+    let template = match is_change_in_circumstances {
+        IsChangeInCircumstances::ChangeInCircumstances(_) => TemplateName::CircumstancesChanged,
+        IsChangeInCircumstances::SomeChangeInCircumstances(_) | IsChangeInCircumstances::NoChangeInCircumstances => TemplateName::CircumstancesUnchanged
+    };
+
+    templates.render(template, &[("last_meeting_date", &last_meeting_date.formatted_day_month_year())])
 
 }
 
@@ -351,4 +621,89 @@ fn construct_couple_risk_review_paragraph(
     risk_review_paragraph.push_str(" As a result, I have reviewed your investment risk tolerance, need, capacity in addition to your financial investment and product knowledge and experience to confirm the outcome as follows for your objectives:");
 
     risk_review_paragraph
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::domain::clock::FixedClock;
+    use crate::domain::report::product::{AccountType, Products};
+
+    fn clock() -> FixedClock {
+        FixedClock(NaiveDate::from_ymd_opt(2026, 6, 1).unwrap())
+    }
+
+    fn sipp_products(value: f64) -> CoupleAdviceAreasAndProducts {
+        let mut sipp = serde_json::to_value(AccountType::SelfInvestedPersonalPension(Default::default())).unwrap();
+        sipp["SelfInvestedPersonalPension"]["currentValue"]["value"] = json!(value);
+        let products: Products = serde_json::from_value(json!([{
+            "type": "existingSingleOwnedProduct",
+            "id": "5f0c7a3e-2b1d-4c8e-9a6f-1e2d3c4b5a69",
+            "platform_or_account_number": null,
+            "account_or_reference_number": { "type": "other", "content": "SIPP123" },
+            "account_type": sipp
+        }])).unwrap();
+
+        CoupleAdviceAreasAndProducts {
+            client_1: Some(AdviceAreasAndProducts { advice_areas: None, products: Some(products), pension_allowances: None, iht_estate: None }),
+            client_2: None,
+            joint: None,
+            married_or_in_civil_partnership: None
+        }
+    }
+
+    fn income_objective(annual_income: f64) -> ObjectiveType {
+        serde_json::from_value(json!({ "incomeObjective": {
+            "id": "0b7f3c52-8d4e-4f1a-b6c9-2e5d7a8f9c10",
+            "annualIncome": annual_income,
+            "frequency": "Annually",
+            "fromYear": null,
+            "fromAge": 65,
+            "linkedRiskProfile": "Moderate"
+        }})).unwrap()
+    }
+
+    fn bullets(products: &CoupleAdviceAreasAndProducts, birth_years: BirthYears, objective: ObjectiveType) -> Option<Vec<String>> {
+        IncomeProjector {
+            birth_years,
+            products,
+            assumptions: &ProjectionAssumptions::default(),
+            templates: &WordingTemplates::default(),
+            current_year: 2026
+        }.bullets(ObjectiveOwner::Client1, &[objective])
+    }
+
+    #[test]
+    fn test_income_objective_is_projected() {
+        let products = sipp_products(600000.0);
+        let birth_years = BirthYears { client_1: Some(1966), client_2: None };
+
+        assert_eq!(
+            bullets(&products, birth_years, income_objective(20000.0)).unwrap(),
+            vec!["Your income objective of £20,000.00 a year in today's money from 2031 is on track, with an estimated £1,313,617.81 projected to remain in 2061. Allowing for the ups and downs of investment markets, the full income was paid in 86% of 1000 simulated outcomes.".to_string()]
+        );
+        assert_eq!(
+            bullets(&products, birth_years, income_objective(50000.0)).unwrap(),
+            vec!["Your income objective of £50,000.00 a year in today's money from 2031 is not on track, as your products are projected to run out in 2048, before 2061. Allowing for the ups and downs of investment markets, the full income was paid in 6% of 1000 simulated outcomes.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_income_objective_needs_date_of_birth_and_products() {
+        assert_eq!(bullets(&sipp_products(600000.0), BirthYears::default(), income_objective(20000.0)), None);
+        assert_eq!(bullets(&sipp_products(0.0), BirthYears { client_1: Some(1966), client_2: None }, income_objective(20000.0)), None);
+    }
+
+    #[test]
+    fn test_date_of_birth() {
+        assert_eq!(birth_year(Some("14/03/1966".to_string()), &clock()), Ok(Some(1966)));
+        assert_eq!(birth_year(None, &clock()), Ok(None));
+        assert!(birth_year(Some("01/01/2030".to_string()), &clock()).is_err());
+    }
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(percent(0.025), "2.5%");
+        assert_eq!(percent(0.03), "3%");
+    }
+}
@@ -1,9 +1,15 @@
 use serde::{Deserialize, Serialize};
 
+use crate::driven::doc_generator::instructions::SectionInstructions;
+
+use crate::domain::clock::Clock;
 use crate::domain::constrained_types::name_string::NameString;
 use crate::domain::report::background_section::BackgroundSection;
 use crate::domain::report::cover_section::CoverSection;
-use crate::domain::report::contents_section::ContentsSection;
+use crate::domain::report::adviser_profile::AdviserProfile;
+use crate::domain::report::report_config::ReportConfig;
+use crate::domain::report::contact_details_section::ContactDetailsSection;
+use crate::domain::report::contents_section::{ContentsSection, SectionId};
 use crate::domain::report::couple_annual_review_report::couple_annual_review_report_background_section::CoupleAnnualReviewReportBackgroundSection;
 use crate::domain::report::couple_annual_review_report::couple_annual_review_report_cover_section::CoupleAnnualReviewReportCoverSection;
 use crate::domain::report::contents_section::AnnualReviewReportContentsSection;
@@ -14,7 +20,7 @@ use crate::domain::DomainError;
 use crate::driven::repository::InvestmentPortfoliosRepository;
 use crate::driving::data_transfer_object::report_type_data_transfer_object::couple_annual_review_data_transfer_object::couple_annual_review_report_sections_data_transfer_object::CoupleAnnualReviewReportSectionsDataTransferObject;
 use crate::domain::report::current_circumstances_section::CurrentCircumstancesSection;
-use crate::domain::report::recommendations_section::RecommendationsSection;
+use crate::domain::report::recommendations_section::{CoupleAdviceAreasAndProducts, RecommendationsSection};
 
 use super::couple_annual_review_recommendations_section::CoupleAnnualReviewReportRecommendationsSection;
 use super::couple_annual_review_report_current_circumstances_section::CoupleAnnualReviewReportCurrentCircumstancesSection;
@@ -28,7 +34,9 @@ pub struct CoupleAnnualReviewReportSections {
     // executive_summary: ExecutiveSummarySection
     background: BackgroundSection,
     current_circumstances: CurrentCircumstancesSection,
-    recommendations: RecommendationsSection
+    recommendations: RecommendationsSection,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    contact_details: Option<ContactDetailsSection>
 }
 
 impl CoupleAnnualReviewReportSections {
@@ -39,8 +47,11 @@ impl CoupleAnnualReviewReportSections {
         validated_individual_two_last_name: &NameString,
         validated_adviser_first_name: &NameString,
         validated_adviser_last_name: &NameString,
+        validated_adviser_profile: Option<&AdviserProfile>,
         unvalidated_sections: CoupleAnnualReviewReportSectionsDataTransferObject,
-        repo: &R
+        repo: &R,
+        report_config: &ReportConfig,
+        clock: &dyn Clock
     ) -> Result<Self, ReportError> where R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync {
 
         let couple_annual_review_report_cover_section = CoverSection::CoupleAnnualReviewReportCoverSection(
@@ -50,11 +61,21 @@ impl CoupleAnnualReviewReportSections {
                 validated_individual_two_first_name,
                 validated_individual_two_last_name,
                 validated_adviser_first_name,
-                validated_adviser_last_name
+                validated_adviser_last_name,
+                validated_adviser_profile,
+                &report_config.branding
             ).map_err(|(section, error)| ReportError::SectionValidationError(section, error))?
         );
 
-        let couple_objectives_annual_review: CoupleObjectivesAnnualReview = CoupleObjectivesAnnualReview::try_from(unvalidated_sections.current_circumstances.couple_objectives).map_err(|e| DomainError::ValidationError("Couple objectives validation error".to_string()))?;
+        let couple_objectives_annual_review: CoupleObjectivesAnnualReview = CoupleObjectivesAnnualReview::try_from((unvalidated_sections.current_circumstances.couple_objectives, clock)).map_err(|e| DomainError::ValidationError("Couple objectives validation error".to_string()))?;
+
+        // Validated once, as income objectives are projected from the same products the recommendations cover
+        let couple_advice_areas_and_products = CoupleAdviceAreasAndProducts::from_dto(unvalidated_sections.recommendations, repo, clock)
+            .await
+            .map_err(|error| ReportError::SectionValidationError("Recommendations".to_string(), error))?;
+
+        let contact_details = validated_adviser_profile
+            .map(|profile| ContactDetailsSection::new(profile, report_config.contents.title(SectionId::ContactDetails)));
 
         let current_circumstances_section = CurrentCircumstancesSection::CoupleAnnualReviewReportCurrentCircumstancesSection(
             CoupleAnnualReviewReportCurrentCircumstancesSection::new(
@@ -64,7 +85,14 @@ impl CoupleAnnualReviewReportSections {
                 unvalidated_sections.current_circumstances.last_meeting_date,
                 unvalidated_sections.current_circumstances.is_change_in_circumstances,
                 &couple_objectives_annual_review,
-                unvalidated_sections.current_circumstances.couple_is_risk_tolerance_change
+                unvalidated_sections.current_circumstances.couple_is_risk_tolerance_change,
+                unvalidated_sections.current_circumstances.client_1_date_of_birth,
+                unvalidated_sections.current_circumstances.client_2_date_of_birth,
+                &couple_advice_areas_and_products,
+                contact_details.is_some(),
+                &report_config.templates,
+                &report_config.projection_assumptions,
+                clock
             ).map_err(|(section, error)| ReportError::SectionValidationError(section, error))?
         );
 
@@ -74,19 +102,45 @@ impl CoupleAnnualReviewReportSections {
                 validated_individual_two_first_name,
                 validated_individual_one_last_name,
                 validated_individual_two_last_name,
-                unvalidated_sections.recommendations,
+                couple_advice_areas_and_products,
                 &couple_objectives_annual_review,
-                repo
-            ).await.map_err(|(section, error)| ReportError::SectionValidationError(section, error))?
+                &report_config.templates,
+                &report_config.tax_rules,
+                &report_config.projection_assumptions,
+                clock
+            ).map_err(|(section, error)| ReportError::SectionValidationError(section, error))?
         );
 
+        // Contents only list the sections this report actually contains
+        let mut present_sections = vec![SectionId::Background, SectionId::CurrentCircumstances, SectionId::Recommendations];
+        if contact_details.is_some() {
+            present_sections.push(SectionId::ContactDetails);
+        }
+
         Ok(Self {
             cover: couple_annual_review_report_cover_section,
-            contents: ContentsSection::AnnualReviewReportContentsSection(AnnualReviewReportContentsSection::new()?),
-            background: BackgroundSection::CoupleAnnualReviewReportBackgroundSection(CoupleAnnualReviewReportBackgroundSection::new(unvalidated_sections.background)?),
+            contents: ContentsSection::AnnualReviewReportContentsSection(AnnualReviewReportContentsSection::new(&present_sections, &report_config.contents)?),
+            background: BackgroundSection::CoupleAnnualReviewReportBackgroundSection(CoupleAnnualReviewReportBackgroundSection::new(unvalidated_sections.background, &report_config.templates)?),
             current_circumstances: current_circumstances_section,
-            recommendations: recommendations_section
+            recommendations: recommendations_section,
+            contact_details
         })
 
     }
+
+    pub fn instructions(&self) -> Vec<SectionInstructions> {
+        let mut sections = vec![
+            SectionInstructions::Cover(self.cover.instructions()),
+            SectionInstructions::Contents(self.contents.instructions()),
+            SectionInstructions::Background(self.background.instructions(self.contents.title(SectionId::Background))),
+            SectionInstructions::CurrentCircumstances(self.current_circumstances.instructions(self.contents.title(SectionId::CurrentCircumstances))),
+            SectionInstructions::Recommendations(self.recommendations.instructions(self.contents.title(SectionId::Recommendations))),
+        ];
+
+        if let Some(contact_details) = &self.contact_details {
+            sections.push(SectionInstructions::ContactDetails(contact_details.instructions()));
+        }
+
+        sections
+    }
 }
\ No newline at end of file
@@ -2,11 +2,13 @@ use std::sync::Arc;
 
 use couple_annual_review_report_sections::CoupleAnnualReviewReportSections;
 use serde::{Deserialize, Serialize};
+use crate::driven::doc_generator::instructions::SectionInstructions;
 
 use crate::{domain::constrained_types::{adviser::Adviser, client_id::ClientId, name_string::NameString}, driven::repository::InvestmentPortfoliosRepository, driving::data_transfer_object::report_type_data_transfer_object::couple_annual_review_data_transfer_object::{couple_annual_review_report_sections_data_transfer_object::CoupleAnnualReviewReportSectionsDataTransferObject, CoupleAnnualReviewReportDataTransferObject}};
 use crate::domain::DomainError;
+use crate::domain::clock::Clock;
 
-use super::{investment_holdings::InvestmentPortfolio, ReportError};
+use super::{adviser_profile::AdviserProfile, investment_holdings::InvestmentPortfolio, report_config::ReportConfig, ReportError};
 
 pub mod couple_annual_review_report_sections;
 pub mod couple_annual_review_report_cover_section;
@@ -28,8 +30,11 @@ impl CoupleAnnualReviewReport {
         unvalidated_individual_two_last_name: String,
         unvalidated_adviser_first_name: String,
         unvalidated_adviser_last_name: String,
+        adviser_profile: Option<AdviserProfile>,
         unvalidated_sections: CoupleAnnualReviewReportSectionsDataTransferObject,
-        investment_repo: &R
+        investment_repo: &R,
+        report_config: &ReportConfig,
+        clock: &dyn Clock
     ) -> Result<Self, ReportError> where R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync {
 
         let individual_one_first_name = NameString::try_from(unvalidated_individual_one_first_name).map_err(|e| ReportError::DomainError(DomainError::ValidationError(e.to_string())))?;
@@ -39,7 +44,8 @@ impl CoupleAnnualReviewReport {
         
         let adviser = Adviser::new(
             unvalidated_adviser_first_name,
-            unvalidated_adviser_last_name
+            unvalidated_adviser_last_name,
+            adviser_profile
         ).map_err(|e| ReportError::DomainError(DomainError::ValidationError(e.to_string())))?;
 
         let couple_annual_review_report_sections = CoupleAnnualReviewReportSections::new(
@@ -49,8 +55,11 @@ impl CoupleAnnualReviewReport {
             &individual_two_last_name,
             &adviser.adviser_first_name,
             &adviser.adviser_last_name,
+            adviser.profile.as_ref(),
             unvalidated_sections,
-            investment_repo
+            investment_repo,
+            report_config,
+            clock
         ).await?;
         
         Ok(Self {
@@ -61,7 +70,10 @@ impl CoupleAnnualReviewReport {
 
     pub async fn from_dto<R>(
         dto: CoupleAnnualReviewReportDataTransferObject,
-        investment_portfolio_repo: Arc<R>
+        investment_portfolio_repo: Arc<R>,
+        adviser_profile: Option<AdviserProfile>,
+        report_config: &ReportConfig,
+        clock: &dyn Clock
     ) -> Result<Self, ReportError> where R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync {
 
         let couple_annual_review_report = CoupleAnnualReviewReport::new(
@@ -71,13 +83,19 @@ impl CoupleAnnualReviewReport {
             dto.individual_two_last_name, 
             dto.adviser.adviser_first_name, 
             dto.adviser.adviser_last_name, 
+            adviser_profile,
             dto.sections, 
-            investment_portfolio_repo.as_ref()
+            investment_portfolio_repo.as_ref(),
+            report_config,
+            clock
         ).await?;
 
         Ok(couple_annual_review_report)
 
     }
 
+    pub fn instructions(&self) -> Vec<SectionInstructions> {
+        self.sections.instructions()
+    }
 }
 
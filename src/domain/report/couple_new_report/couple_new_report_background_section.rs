@@ -14,4 +14,7 @@ impl CoupleNewReportBackgroundSection {
         Ok(Self { background: ConstrainedString1000::try_from(dto.text).map_err(|e| ReportError::SectionValidationError("Background".to_string(), e.to_string()))?.to_string() })
     }
 
+    pub fn text(&self) -> &str {
+        &self.background
+    }
 }
\ No newline at end of file
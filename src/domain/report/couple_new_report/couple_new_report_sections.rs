@@ -1,9 +1,14 @@
 use serde::{Deserialize, Serialize};
 
+use crate::driven::doc_generator::instructions::SectionInstructions;
+
 use crate::domain::constrained_types::name_string::NameString;
 use crate::domain::report::background_section::BackgroundSection;
 use crate::domain::report::cover_section::CoverSection;
-use crate::domain::report::contents_section::{ContentsSection, NewReportContentsSection};
+use crate::domain::report::adviser_profile::AdviserProfile;
+use crate::domain::report::report_config::ReportConfig;
+use crate::domain::report::contact_details_section::ContactDetailsSection;
+use crate::domain::report::contents_section::{ContentsSection, NewReportContentsSection, SectionId};
 
 use crate::domain::report::couple_new_report::couple_new_report_cover_section::CoupleNewReportCoverSection;
 use crate::domain::report::couple_new_report::couple_new_report_background_section::CoupleNewReportBackgroundSection;
@@ -19,6 +24,8 @@ pub struct CoupleNewReportSections {
     // executive_summary: ExecutiveSummarySection
     background: BackgroundSection,
     // current_circumstances: CurrentCircumstancesSection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    contact_details: Option<ContactDetailsSection>
 }
 
 impl CoupleNewReportSections {
@@ -29,7 +36,9 @@ impl CoupleNewReportSections {
         validated_individual_two_last_name: &NameString,
         validated_adviser_first_name: &NameString,
         validated_adviser_last_name: &NameString,
-        unvalidated_sections: CoupleNewReportSectionsDto
+        validated_adviser_profile: Option<&AdviserProfile>,
+        unvalidated_sections: CoupleNewReportSectionsDto,
+        report_config: &ReportConfig
     ) -> Result<Self, ReportError> {
 
         let couple_new_report_cover_section = CoverSection::CoupleNewReportCoverSection(
@@ -39,15 +48,41 @@ impl CoupleNewReportSections {
                 validated_individual_two_first_name,
                 validated_individual_two_last_name,
                 validated_adviser_first_name,
-                validated_adviser_last_name
+                validated_adviser_last_name,
+                validated_adviser_profile,
+                &report_config.branding
             ).map_err(|(section, error)| ReportError::SectionValidationError(section, error))?
         );
 
+        let contact_details = validated_adviser_profile
+            .map(|profile| ContactDetailsSection::new(profile, report_config.contents.title(SectionId::ContactDetails)));
+
+        // Contents only list the sections this report actually contains
+        let mut present_sections = vec![SectionId::Background];
+        if contact_details.is_some() {
+            present_sections.push(SectionId::ContactDetails);
+        }
+
         Ok(Self {
             cover: couple_new_report_cover_section,
-            contents: ContentsSection::NewReportContentsSection(NewReportContentsSection::new()?),
-            background: BackgroundSection::CoupleNewReportBackgroundSection(CoupleNewReportBackgroundSection::new(unvalidated_sections.background)?)
+            contents: ContentsSection::NewReportContentsSection(NewReportContentsSection::new(&present_sections, &report_config.contents)?),
+            background: BackgroundSection::CoupleNewReportBackgroundSection(CoupleNewReportBackgroundSection::new(unvalidated_sections.background)?),
+            contact_details
         })
 
     }
+
+    pub fn instructions(&self) -> Vec<SectionInstructions> {
+        let mut sections = vec![
+            SectionInstructions::Cover(self.cover.instructions()),
+            SectionInstructions::Contents(self.contents.instructions()),
+            SectionInstructions::Background(self.background.instructions(self.contents.title(SectionId::Background))),
+        ];
+
+        if let Some(contact_details) = &self.contact_details {
+            sections.push(SectionInstructions::ContactDetails(contact_details.instructions()));
+        }
+
+        sections
+    }
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
+use crate::driven::doc_generator::instructions::SectionInstructions;
 
 use crate::domain::constrained_types::{name_string::NameString, adviser::Adviser};
 use crate::domain::report::couple_new_report::couple_new_report_sections::CoupleNewReportSections;
@@ -10,6 +11,8 @@ use crate::driven::repository::InvestmentPortfoliosRepository;
 use crate::driving::data_transfer_object::report_type_data_transfer_object::couple_new_report_dto::couple_new_report_sections_dto::CoupleNewReportSectionsDto;
 use crate::driving::data_transfer_object::report_type_data_transfer_object::couple_new_report_dto::CoupleNewReportDto;
 
+use super::adviser_profile::AdviserProfile;
+use super::report_config::ReportConfig;
 use super::investment_holdings::InvestmentPortfolio;
 use super::ReportError;
 
@@ -32,8 +35,10 @@ impl CoupleNewReport {
         unvalidated_individual_two_last_name: String,
         unvalidated_adviser_first_name: String,
         unvalidated_adviser_last_name: String,
+        adviser_profile: Option<AdviserProfile>,
         unvalidated_sections: CoupleNewReportSectionsDto,
-        investment_portfolio_repo: &R
+        investment_portfolio_repo: &R,
+        report_config: &ReportConfig
     ) -> Result<Self, ReportError> where R: InvestmentPortfoliosRepository<InvestmentPortfolio> {
 
         let individual_one_first_name = NameString::try_from(unvalidated_individual_one_first_name).map_err(|e| ReportError::DomainError(DomainError::ValidationError(e.to_string())))?;
@@ -43,7 +48,8 @@ impl CoupleNewReport {
         
         let adviser = Adviser::new(
             unvalidated_adviser_first_name,
-            unvalidated_adviser_last_name
+            unvalidated_adviser_last_name,
+            adviser_profile
         ).map_err(|e| ReportError::DomainError(DomainError::ValidationError(e.to_string())))?;
 
         let couple_new_report_sections = CoupleNewReportSections::new(
@@ -53,7 +59,9 @@ impl CoupleNewReport {
             &individual_two_last_name,
             &adviser.adviser_first_name,
             &adviser.adviser_last_name,
-            unvalidated_sections
+            adviser.profile.as_ref(),
+            unvalidated_sections,
+            report_config
         )?;
         
         Ok(Self {
@@ -64,7 +72,9 @@ impl CoupleNewReport {
 
     pub async fn from_dto<R>(
         dto: CoupleNewReportDto,
-        investment_portfolio_repo: Arc<R>
+        investment_portfolio_repo: Arc<R>,
+        adviser_profile: Option<AdviserProfile>,
+        report_config: &ReportConfig
     ) -> Result<Self, ReportError> where R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync {
 
         let couple_annual_review_report = CoupleNewReport::new(
@@ -74,11 +84,17 @@ impl CoupleNewReport {
             dto.individual_two_last_name, 
             dto.adviser.adviser_first_name, 
             dto.adviser.adviser_last_name, 
+            adviser_profile,
             dto.sections, 
-            investment_portfolio_repo.as_ref()
+            investment_portfolio_repo.as_ref(),
+            report_config
         ).await?;
 
         Ok(couple_annual_review_report)
 
     }
+
+    pub fn instructions(&self) -> Vec<SectionInstructions> {
+        self.sections.instructions()
+    }
 }
\ No newline at end of file
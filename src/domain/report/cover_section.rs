@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::driven::doc_generator::instructions::CoverInstructions;
+
 use super::couple_annual_review_report::couple_annual_review_report_cover_section::CoupleAnnualReviewReportCoverSection;
 use super::couple_new_report::couple_new_report_cover_section::CoupleNewReportCoverSection;
 use super::individual_annual_review_report::individual_annual_review_report_cover_section::IndividualAnnualReviewReportCoverSection;
@@ -12,3 +14,12 @@ pub enum CoverSection {
     CoupleNewReportCoverSection(CoupleNewReportCoverSection)
 }
 
+impl CoverSection {
+    pub fn instructions(&self) -> CoverInstructions {
+        match self {
+            CoverSection::CoupleAnnualReviewReportCoverSection(cover) => cover.instructions(),
+            CoverSection::IndividualAnnualReviewReportCoverSection(cover) => cover.instructions(),
+            CoverSection::CoupleNewReportCoverSection(cover) => cover.instructions()
+        }
+    }
+}
@@ -2,25 +2,31 @@ use std::sync::Arc;
 
 use serde::Serialize;
 use crate::domain::constrained_types::client_id::{ClientId, IoId};
+use crate::domain::clock::Clock;
 use crate::domain::report::Report;
-use crate::driven::repository::InvestmentPortfoliosRepository;
+use crate::driven::repository::{AdviserRepository, InvestmentPortfoliosRepository};
 use crate::driving::data_transfer_object::report_type_data_transfer_object::ReportTypeDataTransferObject;
 
+use super::adviser_profile::AdviserProfile;
+use super::report_config::ReportConfig;
 use super::investment_holdings::InvestmentPortfolio;
 use super::ReportError;
 
 
-pub async fn create_report<R>(
+pub async fn create_report<R, A>(
     data_transfer_object: ReportTypeDataTransferObject, 
-    investment_portfolio_repo: Arc<R>
-) -> Result<Report, ReportError> where R: InvestmentPortfoliosRepository<InvestmentPortfolio> +  Sync{
+    investment_portfolio_repo: Arc<R>,
+    adviser_repo: Arc<A>,
+    report_config: &ReportConfig,
+    clock: &dyn Clock
+) -> Result<Report, ReportError> where R: InvestmentPortfoliosRepository<InvestmentPortfolio> +  Sync, A: AdviserRepository<AdviserProfile> + Sync {
 
     match &data_transfer_object {
         ReportTypeDataTransferObject::CoupleAnnualReviewReportDataTransferObject(couple_annual_review_data_transfer_object) => {
             
             // TODO - check for existing reports with similar names and dates of contruction - get response to continue
 
-            let report = Report::new(data_transfer_object, investment_portfolio_repo).await?;
+            let report = Report::new(data_transfer_object, investment_portfolio_repo, adviser_repo, report_config, clock).await?;
             
             Ok(report)
 
@@ -31,7 +37,7 @@ pub async fn create_report<R>(
             
             // TODO - check for existing reports with similar names and dates of contruction - get response to continue
             
-            let report = Report::new(data_transfer_object, investment_portfolio_repo).await?;
+            let report = Report::new(data_transfer_object, investment_portfolio_repo, adviser_repo, report_config, clock).await?;
             
             Ok(report)
 
@@ -42,7 +48,7 @@ pub async fn create_report<R>(
             
             // TODO - check for existing reports with similar names and dates of contruction - get response to continue
             
-            let report = Report::new(data_transfer_object, investment_portfolio_repo).await?;
+            let report = Report::new(data_transfer_object, investment_portfolio_repo, adviser_repo, report_config, clock).await?;
             
             Ok(report)
 
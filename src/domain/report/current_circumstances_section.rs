@@ -1,6 +1,8 @@
 use std::fmt;
 use serde::{Deserialize, Serialize};
 
+use crate::driven::doc_generator::instructions::CurrentCircumstancesInstructions;
+
 use crate::{domain::{constrained_types::{constrained_string_1000::ConstrainedString1000, name_string::NameString}, report::couple_annual_review_report::couple_annual_review_report_current_circumstances_section::CoupleAnnualReviewReportCurrentCircumstancesSection}, driving::data_transfer_object::report_type_data_transfer_object::current_circumstances_section_dto::{CoupleIsChangeRiskToleranceDto, CurrentCircumstancesSectionDto, IsChangeInCircumstancesDto, IsChangeRiskToleranceDto}};
 
 use super::risk_assessment::RiskProfile;
@@ -11,6 +13,14 @@ pub enum CurrentCircumstancesSection {
     CoupleAnnualReviewReportCurrentCircumstancesSection(CoupleAnnualReviewReportCurrentCircumstancesSection),
 }
 
+impl CurrentCircumstancesSection {
+    pub fn instructions(&self, title: String) -> CurrentCircumstancesInstructions {
+        match self {
+            CurrentCircumstancesSection::CoupleAnnualReviewReportCurrentCircumstancesSection(section) => section.instructions(title)
+        }
+    }
+}
+
 
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
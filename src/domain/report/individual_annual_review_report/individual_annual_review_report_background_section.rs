@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::domain::constrained_types::meeting_date::MeetingDate;
 use crate::domain::report::ReportError;
+use crate::domain::report::wording_templates::WordingTemplates;
 use crate::driving::data_transfer_object::report_type_data_transfer_object::individual_annual_review_data_transfer_object::individual_annual_review_report_background_section::IndividualAnnualReviewBackgroundSectionDataTransferObject;
 
 use crate::domain::report::background_section::{MeetingLocation, AdditionalMeetingAttendee, AdditionalCompanyMeetingAttendee, RelationshipToClient};
@@ -16,10 +17,10 @@ pub struct IndividualAnnualReviewReportBackgroundSection {
 }
 
 impl IndividualAnnualReviewReportBackgroundSection {
-    pub fn new(dto: IndividualAnnualReviewBackgroundSectionDataTransferObject) -> Result<Self, ReportError> {
+    pub fn new(dto: IndividualAnnualReviewBackgroundSectionDataTransferObject, templates: &WordingTemplates) -> Result<Self, ReportError> {
         let background = create_background_text(
             dto,
-            "It was lovely to see you",
+            templates,
         )?;
         Ok(Self { background })
     }
@@ -107,4 +108,7 @@ impl IndividualAnnualReviewReportBackgroundSection {
 
     // }
 
+    pub fn text(&self) -> &str {
+        &self.background
+    }
 }
@@ -1,9 +1,14 @@
 use serde::{Deserialize, Serialize};
 
+use crate::driven::doc_generator::instructions::SectionInstructions;
+
 use crate::domain::constrained_types::name_string::NameString;
 use crate::domain::report::background_section::BackgroundSection;
 use crate::domain::report::cover_section::CoverSection;
-use crate::domain::report::contents_section::ContentsSection;
+use crate::domain::report::adviser_profile::AdviserProfile;
+use crate::domain::report::report_config::ReportConfig;
+use crate::domain::report::contact_details_section::ContactDetailsSection;
+use crate::domain::report::contents_section::{ContentsSection, SectionId};
 use crate::domain::report::individual_annual_review_report::individual_annual_review_report_cover_section::IndividualAnnualReviewReportCoverSection;
 use crate::domain::report::contents_section::AnnualReviewReportContentsSection;
 use crate::domain::report::individual_annual_review_report::individual_annual_review_report_background_section::IndividualAnnualReviewReportBackgroundSection;
@@ -17,7 +22,9 @@ pub struct IndividualAnnualReviewReportSections {
     cover: CoverSection,
     contents: ContentsSection,
     // executive_summary: ExecutiveSummarySection
-    background: BackgroundSection
+    background: BackgroundSection,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    contact_details: Option<ContactDetailsSection>
 }
 
 impl IndividualAnnualReviewReportSections {
@@ -26,7 +33,9 @@ impl IndividualAnnualReviewReportSections {
         validated_individual_one_last_name: &NameString,
         validated_adviser_first_name: &NameString,
         validated_adviser_last_name: &NameString,
-        unvalidated_sections: IndividualAnnualReviewReportSectionsDataTransferObject
+        validated_adviser_profile: Option<&AdviserProfile>,
+        unvalidated_sections: IndividualAnnualReviewReportSectionsDataTransferObject,
+        report_config: &ReportConfig
     ) -> Result<Self, ReportError> {
 
         let individual_annual_review_report_cover_section = CoverSection::IndividualAnnualReviewReportCoverSection(
@@ -34,15 +43,41 @@ impl IndividualAnnualReviewReportSections {
                 validated_individual_one_first_name,
                 validated_individual_one_last_name,
                 validated_adviser_first_name,
-                validated_adviser_last_name
+                validated_adviser_last_name,
+                validated_adviser_profile,
+                &report_config.branding
             ).map_err(|(section, error)| ReportError::SectionValidationError(section, error))?
         );
 
+        let contact_details = validated_adviser_profile
+            .map(|profile| ContactDetailsSection::new(profile, report_config.contents.title(SectionId::ContactDetails)));
+
+        // Contents only list the sections this report actually contains
+        let mut present_sections = vec![SectionId::Background];
+        if contact_details.is_some() {
+            present_sections.push(SectionId::ContactDetails);
+        }
+
         Ok(Self {
             cover: individual_annual_review_report_cover_section,
-            contents: ContentsSection::AnnualReviewReportContentsSection(AnnualReviewReportContentsSection::new()?),
-            background: BackgroundSection::IndividualAnnualReviewBackgroundSection(IndividualAnnualReviewReportBackgroundSection::new(unvalidated_sections.background)?)
+            contents: ContentsSection::AnnualReviewReportContentsSection(AnnualReviewReportContentsSection::new(&present_sections, &report_config.contents)?),
+            background: BackgroundSection::IndividualAnnualReviewBackgroundSection(IndividualAnnualReviewReportBackgroundSection::new(unvalidated_sections.background, &report_config.templates)?),
+            contact_details
         })
 
     }
+
+    pub fn instructions(&self) -> Vec<SectionInstructions> {
+        let mut sections = vec![
+            SectionInstructions::Cover(self.cover.instructions()),
+            SectionInstructions::Contents(self.contents.instructions()),
+            SectionInstructions::Background(self.background.instructions(self.contents.title(SectionId::Background))),
+        ];
+
+        if let Some(contact_details) = &self.contact_details {
+            sections.push(SectionInstructions::ContactDetails(contact_details.instructions()));
+        }
+
+        sections
+    }
 }
\ No newline at end of file
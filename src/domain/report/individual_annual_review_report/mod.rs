@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
+use crate::driven::doc_generator::instructions::SectionInstructions;
 
 use crate::domain::constrained_types::{adviser::Adviser, name_string::NameString};
 use crate::domain::report::individual_annual_review_report::individual_annual_review_report_sections::IndividualAnnualReviewReportSections;
@@ -9,6 +10,8 @@ use crate::driven::repository::InvestmentPortfoliosRepository;
 use crate::driving::data_transfer_object::report_type_data_transfer_object::individual_annual_review_data_transfer_object::individual_annual_review_report_sections_data_transfer_object::IndividualAnnualReviewReportSectionsDataTransferObject;
 use crate::driving::data_transfer_object::report_type_data_transfer_object::individual_annual_review_data_transfer_object::IndividualAnnualReviewReportDataTransferObject;
 
+use super::adviser_profile::AdviserProfile;
+use super::report_config::ReportConfig;
 use super::investment_holdings::InvestmentPortfolio;
 use super::ReportError;
 
@@ -32,8 +35,10 @@ impl IndividualAnnualReviewReport {
         unvalidated_individual_one_last_name: String,
         unvalidated_adviser_first_name: String,
         unvalidated_adviser_last_name: String,
+        adviser_profile: Option<AdviserProfile>,
         unvalidated_sections: IndividualAnnualReviewReportSectionsDataTransferObject,
-        investment_portfolio_repo: &R
+        investment_portfolio_repo: &R,
+        report_config: &ReportConfig
     ) -> Result<Self, ReportError> where R: InvestmentPortfoliosRepository<InvestmentPortfolio> {
 
         let individual_one_first_name = NameString::try_from(unvalidated_individual_one_first_name).map_err(|e| ReportError::DomainError(DomainError::ValidationError(e.to_string())))?;
@@ -41,7 +46,8 @@ impl IndividualAnnualReviewReport {
         
         let adviser = Adviser::new(
             unvalidated_adviser_first_name,
-            unvalidated_adviser_last_name
+            unvalidated_adviser_last_name,
+            adviser_profile
         ).map_err(|e| ReportError::DomainError(DomainError::ValidationError(e.to_string())))?;
 
         let individual_annual_review_report_sections = IndividualAnnualReviewReportSections::new(
@@ -49,7 +55,9 @@ impl IndividualAnnualReviewReport {
             &individual_one_last_name,
             &adviser.adviser_first_name,
             &adviser.adviser_last_name,
-            unvalidated_sections
+            adviser.profile.as_ref(),
+            unvalidated_sections,
+            report_config
         )?;
         
         Ok(Self {
@@ -64,7 +72,9 @@ impl IndividualAnnualReviewReport {
 
     pub async fn from_dto<R>(
         dto: IndividualAnnualReviewReportDataTransferObject,
-        investment_portfolio_repo: Arc<R>
+        investment_portfolio_repo: Arc<R>,
+        adviser_profile: Option<AdviserProfile>,
+        report_config: &ReportConfig
     ) -> Result<Self, ReportError> where R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync {
 
         let individual_annual_review_report = IndividualAnnualReviewReport::new(
@@ -72,11 +82,17 @@ impl IndividualAnnualReviewReport {
             dto.individual_one_last_name,  
             dto.adviser.adviser_first_name, 
             dto.adviser.adviser_last_name, 
+            adviser_profile,
             dto.sections, 
-            investment_portfolio_repo.as_ref()
+            investment_portfolio_repo.as_ref(),
+            report_config
         ).await?;
 
         Ok(individual_annual_review_report)
 
     }
+
+    pub fn instructions(&self) -> Vec<SectionInstructions> {
+        self.sections.instructions()
+    }
 }
\ No newline at end of file
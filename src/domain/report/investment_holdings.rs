@@ -1,6 +1,7 @@
 use std::convert::TryFrom;
 use http::version;
 use serde::{Deserialize, Serialize};
+use tracing::debug;
 
 use crate::{
     domain::{constrained_types::{
@@ -183,7 +184,14 @@ impl Default for InvestmentStrategy {
 // }
 
 impl InvestmentStrategy {
-    
+    /// The model portfolio the strategy follows, or `None` for a bespoke portfolio.
+    pub fn model_portfolio_id(&self) -> Option<&ModelPortfolioId> {
+        match self {
+            InvestmentStrategy::Model(versioned_portfolio) => Some(&versioned_portfolio.id),
+            InvestmentStrategy::Bespoke(_) => None
+        }
+    }
+
     pub async fn from_dto<R>(
         dto: InvestmentStrategyDto, repo: &R
     ) -> Result<Self, String>
@@ -263,7 +271,7 @@ impl TryFrom<InvestmentPortfolioDto> for InvestmentPortfolio {
                 .map(|fh| fh.percentage_of_portfolio.as_ref().unwrap().as_fraction())
                 .sum();
             // Allow a small tolerance for floating‐point rounding.
-            println!("Total Percentage: {}", total_percentage);
+            debug!("Total Percentage: {}", total_percentage);
             if (total_percentage - 1.0).abs() > 0.01 {
                 return Err("Fund holdings percentages do not sum to 100%".to_string());
             }
@@ -271,15 +279,15 @@ impl TryFrom<InvestmentPortfolioDto> for InvestmentPortfolio {
             let sum = fund_holdings
                 .iter()
                 .map(|fh| {
-                    println!("Fund weight percentage: {:?}", fh.percentage_of_portfolio.as_ref().unwrap().as_fraction());
+                    debug!("Fund weight percentage: {:?}", fh.percentage_of_portfolio.as_ref().unwrap().as_fraction());
                     let weight = fh.percentage_of_portfolio.as_ref().unwrap().as_fraction();
-                    println!("Fund charge percentage: {:?}", fh.percentage_of_portfolio.as_ref().unwrap().as_fraction());
+                    debug!("Fund charge percentage: {:?}", fh.percentage_of_portfolio.as_ref().unwrap().as_fraction());
                     let charge = fh.fund_charge.as_fraction();
-                    println!("Computed weighted charge: {:?}", weight * charge);
+                    debug!("Computed weighted charge: {:?}", weight * charge);
                     weight * charge
                 })
                 .sum();
-            println!("Percentages provided Fund holdings total: {:?}", sum);
+            debug!("Percentages provided Fund holdings total: {:?}", sum);
             
             sum
             
@@ -297,16 +305,16 @@ impl TryFrom<InvestmentPortfolioDto> for InvestmentPortfolio {
             let sum = fund_holdings
                 .iter()
                 .map(|fh| {
-                    println!("Computed weight based on value percentage: {:?}", (fh.value.as_ref().unwrap().value() / total_value) as f32);
+                    debug!("Computed weight based on value percentage: {:?}", (fh.value.as_ref().unwrap().value() / total_value) as f32);
                     let weight = (fh.value.as_ref().unwrap().value() / total_value) as f32;
-                    println!("Fund charge percentage: {:?}", fh.percentage_of_portfolio.as_ref().unwrap().as_fraction());
+                    debug!("Fund charge percentage: {:?}", fh.percentage_of_portfolio.as_ref().unwrap().as_fraction());
                     let charge = fh.fund_charge.as_fraction();
-                    println!("Computed weighted charge: {:?}", weight * charge);
+                    debug!("Computed weighted charge: {:?}", weight * charge);
                     weight * charge
                 })
                 .sum();
 
-            println!("Values provided Fund holdings total: {:?}", sum);
+            debug!("Values provided Fund holdings total: {:?}", sum);
 
             sum
 
@@ -314,7 +322,7 @@ impl TryFrom<InvestmentPortfolioDto> for InvestmentPortfolio {
             return Err("Fund holdings must either all have percentages or all have values".to_string());
         };
 
-        println!("Computed charge: {:?}", computed_charge);
+        debug!("Computed charge: {:?}", computed_charge);
         // Convert the computed f32 into a Percentage (using its TryFrom implementation).
         let fund_charges: Percentage = computed_charge.try_into()?;
 
@@ -466,7 +474,7 @@ impl TryFrom<FundHoldingDto> for FundHolding {
 
     fn try_from(dto: FundHoldingDto) -> Result<Self, Self::Error> {
 
-        println!("FundHoldingDto provided for conversion: {:?}", dto);
+        debug!("FundHoldingDto provided for conversion: {:?}", dto);
 
         if dto.value.is_none() && dto.percentage_of_portfolio.is_none() {
             return Err("A fund holding must have either a value or a percentage of the portoflio".to_string())
@@ -1,15 +1,20 @@
 use std::sync::Arc;
 
+use chrono::NaiveDate;
 use investment_holdings::InvestmentPortfolio;
 use report_type::ReportType;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use thiserror::Error;
 
-use crate::driven::repository::InvestmentPortfoliosRepository;
+use adviser_profile::AdviserProfile;
+use report_config::ReportConfig;
+use crate::driven::doc_generator::instructions::{DocumentInstructions, SCHEMA_VERSION};
+use crate::driven::repository::{AdviserRepository, InvestmentPortfoliosRepository};
 use crate::driving::data_transfer_object::report_type_data_transfer_object::ReportTypeDataTransferObject;
 
 use super::DomainError;
+use super::clock::Clock;
 
 pub mod create_report;
 pub mod report_type;
@@ -26,6 +31,11 @@ pub mod recommendations_section;
 pub mod product;
 pub mod advice_areas;
 pub mod investment_holdings;
+pub mod adviser_profile;
+pub mod contact_details_section;
+pub mod branding;
+pub mod report_config;
+pub mod wording_templates;
 
 #[derive(Debug, Error, Deserialize, Serialize)]
 pub enum ReportError {
@@ -46,21 +56,45 @@ pub enum ReportError {
 pub struct Report {
     #[serde(with = "uuid::serde::simple")]
     id: uuid::Uuid,
+    /// Names of the clients, used to file the generated documents
+    client: String,
+    as_at: NaiveDate,
     report_type: report_type::ReportType
 }
 
 impl Report {
-    pub async fn new<R>(
+    pub async fn new<R, A>(
         report_data: ReportTypeDataTransferObject, 
-        investment_portfolio_repo: Arc<R>
-    ) -> Result<Self, ReportError> where R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync {
+        investment_portfolio_repo: Arc<R>,
+        adviser_repo: Arc<A>,
+        report_config: &ReportConfig,
+        clock: &dyn Clock
+    ) -> Result<Self, ReportError> where R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync, A: AdviserRepository<AdviserProfile> + Sync {
+
+        let client = report_data.client_names().join(" & ");
 
         Ok(Self {
             id: Uuid::new_v4(),
-            report_type: ReportType::from_dto(report_data, investment_portfolio_repo).await?
+            client,
+            as_at: clock.today(),
+            report_type: ReportType::from_dto(report_data, investment_portfolio_repo, adviser_repo, report_config, clock).await?
         })
 
     }
+
+    /// The renderer-facing contract for this report.
+    pub fn instructions(&self) -> DocumentInstructions {
+        let (report_kind, sections) = self.report_type.instructions();
+
+        DocumentInstructions {
+            schema_version: SCHEMA_VERSION.to_string(),
+            id: self.id.simple().to_string(),
+            client: self.client.clone(),
+            as_at: self.as_at.format("%Y-%m-%d").to_string(),
+            report_kind,
+            sections
+        }
+    }
 }
 
 
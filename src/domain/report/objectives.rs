@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::clock::Clock;
 use crate::domain::constrained_types::constrained_money_amount_medium::ConstrainedMoneyAmountMedium;
 use crate::domain::constrained_types::frequency::Frequency;
 use crate::domain::constrained_types::retirement_age::{InvalidAgeError, RetirementAge};
@@ -70,10 +71,10 @@ impl CoupleObjectivesAnnualReview {
 }
 
 
-impl TryFrom<CoupleObjectivesAnnualReviewDto> for CoupleObjectivesAnnualReview {
+impl TryFrom<(CoupleObjectivesAnnualReviewDto, &dyn Clock)> for CoupleObjectivesAnnualReview {
     type Error = String;
 
-    fn try_from(value: CoupleObjectivesAnnualReviewDto) -> Result<Self, Self::Error> {
+    fn try_from((value, clock): (CoupleObjectivesAnnualReviewDto, &dyn Clock)) -> Result<Self, Self::Error> {
         // Validate that the provided objectives meet the required conditions:
         // 1. The case is invalid if only one of `client_1_objectives` or `client_2_objectives`
         //    is present (exclusive OR), and `shared_objectives` is None.
@@ -92,17 +93,17 @@ impl TryFrom<CoupleObjectivesAnnualReviewDto> for CoupleObjectivesAnnualReview {
 
         Ok(Self {
             client_1_objectives: if let Some(obj) = value.client_1_objectives {
-                Some(ChangeInObjectives::try_from(obj)?)
+                Some(ChangeInObjectives::try_from((obj, clock))?)
             } else {
                 None
             },
             client_2_objectives: if let Some(obj) = value.client_2_objectives {
-                Some(ChangeInObjectives::try_from(obj)?)
+                Some(ChangeInObjectives::try_from((obj, clock))?)
             } else {
                 None
             },
             shared_objectives: if let Some(obj) = value.shared_objectives {
-                Some(ChangeInObjectives::try_from(obj)?)
+                Some(ChangeInObjectives::try_from((obj, clock))?)
             } else {
                 None
             },
@@ -117,16 +118,16 @@ pub enum ChangeInObjectives {
     ChangeInObjectives(Vec<ObjectiveType>)
 }
 
-impl TryFrom<ChangeInObjectivesDto> for ChangeInObjectives {
+impl TryFrom<(ChangeInObjectivesDto, &dyn Clock)> for ChangeInObjectives {
     type Error = String;
 
-    fn try_from(change_in_objectives_dto: ChangeInObjectivesDto) -> Result<Self, Self::Error> {
+    fn try_from((change_in_objectives_dto, clock): (ChangeInObjectivesDto, &dyn Clock)) -> Result<Self, Self::Error> {
         match change_in_objectives_dto {
             ChangeInObjectivesDto::ChangeInObjectives(unvalidated_objectives) => {
                 let mut validated_objectives = Vec::new();
                 for unvalidated_objective in unvalidated_objectives {
                     validated_objectives.push(
-                        ObjectiveType::try_from(unvalidated_objective)?
+                        ObjectiveType::try_from((unvalidated_objective, clock))?
                     );
                 }
                 Ok(ChangeInObjectives::ChangeInObjectives(validated_objectives))
@@ -135,7 +136,7 @@ impl TryFrom<ChangeInObjectivesDto> for ChangeInObjectives {
                 let mut validated_objectives = Vec::new();
                 for unvalidated_objective in unvalidated_objectives {
                     validated_objectives.push(
-                        ObjectiveType::try_from(unvalidated_objective)?
+                        ObjectiveType::try_from((unvalidated_objective, clock))?
                     );
                 }
                 Ok(ChangeInObjectives::NoChangeInObjectives(validated_objectives))
@@ -156,10 +157,10 @@ pub enum ObjectiveType {
     OtherObjective(OtherObjective)
 }
 
-impl TryFrom<ObjectiveTypeDto> for ObjectiveType {
+impl TryFrom<(ObjectiveTypeDto, &dyn Clock)> for ObjectiveType {
     type Error = String;
 
-    fn try_from(objective_type_dto: ObjectiveTypeDto) -> Result<Self, Self::Error> {
+    fn try_from((objective_type_dto, clock): (ObjectiveTypeDto, &dyn Clock)) -> Result<Self, Self::Error> {
 
         match objective_type_dto {
             ObjectiveTypeDto::CoupleIncomeObjective(income_objective_dto) => {
@@ -168,7 +169,7 @@ impl TryFrom<ObjectiveTypeDto> for ObjectiveType {
                     return Err("An income objective must include either a start year or age.".to_string());
                 }
 
-                Ok(Self::CoupleIncomeObjective(CoupleIncomeObjective::try_from(income_objective_dto)?))
+                Ok(Self::CoupleIncomeObjective(CoupleIncomeObjective::try_from((income_objective_dto, clock))?))
             }
             ObjectiveTypeDto::IncomeObjective(income_objective_dto) => {
 
@@ -176,7 +177,7 @@ impl TryFrom<ObjectiveTypeDto> for ObjectiveType {
                     return Err("An income objective must include either a start year or age.".to_string());
                 }
                 
-                Ok(Self::IncomeObjective(IncomeObjective::try_from(income_objective_dto)?))
+                Ok(Self::IncomeObjective(IncomeObjective::try_from((income_objective_dto, clock))?))
             }
             ObjectiveTypeDto::CapitalProtectionObjective(capital_protection_objective_dto) => {
                 Ok(Self::CapitalProtectionObjective(CapitalProtectionObjective::try_from(capital_protection_objective_dto)?))
@@ -218,6 +219,17 @@ impl ObjectiveType {
             ObjectiveType::OtherObjective(obj) => obj.id.to_string(),
         }
     }
+
+    pub fn linked_risk_profile(&self) -> &RiskProfile {
+        match self {
+            ObjectiveType::CoupleIncomeObjective(obj) => &obj.linked_risk_profile,
+            ObjectiveType::IncomeObjective(obj) => &obj.linked_risk_profile,
+            ObjectiveType::InRetirementIncomeObjective(obj) => &obj.linked_risk_profile,
+            ObjectiveType::CapitalProtectionObjective(obj) => &obj.linked_risk_profile,
+            ObjectiveType::IhtObjective(obj) => &obj.linked_risk_profile,
+            ObjectiveType::OtherObjective(obj) => &obj.linked_risk_profile,
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -231,15 +243,15 @@ pub struct CoupleIncomeObjective {
     pub linked_risk_profile: RiskProfile
 }
 
-impl TryFrom<CoupleIncomeObjectiveDto> for CoupleIncomeObjective {
+impl TryFrom<(CoupleIncomeObjectiveDto, &dyn Clock)> for CoupleIncomeObjective {
     type Error = String;
 
-    fn try_from(income_objective_dto: CoupleIncomeObjectiveDto) -> Result<Self, Self::Error> {
+    fn try_from((income_objective_dto, clock): (CoupleIncomeObjectiveDto, &dyn Clock)) -> Result<Self, Self::Error> {
         Ok(Self { 
             id: income_objective_dto.id,
             annual_income: ConstrainedMoneyAmountMedium::try_from(income_objective_dto.annual_income)?, 
             frequency: Frequency::try_from(income_objective_dto.frequency)?, 
-            from_year: income_objective_dto.from_year.map(|dto| dto.try_into().map_err(|err: InvalidYearError| err.to_string())).transpose()?,
+            from_year: income_objective_dto.from_year.map(|dto| RetirementYear::try_from((dto, clock)).map_err(|err: InvalidYearError| err.to_string())).transpose()?,
             from_age: income_objective_dto.from_age.map(|dto| dto.try_into()).transpose()?,
             linked_risk_profile: RiskProfile::try_from(income_objective_dto.linked_risk_profile)?
         })
@@ -276,15 +288,15 @@ pub struct IncomeObjective {
     pub linked_risk_profile: RiskProfile
 }
 
-impl TryFrom<IncomeObjectiveDto> for IncomeObjective {
+impl TryFrom<(IncomeObjectiveDto, &dyn Clock)> for IncomeObjective {
     type Error = String;
 
-    fn try_from(income_objective_dto: IncomeObjectiveDto) -> Result<Self, Self::Error> {
+    fn try_from((income_objective_dto, clock): (IncomeObjectiveDto, &dyn Clock)) -> Result<Self, Self::Error> {
         Ok(Self { 
             id: income_objective_dto.id,
             annual_income: ConstrainedMoneyAmountMedium::try_from(income_objective_dto.annual_income)?, 
             frequency: Frequency::try_from(income_objective_dto.frequency)?, 
-            from_year: income_objective_dto.from_year.map(|dto| dto.try_into().map_err(|err: InvalidYearError| err.to_string())).transpose()?,
+            from_year: income_objective_dto.from_year.map(|dto| RetirementYear::try_from((dto, clock)).map_err(|err: InvalidYearError| err.to_string())).transpose()?,
             from_age: income_objective_dto.from_age.map(|dto| dto.try_into().map_err(|err: InvalidAgeError| err.to_string())).transpose()?,
             linked_risk_profile: RiskProfile::try_from(income_objective_dto.linked_risk_profile)?
         })
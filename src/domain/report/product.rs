@@ -1,3 +1,4 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use std::convert::TryFrom;
@@ -28,7 +29,11 @@ use crate::domain::constrained_types::{
     sedol::Sedol,
 };
 use crate::domain::constrained_types::date::Date;
+use crate::domain::clock::Clock;
 use crate::domain::constrained_types::tax_year::TaxYear;
+use crate::domain::tax::TaxRules;
+use crate::domain::tax::capital_gains::{self, CapitalGainsCircumstances, CapitalGainsTaxCalculation};
+use crate::domain::tax::chargeable_gains::{self, BondHistory, BondSitus, ChargeableGainCalculation};
 use crate::driven::repository::InvestmentPortfoliosRepository;
 use super::investment_holdings::{FundHolding, InvestmentPortfolio, InvestmentStrategy};
 use super::risk_assessment::RiskProfile;
@@ -46,6 +51,7 @@ impl Products {
     pub async fn from_dto<R>(
         dto: ProductsDto,
         repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where 
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync,
@@ -53,7 +59,7 @@ impl Products {
         let mut out = Vec::with_capacity(dto.value().len());
         for item_dto in dto.value().iter().cloned() {
             // await each conversion in turn, propagating errors
-            let product = ExistingNewJointSingleProduct::from_dto(item_dto, repo).await?;
+            let product = ExistingNewJointSingleProduct::from_dto(item_dto, repo, clock).await?;
             out.push(product);
         }
         Ok(Products(out))
@@ -278,6 +284,67 @@ impl ExistingProduct {
         }
     }
 
+    pub fn current_value(&self) -> &Valuation {
+        match self {
+            ExistingProduct::JointlyOwned(product) => {
+                match &product.account_type {
+                    CanBeJointlyOwnedAccountType::GeneralInvestmentAccount(gia) => &gia.current_value,
+                    CanBeJointlyOwnedAccountType::OnshoreInvestmentBond(oib) => &oib.current_value,
+                    CanBeJointlyOwnedAccountType::OffshoreInvestmentBond(oib) => &oib.current_value,
+                }
+            }
+            ExistingProduct::SingleOwned(product) => {
+                match &product.account_type {
+                    AccountType::IsaStocksAndShares(iss) => &iss.current_value,
+                    AccountType::SelfInvestedPersonalPension(sipp) => &sipp.current_value,
+                    AccountType::PersonalPension(pp) => &pp.current_value,
+                    AccountType::JuniorIsaStocksAndShares(jisa) => &jisa.current_value,
+                    AccountType::CashIsa(ci) => &ci.current_value,
+                    AccountType::GeneralInvestmentAccount(gia) => &gia.current_value,
+                    AccountType::OnshoreInvestmentBond(oib) => &oib.current_value,
+                    AccountType::OffshoreInvestmentBond(oib) => &oib.current_value,
+                }
+            }
+        }
+    }
+
+    /// Returns the charges of an invested product, or `None` for a Cash ISA.
+    pub fn charges(&self) -> Option<&ProductCharges> {
+        match self {
+            ExistingProduct::JointlyOwned(product) => {
+                match &product.account_type {
+                    CanBeJointlyOwnedAccountType::GeneralInvestmentAccount(gia) => Some(&gia.charges),
+                    CanBeJointlyOwnedAccountType::OnshoreInvestmentBond(oib) => Some(&oib.charges),
+                    CanBeJointlyOwnedAccountType::OffshoreInvestmentBond(oib) => Some(&oib.charges),
+                }
+            }
+            ExistingProduct::SingleOwned(product) => {
+                match &product.account_type {
+                    AccountType::IsaStocksAndShares(iss) => Some(&iss.charges),
+                    AccountType::SelfInvestedPersonalPension(sipp) => Some(&sipp.charges),
+                    AccountType::PersonalPension(pp) => Some(&pp.charges),
+                    AccountType::JuniorIsaStocksAndShares(jisa) => Some(&jisa.charges),
+                    AccountType::CashIsa(_) => None,
+                    AccountType::GeneralInvestmentAccount(gia) => Some(&gia.charges),
+                    AccountType::OnshoreInvestmentBond(oib) => Some(&oib.charges),
+                    AccountType::OffshoreInvestmentBond(oib) => Some(&oib.charges),
+                }
+            }
+        }
+    }
+
+    /// Whether the product is a pension or investment the client can draw an income from. Cash
+    /// ISAs are not invested, and Junior ISAs belong to a child.
+    pub fn is_pension_or_investment(&self) -> bool {
+        match self {
+            ExistingProduct::JointlyOwned(_) => true,
+            ExistingProduct::SingleOwned(product) => !matches!(
+                product.account_type,
+                AccountType::CashIsa(_) | AccountType::JuniorIsaStocksAndShares(_)
+            )
+        }
+    }
+
 }
 
 
@@ -369,6 +436,20 @@ impl NewProduct {
         }
     }
 
+    /// Returns a reference to the account type.
+    pub fn account_type(&self) -> &AccountType {
+        match self {
+            NewProduct::SingleOwned(product) => product.account_type(),
+        }
+    }
+
+    /// Returns the actions recommended for the new product.
+    pub fn recommendation_actions(&self) -> &Vec<RecommendedAction> {
+        match self {
+            NewProduct::SingleOwned(product) => product.recommendation_actions(),
+        }
+    }
+
 }
 
 
@@ -388,6 +469,7 @@ impl ExistingNewJointSingleProduct {
     pub async fn from_dto<R>(
         dto: ExistingNewJointSingleProductDto,
         repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where 
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync,
@@ -395,15 +477,15 @@ impl ExistingNewJointSingleProduct {
         let product = match dto {
             ExistingNewJointSingleProductDto::ExistingJointlyOwnedProduct(inner_dto) => {
                 // call the async constructor, await, propagate any error with `?`
-                let inner = ExistingJointlyOwnedProduct::from_dto(inner_dto, repo).await?;
+                let inner = ExistingJointlyOwnedProduct::from_dto(inner_dto, repo, clock).await?;
                 Self::ExistingJointlyOwnedProduct(inner)
             }
             ExistingNewJointSingleProductDto::ExistingSingleOwnedProduct(inner_dto) => {
-                let inner = ExistingSingleOwnedProduct::from_dto(inner_dto, repo).await?;
+                let inner = ExistingSingleOwnedProduct::from_dto(inner_dto, repo, clock).await?;
                 Self::ExistingSingleOwnedProduct(inner)
             }
             ExistingNewJointSingleProductDto::NewSingleOwnedProduct(inner_dto) => {
-                let inner = NewSingleOwnedProduct::from_dto(inner_dto, repo).await?;
+                let inner = NewSingleOwnedProduct::from_dto(inner_dto, repo, clock).await?;
                 Self::NewSingleOwnedProduct(inner)
             }
         };
@@ -514,6 +596,7 @@ impl ExistingJointlyOwnedProduct {
     pub async fn from_dto<R>(
         dto: ExistingJointlyOwnedProductDto,
         repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where 
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync,
@@ -522,7 +605,7 @@ impl ExistingJointlyOwnedProduct {
             id: Uuid::parse_str(&dto.id).map_err(|err|format!("Failed to parse UUID: {}", err))?,
             platform_or_account_number: dto.platform_or_account_number.map(|dto|dto.try_into()).transpose()?,
             account_or_reference_number: dto.account_or_reference_number.try_into()?,
-            account_type: CanBeJointlyOwnedAccountType::from_dto(dto.account_type, repo).await?
+            account_type: CanBeJointlyOwnedAccountType::from_dto(dto.account_type, repo, clock).await?
         })
     }
 }
@@ -609,6 +692,7 @@ impl ExistingSingleOwnedProduct {
     pub async fn from_dto<R>(
         dto: ExistingSingleOwnedProductDto,
         repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where 
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync,
@@ -617,7 +701,7 @@ impl ExistingSingleOwnedProduct {
             id: Uuid::parse_str(&dto.id).map_err(|err|format!("Failed to parse UUID: {}", err))?,
             platform_or_account_number: dto.platform_or_account_number.map(|dto|dto.try_into()).transpose()?,
             account_or_reference_number: dto.account_or_reference_number.try_into()?,
-            account_type: AccountType::from_dto(dto.account_type, repo).await?
+            account_type: AccountType::from_dto(dto.account_type, repo, clock).await?
         })
     }
 }
@@ -698,6 +782,10 @@ impl ExistingSingleOwnedProduct {
             AccountType::OffshoreInvestmentBond(oib) => &oib.recommendations.product_retention,
         }
     }
+
+    pub fn account_type(&self) -> &AccountType {
+        &self.account_type
+    }
     
 }
 
@@ -716,6 +804,7 @@ impl NewSingleOwnedProduct {
     pub async fn from_dto<R>(
         dto: NewSingleOwnedProductDto,
         repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where 
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync,
@@ -724,13 +813,23 @@ impl NewSingleOwnedProduct {
             id: Uuid::parse_str(&dto.id).map_err(|err|format!("Failed to parse UUID: {}", err))?,
             platform_or_account_number: dto.platform_or_account_number.map(|dto| dto.try_into()).transpose()?,
             account_or_reference_number: dto.account_or_reference_number.map(|dto|dto.try_into()).transpose()?,
-            account_type: AccountType::from_dto(dto.account_type, repo).await?,
-            recommendations: NewProductRecommendations::from_dto(dto.recommendations, repo).await?
+            account_type: AccountType::from_dto(dto.account_type, repo, clock).await?,
+            recommendations: NewProductRecommendations::from_dto(dto.recommendations, repo, clock).await?
         })
     }
 }
 
 impl NewSingleOwnedProduct {
+    /// Returns a reference to the account type.
+    pub fn account_type(&self) -> &AccountType {
+        &self.account_type
+    }
+
+    /// Returns the actions recommended for the new product.
+    pub fn recommendation_actions(&self) -> &Vec<RecommendedAction> {
+        &self.recommendations.recommendation_actions
+    }
+
     /// Returns the provider as a string.
     pub fn provider_as_string(&self) -> String {
         match &self.account_type {
@@ -805,6 +904,7 @@ impl CanBeJointlyOwnedAccountType {
     pub async fn from_dto<R>(
         dto: CanBeJointlyOwnedAccountTypeDto,
         repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where 
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync,
@@ -812,15 +912,15 @@ impl CanBeJointlyOwnedAccountType {
         Ok(
             match dto {
                 CanBeJointlyOwnedAccountTypeDto::GeneralInvestmentAccount(inner_dto) => {
-                    let inner = GeneralInvestmentAccount::from_dto(inner_dto, repo).await?;
+                    let inner = GeneralInvestmentAccount::from_dto(inner_dto, repo, clock).await?;
                     CanBeJointlyOwnedAccountType::GeneralInvestmentAccount(inner)
                 } 
                 CanBeJointlyOwnedAccountTypeDto::OffshoreInvestmentBond(inner_dto) => {
-                    let inner = OffshoreInvestmentBond::from_dto(inner_dto, repo).await?;
+                    let inner = OffshoreInvestmentBond::from_dto(inner_dto, repo, clock).await?;
                     CanBeJointlyOwnedAccountType::OffshoreInvestmentBond(inner)
                 } 
                 CanBeJointlyOwnedAccountTypeDto::OnshoreInvestmentBond(inner_dto) => {
-                    let inner = OnshoreInvestmentBond::from_dto(inner_dto, repo).await?;
+                    let inner = OnshoreInvestmentBond::from_dto(inner_dto, repo, clock).await?;
                     CanBeJointlyOwnedAccountType::OnshoreInvestmentBond(inner)
                 } 
             }
@@ -892,6 +992,7 @@ impl AccountType {
     pub async fn from_dto<R>(
         dto: AccountTypeDto,
         repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where 
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync,
@@ -899,35 +1000,35 @@ impl AccountType {
         Ok(
             match dto {
                 AccountTypeDto::IsaStocksAndShares(inner_dto) => {
-                    let inner = IsaStocksAndShares::from_dto(inner_dto, repo).await?;
+                    let inner = IsaStocksAndShares::from_dto(inner_dto, repo, clock).await?;
                     AccountType::IsaStocksAndShares(inner)
                 } 
                 AccountTypeDto::SelfInvestedPersonalPension(inner_dto) => {
-                    let inner = SelfInvestedPersonalPension::from_dto(inner_dto, repo).await?;
+                    let inner = SelfInvestedPersonalPension::from_dto(inner_dto, repo, clock).await?;
                     AccountType::SelfInvestedPersonalPension(inner)
                 } 
                 AccountTypeDto::PersonalPension(inner_dto) => {
-                    let inner = PersonalPension::from_dto(inner_dto, repo).await?;
+                    let inner = PersonalPension::from_dto(inner_dto, repo, clock).await?;
                     AccountType::PersonalPension(inner)
                 } 
                 AccountTypeDto::JuniorIsaStocksAndShares(inner_dto) => {
-                    let inner = JuniorIsaStocksAndShares::from_dto(inner_dto, repo).await?;
+                    let inner = JuniorIsaStocksAndShares::from_dto(inner_dto, repo, clock).await?;
                     AccountType::JuniorIsaStocksAndShares(inner)
                 } 
                 AccountTypeDto::CashIsa(inner_dto) => {
-                    let inner = CashIsa::from_dto(inner_dto, repo).await?;
+                    let inner = CashIsa::from_dto(inner_dto, repo, clock).await?;
                     AccountType::CashIsa(inner)
                 } 
                 AccountTypeDto::GeneralInvestmentAccount(inner_dto) => {
-                    let inner = GeneralInvestmentAccount::from_dto(inner_dto, repo).await?;
+                    let inner = GeneralInvestmentAccount::from_dto(inner_dto, repo, clock).await?;
                     AccountType::GeneralInvestmentAccount(inner)
                 } 
                 AccountTypeDto::OffshoreInvestmentBond(inner_dto) => {
-                    let inner = OffshoreInvestmentBond::from_dto(inner_dto, repo).await?;
+                    let inner = OffshoreInvestmentBond::from_dto(inner_dto, repo, clock).await?;
                     AccountType::OffshoreInvestmentBond(inner)
                 } 
                 AccountTypeDto::OnshoreInvestmentBond(inner_dto) => {
-                    let inner = OnshoreInvestmentBond::from_dto(inner_dto, repo).await?;
+                    let inner = OnshoreInvestmentBond::from_dto(inner_dto, repo, clock).await?;
                     AccountType::OnshoreInvestmentBond(inner)
                 } 
             }
@@ -1050,7 +1151,8 @@ impl IsaStocksAndShares {
 
     pub async fn from_dto<R>(
         dto: IsaStocksAndSharesDto,
-        repo: &R
+        repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync
@@ -1058,7 +1160,7 @@ impl IsaStocksAndShares {
         let investment_strategy = InvestmentStrategy::from_dto(dto.current_investment_strategy.clone(), repo)
             .await?;
 
-        let existing_product_recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo)
+        let existing_product_recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo, clock)
             .await?;
 
         let sipp  = (
@@ -1086,19 +1188,22 @@ pub struct GeneralInvestmentAccount {
     linked_cash_or_fee_payment_wrapper: AccountOrReferenceNumberType,
     charges: ProductCharges,
     current_tax_position: CapitalGainsPosition,
+    capital_gains_tax_basis: Option<CapitalGainsTaxBasis>,
     recommendations: ExistingProductRecommendations,
 }
 
-impl TryFrom<(GeneralInvestmentAccountDto, InvestmentStrategy, ExistingProductRecommendations)> for GeneralInvestmentAccount {
+impl TryFrom<(GeneralInvestmentAccountDto, InvestmentStrategy, ExistingProductRecommendations, &dyn Clock)> for GeneralInvestmentAccount {
     type Error = String;
 
     fn try_from((
         dto, 
         current_investment_strategy,
-        existing_product_recommendations): (
+        existing_product_recommendations,
+        clock): (
             GeneralInvestmentAccountDto, 
             InvestmentStrategy, 
-            ExistingProductRecommendations
+            ExistingProductRecommendations,
+            &dyn Clock
         )) -> Result<Self, Self::Error> {
         Ok(Self {
             ownership: dto.ownership.try_into()?,
@@ -1109,16 +1214,27 @@ impl TryFrom<(GeneralInvestmentAccountDto, InvestmentStrategy, ExistingProductRe
             linked_cash_or_fee_payment_wrapper: dto.linked_cash_or_fee_payment_wrapper.try_into()?,
             charges: dto.charges.try_into()?,
             current_tax_position: dto.current_tax_position.try_into()?,
+            capital_gains_tax_basis: dto.capital_gains_tax_basis.map(|dto| (dto, clock).try_into()).transpose()?,
             recommendations: existing_product_recommendations
         })
     }
 }
 
 impl GeneralInvestmentAccount {
+    /// Returns a reference to the current valuation.
+    pub fn current_value(&self) -> &Valuation {
+        &self.current_value
+    }
+
+    /// Returns the figures needed to calculate Capital Gains Tax, when the adviser supplied them.
+    pub fn capital_gains_tax_basis(&self) -> Option<&CapitalGainsTaxBasis> {
+        self.capital_gains_tax_basis.as_ref()
+    }
 
     pub async fn from_dto<R>(
         dto: GeneralInvestmentAccountDto,
-        repo: &R
+        repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync
@@ -1126,13 +1242,14 @@ impl GeneralInvestmentAccount {
         let investment_strategy = InvestmentStrategy::from_dto(dto.current_investment_strategy.clone(), repo)
             .await?;
 
-        let existing_product_recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo)
+        let existing_product_recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo, clock)
             .await?;
 
         let gia  = (
             dto,
             investment_strategy,
-            existing_product_recommendations
+            existing_product_recommendations,
+            clock
         )
             .try_into()
             .map_err(|e| e)?;
@@ -1154,19 +1271,22 @@ pub struct OnshoreInvestmentBond {
     linked_cash_or_fee_payment_wrapper: AccountOrReferenceNumberType,
     charges: ProductCharges,
     current_tax_position: ChargeableGainsPosition,
+    chargeable_gains_basis: Option<ChargeableGainsBasis>,
     recommendations: ExistingProductRecommendations,
 }
 
-impl TryFrom<(OnshoreInvestmentBondDto, InvestmentStrategy, ExistingProductRecommendations)> for OnshoreInvestmentBond {
+impl TryFrom<(OnshoreInvestmentBondDto, InvestmentStrategy, ExistingProductRecommendations, &dyn Clock)> for OnshoreInvestmentBond {
     type Error = String;
 
     fn try_from((
         dto, 
         current_investment_strategy,
-        existing_product_recommendations): (
+        existing_product_recommendations,
+        clock): (
             OnshoreInvestmentBondDto, 
             InvestmentStrategy, 
-            ExistingProductRecommendations
+            ExistingProductRecommendations,
+            &dyn Clock
         )) -> Result<Self, Self::Error> {
         Ok(Self {
             ownership: dto.ownership.try_into()?,
@@ -1177,16 +1297,27 @@ impl TryFrom<(OnshoreInvestmentBondDto, InvestmentStrategy, ExistingProductRecom
             linked_cash_or_fee_payment_wrapper: dto.linked_cash_or_fee_payment_wrapper.try_into()?,
             charges: dto.charges.try_into()?,
             current_tax_position: dto.current_tax_position.try_into()?,
+            chargeable_gains_basis: dto.chargeable_gains_basis.map(|dto| (dto, clock).try_into()).transpose()?,
             recommendations: existing_product_recommendations
         })
     }
 }
 
 impl OnshoreInvestmentBond {
+    /// Returns a reference to the current valuation.
+    pub fn current_value(&self) -> &Valuation {
+        &self.current_value
+    }
+
+    /// Returns the bond's history for calculating chargeable gains, when the adviser supplied it.
+    pub fn chargeable_gains_basis(&self) -> Option<&ChargeableGainsBasis> {
+        self.chargeable_gains_basis.as_ref()
+    }
 
     pub async fn from_dto<R>(
         dto: OnshoreInvestmentBondDto,
-        repo: &R
+        repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync
@@ -1194,13 +1325,14 @@ impl OnshoreInvestmentBond {
         let investment_strategy = InvestmentStrategy::from_dto(dto.current_investment_strategy.clone(), repo)
             .await?;
 
-        let existing_product_recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo)
+        let existing_product_recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo, clock)
             .await?;
 
         let onb  = (
             dto,
             investment_strategy,
-            existing_product_recommendations
+            existing_product_recommendations,
+            clock
         )
             .try_into()
             .map_err(|e| e)?;
@@ -1222,19 +1354,22 @@ pub struct OffshoreInvestmentBond {
     linked_cash_or_fee_payment_wrapper: AccountOrReferenceNumberType,
     charges: ProductCharges,
     current_tax_position: ChargeableGainsPosition,
+    chargeable_gains_basis: Option<ChargeableGainsBasis>,
     recommendations: ExistingProductRecommendations,
 }
 
-impl TryFrom<(OffshoreInvestmentBondDto, InvestmentStrategy, ExistingProductRecommendations)> for OffshoreInvestmentBond {
+impl TryFrom<(OffshoreInvestmentBondDto, InvestmentStrategy, ExistingProductRecommendations, &dyn Clock)> for OffshoreInvestmentBond {
     type Error = String;
 
     fn try_from((
         dto, 
         current_investment_strategy,
-        existing_product_recommendations): (
+        existing_product_recommendations,
+        clock): (
             OffshoreInvestmentBondDto, 
             InvestmentStrategy, 
-            ExistingProductRecommendations
+            ExistingProductRecommendations,
+            &dyn Clock
         )) -> Result<Self, Self::Error> {
         Ok(Self {
             ownership: dto.ownership.try_into()?,
@@ -1245,16 +1380,27 @@ impl TryFrom<(OffshoreInvestmentBondDto, InvestmentStrategy, ExistingProductReco
             linked_cash_or_fee_payment_wrapper: dto.linked_cash_or_fee_payment_wrapper.try_into()?,
             charges: dto.charges.try_into()?,
             current_tax_position: dto.current_tax_position.try_into()?,
+            chargeable_gains_basis: dto.chargeable_gains_basis.map(|dto| (dto, clock).try_into()).transpose()?,
             recommendations: existing_product_recommendations
         })
     }
 }
 
 impl OffshoreInvestmentBond {
+    /// Returns a reference to the current valuation.
+    pub fn current_value(&self) -> &Valuation {
+        &self.current_value
+    }
+
+    /// Returns the bond's history for calculating chargeable gains, when the adviser supplied it.
+    pub fn chargeable_gains_basis(&self) -> Option<&ChargeableGainsBasis> {
+        self.chargeable_gains_basis.as_ref()
+    }
 
     pub async fn from_dto<R>(
         dto: OffshoreInvestmentBondDto,
-        repo: &R
+        repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync
@@ -1262,13 +1408,14 @@ impl OffshoreInvestmentBond {
         let investment_strategy = InvestmentStrategy::from_dto(dto.current_investment_strategy.clone(), repo)
             .await?;
 
-        let existing_product_recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo)
+        let existing_product_recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo, clock)
             .await?;
 
         let offb  = (
             dto,
             investment_strategy,
-            existing_product_recommendations
+            existing_product_recommendations,
+            clock
         )
             .try_into()
             .map_err(|e| e)?;
@@ -1318,7 +1465,8 @@ impl SelfInvestedPersonalPension {
 
     pub async fn from_dto<R>(
         dto: SelfInvestedPersonalPensionDto,
-        repo: &R
+        repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync
@@ -1326,7 +1474,7 @@ impl SelfInvestedPersonalPension {
         let investment_strategy = InvestmentStrategy::from_dto(dto.current_investment_strategy.clone(), repo)
             .await?;
 
-        let existing_product_recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo)
+        let existing_product_recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo, clock)
             .await?;
 
         let sipp  = (
@@ -1382,7 +1530,8 @@ impl PersonalPension {
 
     pub async fn from_dto<R>(
         dto: PersonalPensionDto,
-        repo: &R
+        repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync
@@ -1390,7 +1539,7 @@ impl PersonalPension {
         let investment_strategy = InvestmentStrategy::from_dto(dto.current_investment_strategy.clone(), repo)
             .await?;
 
-        let existing_product_recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo)
+        let existing_product_recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo, clock)
             .await?;
 
         let pp  = (
@@ -1411,6 +1560,8 @@ impl PersonalPension {
 #[serde(rename_all = "camelCase")]
 pub struct JuniorIsaStocksAndShares {
     provider: Provider,
+    /// The child the account is held for, whose Junior ISAs share one allowance
+    child: NameString,
     optional_description: Option<ConstrainedString200>,
     current_investment_strategy: InvestmentStrategy,
     current_value: Valuation,
@@ -1432,6 +1583,7 @@ impl TryFrom<(JuniorIsaStocksAndSharesDto, InvestmentStrategy, ExistingProductRe
         )) -> Result<Self, Self::Error> {
         Ok(Self {
             provider: dto.provider.try_into()?,
+            child: dto.child.try_into()?,
             optional_description: dto.optional_description.map(|dto| dto.try_into()).transpose()?,
             current_investment_strategy: current_investment_strategy,
             current_value: dto.current_value.try_into()?,
@@ -1443,10 +1595,14 @@ impl TryFrom<(JuniorIsaStocksAndSharesDto, InvestmentStrategy, ExistingProductRe
 }
 
 impl JuniorIsaStocksAndShares {
+    pub fn child(&self) -> &NameString {
+        &self.child
+    }
 
     pub async fn from_dto<R>(
         dto: JuniorIsaStocksAndSharesDto,
-        repo: &R
+        repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync
@@ -1454,7 +1610,7 @@ impl JuniorIsaStocksAndShares {
         let investment_strategy = InvestmentStrategy::from_dto(dto.current_investment_strategy.clone(), repo)
             .await?;
 
-        let existing_product_recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo)
+        let existing_product_recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo, clock)
             .await?;
 
         let jisa  = (
@@ -1501,12 +1657,13 @@ impl CashIsa {
 
     pub async fn from_dto<R>(
         dto: CashIsaDto,
-        repo: &R
+        repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync
     {
-        let recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo)
+        let recommendations = ExistingProductRecommendations::from_dto(dto.recommendations.clone(), repo, clock)
             .await?;
 
         let jisa  = (
@@ -1622,6 +1779,13 @@ pub struct Valuation {
     date_of_valuation: Date
 }
 
+impl Valuation {
+    /// Returns a reference to the value.
+    pub fn value(&self) -> &ConstrainedMoneyAmountLarge {
+        &self.value
+    }
+}
+
 impl TryFrom<ValuationDto> for Valuation {
     type Error = String;
 
@@ -1642,6 +1806,14 @@ pub struct ProductCharges {
     other_charges: Option<OtherCharge>
 }
 
+impl ProductCharges {
+    /// The advice, platform and fund charges a year, as a fraction of the product's value.
+    pub fn annual_rate(&self) -> f64 {
+        let fund_charge = self.ongoing_fund_charge.as_ref().map(Percentage::as_fraction).unwrap_or(0.0);
+        f64::from(self.ongoing_advice_charge.as_fraction() + self.platform_charge.as_fraction() + fund_charge)
+    }
+}
+
 impl TryFrom<ProductChargesDto> for ProductCharges {
     type Error = String;
 
@@ -1682,12 +1854,13 @@ impl ExistingProductRecommendations {
     pub async fn from_dto<R>(
         dto: ExistingProductRecommendationsDto,
         repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where 
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync,
     {
         Ok(Self {
-            product_retention: ProductRetention::from_dto(dto.product_retention, repo).await?,
+            product_retention: ProductRetention::from_dto(dto.product_retention, repo, clock).await?,
         })
     }
 
@@ -1705,16 +1878,16 @@ pub struct NewProductRecommendations {
     recommendation_actions: Vec<RecommendedAction>
 }
 
-impl TryFrom<(NewProductRecommendationsDto, InvestmentStrategy)> for NewProductRecommendations {
+impl TryFrom<(NewProductRecommendationsDto, InvestmentStrategy, &dyn Clock)> for NewProductRecommendations {
     type Error = String;
 
-    fn try_from((dto, investment_strategy): (NewProductRecommendationsDto, InvestmentStrategy)) -> Result<Self, Self::Error> {
+    fn try_from((dto, investment_strategy, clock): (NewProductRecommendationsDto, InvestmentStrategy, &dyn Clock)) -> Result<Self, Self::Error> {
         Ok(Self {
             rationale: dto.rationale.try_into()?,
             recommended_product_charges: dto.recommended_product_charges.try_into()?,
             recommended_investment_strategy: investment_strategy,
             linked_objectives: dto.linked_objectives,
-            recommendation_actions: dto.recommendation_actions.iter().map(|dto| dto.clone().try_into()).collect::<Result<_, _>>()?
+            recommendation_actions: dto.recommendation_actions.iter().map(|dto| (dto.clone(), clock).try_into()).collect::<Result<_, _>>()?
         })
     }
 }
@@ -1723,7 +1896,8 @@ impl NewProductRecommendations {
 
     pub async fn from_dto<R>(
         dto: NewProductRecommendationsDto,
-        repo: &R
+        repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where
         R: InvestmentPortfoliosRepository<InvestmentPortfolio>
@@ -1733,7 +1907,8 @@ impl NewProductRecommendations {
 
         let iss  = (
             dto,
-            investment_strategy
+            investment_strategy,
+            clock
         )
             .try_into()
             .map_err(|e| e)?;
@@ -1833,6 +2008,136 @@ impl TryFrom<CapitalGainsPositionDto> for CapitalGainsPosition {
     }
 }
 
+/// The account's base cost and its owner's position for the tax year, from which the Capital
+/// Gains Tax on a disposal is calculated.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CapitalGainsTaxBasis {
+    base_cost: ConstrainedMoneyAmountLarge,
+    tax_year: TaxYear,
+    taxable_income: ConstrainedMoneyAmountLarge,
+    other_gains: ConstrainedMoneyAmountLarge,
+    other_losses: ConstrainedMoneyAmountLarge
+}
+
+impl CapitalGainsTaxBasis {
+    /// Returns a reference to the base cost of the whole account.
+    pub fn base_cost(&self) -> &ConstrainedMoneyAmountLarge {
+        &self.base_cost
+    }
+
+    /// Returns the tax year disposals are expected in, unless an action names its own.
+    pub fn tax_year(&self) -> &TaxYear {
+        &self.tax_year
+    }
+
+    /// Calculates the tax on taking `proceeds` from the account, currently worth `value`, in `tax_year`.
+    pub fn calculate(&self, value: f64, proceeds: f64, tax_year: &TaxYear, tax_rules: &TaxRules) -> Result<CapitalGainsTaxCalculation, String> {
+        let circumstances = CapitalGainsCircumstances {
+            taxable_income: self.taxable_income.value(),
+            other_gains: self.other_gains.value(),
+            other_losses: self.other_losses.value()
+        };
+
+        capital_gains::calculate(
+            self.base_cost.value(),
+            value,
+            proceeds,
+            &circumstances,
+            &tax_rules.for_tax_year(tax_year)?.capital_gains
+        )
+    }
+}
+
+impl TryFrom<(CapitalGainsTaxBasisDto, &dyn Clock)> for CapitalGainsTaxBasis {
+    type Error = String;
+
+    fn try_from((dto, clock): (CapitalGainsTaxBasisDto, &dyn Clock)) -> Result<Self, Self::Error> {
+        Ok(Self {
+            base_cost: dto.base_cost.try_into()?,
+            tax_year: TaxYear::try_from((dto.tax_year, clock))?,
+            taxable_income: dto.taxable_income.try_into()?,
+            other_gains: dto.other_gains.try_into()?,
+            other_losses: dto.other_losses.try_into()?
+        })
+    }
+}
+
+/// The bond's premiums and withdrawal history and its owner's income for the tax year, from
+/// which the chargeable gain and income tax on a withdrawal or surrender are calculated.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChargeableGainsBasis {
+    premiums_paid: ConstrainedMoneyAmountLarge,
+    policy_years: u32,
+    previous_withdrawals: ConstrainedMoneyAmountLarge,
+    previous_chargeable_gains: ConstrainedMoneyAmountLarge,
+    last_excess_event_policy_year: Option<u32>,
+    tax_year: TaxYear,
+    taxable_income: ConstrainedMoneyAmountLarge
+}
+
+impl ChargeableGainsBasis {
+    /// Returns the tax year withdrawals are expected in, unless an action names its own.
+    pub fn tax_year(&self) -> &TaxYear {
+        &self.tax_year
+    }
+
+    fn history(&self) -> BondHistory {
+        BondHistory {
+            premiums_paid: self.premiums_paid.value(),
+            policy_years: self.policy_years,
+            previous_withdrawals: self.previous_withdrawals.value(),
+            previous_chargeable_gains: self.previous_chargeable_gains.value(),
+            last_excess_event_policy_year: self.last_excess_event_policy_year
+        }
+    }
+
+    /// Calculates the gain and tax on withdrawing `withdrawal` from the bond in `tax_year`.
+    pub fn partial_surrender(&self, withdrawal: f64, situs: BondSitus, tax_year: &TaxYear, tax_rules: &TaxRules) -> Result<ChargeableGainCalculation, String> {
+        Ok(chargeable_gains::partial_surrender(
+            &self.history(),
+            withdrawal,
+            situs,
+            self.taxable_income.value(),
+            &tax_rules.for_tax_year(tax_year)?.income_tax
+        ))
+    }
+
+    /// Calculates the gain and tax on surrendering the whole bond, currently worth `value`, in `tax_year`.
+    pub fn full_surrender(&self, value: f64, situs: BondSitus, tax_year: &TaxYear, tax_rules: &TaxRules) -> Result<ChargeableGainCalculation, String> {
+        Ok(chargeable_gains::full_surrender(
+            &self.history(),
+            value,
+            situs,
+            self.taxable_income.value(),
+            &tax_rules.for_tax_year(tax_year)?.income_tax
+        ))
+    }
+}
+
+impl TryFrom<(ChargeableGainsBasisDto, &dyn Clock)> for ChargeableGainsBasis {
+    type Error = String;
+
+    fn try_from((dto, clock): (ChargeableGainsBasisDto, &dyn Clock)) -> Result<Self, Self::Error> {
+        if let Some(year) = dto.last_excess_event_policy_year {
+            if year == 0 || year > dto.policy_years {
+                return Err(format!("The last excess event policy year {} must be between 1 and the {} policy years begun", year, dto.policy_years));
+            }
+        }
+
+        Ok(Self {
+            premiums_paid: dto.premiums_paid.try_into()?,
+            policy_years: dto.policy_years,
+            previous_withdrawals: dto.previous_withdrawals.try_into()?,
+            previous_chargeable_gains: dto.previous_chargeable_gains.try_into()?,
+            last_excess_event_policy_year: dto.last_excess_event_policy_year,
+            tax_year: TaxYear::try_from((dto.tax_year, clock))?,
+            taxable_income: dto.taxable_income.try_into()?
+        })
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
@@ -1891,6 +2196,7 @@ impl ProductRetention {
     pub async fn from_dto<R>(
         dto: ProductRetentionDto,
         repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where 
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync,
@@ -1898,7 +2204,7 @@ impl ProductRetention {
         Ok(
             match dto {
                 ProductRetentionDto::Retain(inner_dto) => {
-                    let inner = Retain::from_dto(inner_dto, repo).await?;
+                    let inner = Retain::from_dto(inner_dto, repo, clock).await?;
                     ProductRetention::Retain(inner)
                 } 
                 ProductRetentionDto::Replace(inner_dto) => Self::Replace(inner_dto.try_into()?),
@@ -1922,17 +2228,17 @@ pub enum RecommendedAction {
     StopWithdrawal(StopWithdrawal)
 }
 
-impl TryFrom<RecommendedActionDto> for RecommendedAction {
+impl TryFrom<(RecommendedActionDto, &dyn Clock)> for RecommendedAction {
     type Error = String;
 
-    fn try_from(dto: RecommendedActionDto) -> Result<Self, Self::Error> {
+    fn try_from((dto, clock): (RecommendedActionDto, &dyn Clock)) -> Result<Self, Self::Error> {
         match dto {
-            RecommendedActionDto::SingleWithdrawal(single_withdrawal_dto) => Ok(Self::SingleWithdrawal(single_withdrawal_dto.try_into()?)),
-            RecommendedActionDto::SingleContribution(single_contribution_dto) => Ok(Self::SingleContribution(single_contribution_dto.try_into()?)),
-            RecommendedActionDto::RegularContribution(regular_contribution_dto) => Ok(Self::RegularContribution(regular_contribution_dto.try_into()?)),
-            RecommendedActionDto::RegularWithdrawal(regular_withdrawal_dto) => Ok(Self::RegularWithdrawal(regular_withdrawal_dto.try_into()?)),
-            RecommendedActionDto::StopWithdrawal(stop_withdrawal_dto) => Ok(Self::StopWithdrawal(stop_withdrawal_dto.try_into()?)),
-            RecommendedActionDto::Transfer(transfer_dto) => Ok(Self::Transfer(transfer_dto.try_into()?))
+            RecommendedActionDto::SingleWithdrawal(single_withdrawal_dto) => Ok(Self::SingleWithdrawal((single_withdrawal_dto, clock).try_into()?)),
+            RecommendedActionDto::SingleContribution(single_contribution_dto) => Ok(Self::SingleContribution((single_contribution_dto, clock).try_into()?)),
+            RecommendedActionDto::RegularContribution(regular_contribution_dto) => Ok(Self::RegularContribution((regular_contribution_dto, clock).try_into()?)),
+            RecommendedActionDto::RegularWithdrawal(regular_withdrawal_dto) => Ok(Self::RegularWithdrawal((regular_withdrawal_dto, clock).try_into()?)),
+            RecommendedActionDto::StopWithdrawal(stop_withdrawal_dto) => Ok(Self::StopWithdrawal((stop_withdrawal_dto, clock).try_into()?)),
+            RecommendedActionDto::Transfer(transfer_dto) => Ok(Self::Transfer((transfer_dto, clock).try_into()?))
         }
     }
 }
@@ -2144,6 +2450,7 @@ impl Retain {
     pub async fn from_dto<R>(
         dto: RetainDto,
         repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where 
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync,
@@ -2163,7 +2470,7 @@ impl Retain {
                 .map(|actions| {
                     actions
                         .into_iter()
-                        .map(|action_dto| action_dto.try_into())
+                        .map(|action_dto| (action_dto, clock).try_into())
                         .collect::<Result<Vec<_>, _>>()
                 })
                 .transpose()?,
@@ -2177,6 +2484,18 @@ impl Retain {
         &self.recommendation_actions
     }
 
+    pub fn recommended_product_charges(&self) -> &ProductCharges {
+        &self.recommended_product_charges
+    }
+
+    /// The investment strategy the product is realigned or rebalanced to.
+    pub fn recommended_investment_strategy(&self) -> &InvestmentStrategy {
+        match &self.recommended_investment_strategy {
+            RealignOrRebalance::Realign(realign) => &realign.recommended_investment_strategy,
+            RealignOrRebalance::Rebalance(rebalance) => &rebalance.recommended_investment_strategy
+        }
+    }
+
     /// Returns a HashMap grouping the recommended actions by their type.
     ///
     /// The key is a string (derived from the `description()` method of `RecommendedAction`),
@@ -2614,19 +2933,44 @@ pub struct SingleWithdrawal {
     executive_summary_description: ConstrainedString200,
     rationale: ConstrainedString1000,
     date_of_action: Option<Date>,
-    tax_year_of_action: Option<Date>
+    tax_year_of_action: Option<TaxYear>,
+    #[serde(default)]
+    gifted: bool
+}
+
+impl SingleWithdrawal {
+    /// Returns a reference to the withdrawal value.
+    pub fn value(&self) -> &ConstrainedMoneyAmountLarge {
+        &self.value
+    }
+
+    /// Returns an optional reference to the date of the withdrawal.
+    pub fn date_of_action(&self) -> Option<&Date> {
+        self.date_of_action.as_ref()
+    }
+
+    /// Whether the withdrawal is given away.
+    pub fn gifted(&self) -> bool {
+        self.gifted
+    }
+
+    /// Returns an optional reference to the tax year of action.
+    pub fn tax_year_of_action(&self) -> Option<&TaxYear> {
+        self.tax_year_of_action.as_ref()
+    }
 }
 
-impl TryFrom<SingleWithdrawalDto> for SingleWithdrawal {
+impl TryFrom<(SingleWithdrawalDto, &dyn Clock)> for SingleWithdrawal {
     type Error = String;
 
-    fn try_from(dto: SingleWithdrawalDto) -> Result<Self, Self::Error> {
+    fn try_from((dto, clock): (SingleWithdrawalDto, &dyn Clock)) -> Result<Self, Self::Error> {
         Ok(Self {
             value: dto.value.try_into()?,
             executive_summary_description: dto.executive_summary_description.try_into()?,
             rationale: dto.rationale.try_into()?,
             date_of_action: if dto.date_of_action.is_some() { Some(dto.date_of_action.unwrap().try_into()?) } else { None },
-            tax_year_of_action: if dto.tax_year_of_action.is_some() { Some(dto.tax_year_of_action.unwrap().try_into()?) } else { None }
+            tax_year_of_action: if dto.tax_year_of_action.is_some() { Some(TaxYear::try_from((dto.tax_year_of_action.unwrap(), clock))?) } else { None },
+            gifted: dto.gifted
         })
     }
 }
@@ -2641,16 +2985,16 @@ pub struct SingleContribution {
     tax_year_of_action: Option<TaxYear>
 }
 
-impl TryFrom<SingleContributionDto> for SingleContribution {
+impl TryFrom<(SingleContributionDto, &dyn Clock)> for SingleContribution {
     type Error = String;
 
-    fn try_from(dto: SingleContributionDto) -> Result<Self, Self::Error> {
+    fn try_from((dto, clock): (SingleContributionDto, &dyn Clock)) -> Result<Self, Self::Error> {
         Ok(Self {
             value: dto.value.try_into()?,
             executive_summary_description: dto.executive_summary_description.try_into()?,
             rationale: dto.rationale.map(|dto|dto.try_into()).transpose()?,
             date_of_action: if dto.date_of_action.is_some() { Some(dto.date_of_action.unwrap().try_into()?) } else { None },
-            tax_year_of_action: if dto.tax_year_of_action.is_some() { Some(dto.tax_year_of_action.unwrap().try_into()?) } else { None }
+            tax_year_of_action: if dto.tax_year_of_action.is_some() { Some(TaxYear::try_from((dto.tax_year_of_action.unwrap(), clock))?) } else { None }
         })
     }
 }
@@ -2695,10 +3039,61 @@ pub struct RegularContribution {
     end_date_of_action: Option<Date>,
 }
 
-impl TryFrom<RegularContributionDto> for RegularContribution {
+impl RegularContribution {
+    /// Returns a reference to the value of each contribution.
+    pub fn value(&self) -> &ConstrainedMoneyAmountLarge {
+        &self.value
+    }
+
+    /// Returns an optional reference to the tax year of action.
+    pub fn tax_year_of_action(&self) -> Option<&TaxYear> {
+        self.tax_year_of_action.as_ref()
+    }
+
+    /// Totals the contributions falling within `tax_year`, between the start date and any end date.
+    pub fn total_in_tax_year(&self, tax_year: &TaxYear) -> Result<f64, String> {
+        let first_day = tax_year.first_day()?;
+        let last_day = match &self.end_date_of_action {
+            Some(end_date) => tax_year.last_day()?.min(*end_date.value()),
+            None => tax_year.last_day()?
+        };
+
+        let occurrences = (0..)
+            .map_while(|n| self.frequency.nth_occurrence(*self.start_date_of_action.value(), n))
+            .take_while(|date| *date <= last_day)
+            .filter(|date| *date >= first_day)
+            .count();
+
+        Ok(self.value.value() * occurrences as f64)
+    }
+
+    /// Totals the contributions in each tax year they are paid in, from the start date to the end
+    /// date. Without an end date they are totalled up to the tax year after `current_tax_year`,
+    /// or after the first payment's if that is later.
+    pub fn totals_by_tax_year(&self, current_tax_year: &TaxYear) -> Result<Vec<(TaxYear, f64)>, String> {
+        let first = TaxYear::containing(*self.start_date_of_action.value());
+        let last = match &self.end_date_of_action {
+            Some(end_date) => TaxYear::containing(*end_date.value()),
+            None => first.clone().max(current_tax_year.clone()).next()?
+        };
+
+        let mut totals = Vec::new();
+        let mut tax_year = first;
+        while tax_year <= last {
+            let total = self.total_in_tax_year(&tax_year)?;
+            if total > 0.0 {
+                totals.push((tax_year.clone(), total));
+            }
+            tax_year = tax_year.next()?;
+        }
+        Ok(totals)
+    }
+}
+
+impl TryFrom<(RegularContributionDto, &dyn Clock)> for RegularContribution {
     type Error = String;
 
-    fn try_from(dto: RegularContributionDto) -> Result<Self, Self::Error> {
+    fn try_from((dto, clock): (RegularContributionDto, &dyn Clock)) -> Result<Self, Self::Error> {
         Ok(Self {
             value: dto.value.try_into()?,
             executive_summary_description: dto.executive_summary_description.try_into()?,
@@ -2706,7 +3101,7 @@ impl TryFrom<RegularContributionDto> for RegularContribution {
             start_date_of_action: dto.start_date_of_action.try_into()?,
             frequency: dto.frequency.try_into()?,
             end_date_of_action: if dto.end_date_of_action.is_some() { Some(dto.end_date_of_action.unwrap().try_into()?) } else { None },
-            tax_year_of_action: if dto.tax_year_of_action.is_some() { Some(dto.tax_year_of_action.unwrap().try_into()?) } else { None }
+            tax_year_of_action: if dto.tax_year_of_action.is_some() { Some(TaxYear::try_from((dto.tax_year_of_action.unwrap(), clock))?) } else { None }
         })
     }
 }
@@ -2721,12 +3116,60 @@ pub struct RegularWithdrawal {
     start_date_of_action: Date,
     tax_year_of_action: Option<TaxYear>,
     end_date_of_action: Option<Date>,
+    #[serde(default)]
+    gifted: bool
+}
+
+impl RegularWithdrawal {
+    /// Returns a reference to the value of each withdrawal.
+    pub fn value(&self) -> &ConstrainedMoneyAmountLarge {
+        &self.value
+    }
+
+    /// Returns a reference to how often the withdrawal is taken.
+    pub fn frequency(&self) -> &Frequency {
+        &self.frequency
+    }
+
+    /// Returns an optional reference to the tax year of action.
+    pub fn tax_year_of_action(&self) -> Option<&TaxYear> {
+        self.tax_year_of_action.as_ref()
+    }
+
+    /// Returns a reference to the date of the first withdrawal.
+    pub fn start_date_of_action(&self) -> &Date {
+        &self.start_date_of_action
+    }
+
+    /// Returns an optional reference to the date withdrawals stop, if they do.
+    pub fn end_date_of_action(&self) -> Option<&Date> {
+        self.end_date_of_action.as_ref()
+    }
+
+    /// Whether the withdrawals are given away.
+    pub fn gifted(&self) -> bool {
+        self.gifted
+    }
+
+    /// The dates of the withdrawals taken between `first_day` and `last_day` inclusive.
+    pub fn dates_between(&self, first_day: NaiveDate, last_day: NaiveDate) -> Vec<NaiveDate> {
+        let last_day = match &self.end_date_of_action {
+            Some(end_date) => last_day.min(*end_date.value()),
+            None => last_day
+        };
+
+        (0..)
+            .map_while(|n| self.frequency.nth_occurrence(*self.start_date_of_action.value(), n))
+            .take_while(|date| *date <= last_day)
+            .filter(|date| *date >= first_day)
+            .collect()
+    }
 }
 
-impl TryFrom<RegularWithdrawalDto> for RegularWithdrawal {
+impl TryFrom<(RegularWithdrawalDto, &dyn Clock)> for RegularWithdrawal {
     type Error = String;
 
-    fn try_from(dto: RegularWithdrawalDto) -> Result<Self, Self::Error> {
+    fn try_from((dto, clock): (RegularWithdrawalDto, &dyn Clock)) -> Result<Self, Self::Error> {
         Ok(Self {
             value: dto.value.try_into()?,
             executive_summary_description: dto.executive_summary_description.try_into()?,
@@ -2734,7 +3177,8 @@ impl TryFrom<RegularWithdrawalDto> for RegularWithdrawal {
             start_date_of_action: dto.start_date_of_action.try_into()?,
             frequency: dto.frequency.try_into()?,
             end_date_of_action: if dto.end_date_of_action.is_some() { Some(dto.end_date_of_action.unwrap().try_into()?) } else { None },
-            tax_year_of_action: if dto.tax_year_of_action.is_some() { Some(dto.tax_year_of_action.unwrap().try_into()?) } else { None }
+            tax_year_of_action: if dto.tax_year_of_action.is_some() { Some(TaxYear::try_from((dto.tax_year_of_action.unwrap(), clock))?) } else { None },
+            gifted: dto.gifted
         })
     }
 }
@@ -2751,10 +3195,10 @@ pub struct Transfer {
     transfer_to_details: TransferDetail
 }
 
-impl TryFrom<TransferDto> for Transfer {
+impl TryFrom<(TransferDto, &dyn Clock)> for Transfer {
     type Error = String;
 
-    fn try_from(dto: TransferDto) -> Result<Self, Self::Error> {
+    fn try_from((dto, clock): (TransferDto, &dyn Clock)) -> Result<Self, Self::Error> {
 
         Ok(Self {
             value: dto.value.try_into()?,
@@ -2762,7 +3206,7 @@ impl TryFrom<TransferDto> for Transfer {
             executive_summary_description_transferring_product: dto. executive_summary_description_transferring_product.map(|dto| dto.try_into()).transpose()?,
             rationale: dto.rationale.try_into()?,
             date_of_action: if dto.date_of_action.is_some() { Some(dto.date_of_action.unwrap().try_into()?) } else { None },
-            tax_year_of_action: if dto.tax_year_of_action.is_some() { Some(dto.tax_year_of_action.unwrap().try_into()?) } else { None },
+            tax_year_of_action: if dto.tax_year_of_action.is_some() { Some(TaxYear::try_from((dto.tax_year_of_action.unwrap(), clock))?) } else { None },
             transfer_to_details: dto.transfer_details.try_into()?
         })
     }
@@ -2779,17 +3223,17 @@ pub struct StopWithdrawal {
     end_date_of_action: Option<Date>,
 }
 
-impl TryFrom<StopWithdrawalDto> for StopWithdrawal {
+impl TryFrom<(StopWithdrawalDto, &dyn Clock)> for StopWithdrawal {
     type Error = String;
 
-    fn try_from(dto: StopWithdrawalDto) -> Result<Self, Self::Error> {
+    fn try_from((dto, clock): (StopWithdrawalDto, &dyn Clock)) -> Result<Self, Self::Error> {
         Ok(Self {
             value: dto.value.try_into()?,
             executive_summary_description: dto.executive_summary_description.try_into()?,
             rationale: dto.rationale.try_into()?,
             start_date_of_action: if dto.start_date_of_action.is_some() { Some(dto.start_date_of_action.unwrap().try_into()?) } else { None },
             end_date_of_action: if dto.end_date_of_action.is_some() { Some(dto.end_date_of_action.unwrap().try_into()?) } else { None },
-            tax_year_of_action: if dto.tax_year_of_action.is_some() { Some(dto.tax_year_of_action.unwrap().try_into()?) } else { None }
+            tax_year_of_action: if dto.tax_year_of_action.is_some() { Some(TaxYear::try_from((dto.tax_year_of_action.unwrap(), clock))?) } else { None }
         })
     }
 }
@@ -2998,6 +3442,17 @@ impl TryFrom<PlatformAccountNumberTypeDto> for PlatformAccountNumberType {
     }
 }
 
+impl PlatformAccountNumberType {
+    /// The provider numbering scheme, named as in the request.
+    pub fn scheme(&self) -> &'static str {
+        match self {
+            PlatformAccountNumberType::Abrdn(_) => "abrdn",
+            PlatformAccountNumberType::Transact(_) => "transact",
+            PlatformAccountNumberType::Other(_) => "other",
+        }
+    }
+}
+
 impl fmt::Display for PlatformAccountNumberType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -3049,6 +3504,19 @@ impl TryFrom<AccountOrReferenceNumberTypeDto> for AccountOrReferenceNumberType {
     }
 }
 
+impl AccountOrReferenceNumberType {
+    /// The provider numbering scheme, named as in the request.
+    pub fn scheme(&self) -> &'static str {
+        match self {
+            AccountOrReferenceNumberType::Abrdn(_) => "abrdn",
+            AccountOrReferenceNumberType::AbrdnSipp(_) => "abrdnSipp",
+            AccountOrReferenceNumberType::Transact(_) => "transact",
+            AccountOrReferenceNumberType::Other(_) => "other",
+            AccountOrReferenceNumberType::NewAccount(_) => "newAccount",
+        }
+    }
+}
+
 impl fmt::Display for AccountOrReferenceNumberType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
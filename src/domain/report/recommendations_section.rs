@@ -1,7 +1,21 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
+use crate::driven::doc_generator::instructions::RecommendationsInstructions;
+
 use crate::{domain::report::couple_annual_review_report::couple_annual_review_recommendations_section::CoupleAnnualReviewReportRecommendationsSection, driven::repository::InvestmentPortfoliosRepository, driving::data_transfer_object::report_type_data_transfer_object::advice_areas_and_products_dto::{AdviceAreasAndProductsDto, CoupleAdviceAreasAndProductsDto}};
 
+use crate::domain::clock::Clock;
+use crate::domain::constrained_types::constrained_money_amount_large::ConstrainedMoneyAmountLarge;
+use crate::domain::constrained_types::constrained_string_200::ConstrainedString200;
+use crate::domain::constrained_types::date::Date;
+use crate::domain::constrained_types::percentage::Percentage;
+use crate::domain::constrained_types::tax_year::TaxYear;
+use crate::domain::tax::TaxRules;
+use crate::domain::tax::inheritance_tax::{Estate, Gift};
+use crate::domain::tax::pension_allowance::{self, PensionAllowanceCheck, PensionAllowanceCircumstances};
+use crate::driving::data_transfer_object::report_type_data_transfer_object::advice_areas_and_products_dto::{DeclaredAssetDto, GiftDto, IhtEstateBasisDto, MainResidenceDto, PensionAllowanceBasisDto};
+
 use super::{advice_areas::{AdviceArea, AdviceAreas}, investment_holdings::InvestmentPortfolio, product::Products};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -11,12 +25,21 @@ pub enum RecommendationsSection {
     //NewReportRecommendationsSection(NewReportRecommendationsSection)
 }
 
+impl RecommendationsSection {
+    pub fn instructions(&self, title: String) -> RecommendationsInstructions {
+        match self {
+            RecommendationsSection::CoupleAnnualReviewReportRecommendationsSection(section) => section.instructions(title)
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CoupleAdviceAreasAndProducts {
     pub client_1: Option<AdviceAreasAndProducts>,
     pub client_2: Option<AdviceAreasAndProducts>,
     pub joint:    Option<AdviceAreasAndProducts>,
+    pub married_or_in_civil_partnership: Option<bool>
 }
 
 /// “Do these raw areas contain one of the four required advice‐types?”
@@ -34,6 +57,7 @@ impl CoupleAdviceAreasAndProducts {
     pub async fn from_dto<R>(
         dto: CoupleAdviceAreasAndProductsDto,
         repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync,
@@ -41,17 +65,17 @@ impl CoupleAdviceAreasAndProducts {
         // first build your three Option<AdviceAreasAndProducts>
         // convert each optional AdviceAreasAndProductsDto
         let client_1 = match dto.client_1 {
-            Some(aap_dto) => Some(AdviceAreasAndProducts::from_dto(aap_dto, repo).await?),
+            Some(aap_dto) => Some(AdviceAreasAndProducts::from_dto(aap_dto, repo, clock).await?),
             None => None,
         };
 
         let client_2 = match dto.client_2 {
-            Some(aap_dto) => Some(AdviceAreasAndProducts::from_dto(aap_dto, repo).await?),
+            Some(aap_dto) => Some(AdviceAreasAndProducts::from_dto(aap_dto, repo, clock).await?),
             None => None,
         };
 
         let joint = match dto.joint {
-            Some(aap_dto) => Some(AdviceAreasAndProducts::from_dto(aap_dto, repo).await?),
+            Some(aap_dto) => Some(AdviceAreasAndProducts::from_dto(aap_dto, repo, clock).await?),
             None => None,
         };
 
@@ -63,6 +87,25 @@ impl CoupleAdviceAreasAndProducts {
                 .map_or(false, |areas| has_required_in_raw(areas))
         };
 
+        if joint.as_ref().is_some_and(|joint| joint.pension_allowances.is_some()) {
+            return Err("Pension allowances belong to each client, so cannot be given jointly".to_string());
+        }
+
+        if joint.as_ref().is_some_and(|joint| joint.iht_estate.is_some()) {
+            return Err("An Inheritance Tax estate belongs to each client, so cannot be given jointly".to_string());
+        }
+
+        let iht_estates = [&client_1, &client_2].map(|client| client.as_ref().is_some_and(|client| client.iht_estate.is_some()));
+        match dto.married_or_in_civil_partnership {
+            None if iht_estates.contains(&true) => {
+                return Err("Whether the couple are married or in a civil partnership is needed to estimate Inheritance Tax".to_string());
+            }
+            Some(true) if iht_estates[0] != iht_estates[1] => {
+                return Err("Married couples and civil partners need an Inheritance Tax estate for both clients, as their estates are estimated together".to_string());
+            }
+            _ => {}
+        }
+
         if !has_req(&joint) && !(has_req(&client_1) && has_req(&client_2)) {
             return Err(
                 "At least one of Emergency Fund, IHT, Wills, or POA \
@@ -71,7 +114,7 @@ impl CoupleAdviceAreasAndProducts {
             );
         }
 
-        Ok(Self { client_1, client_2, joint })
+        Ok(Self { client_1, client_2, joint, married_or_in_civil_partnership: dto.married_or_in_civil_partnership })
     }
 }
 
@@ -79,14 +122,17 @@ impl CoupleAdviceAreasAndProducts {
 #[serde(rename_all = "camelCase")]
 pub struct AdviceAreasAndProducts {
     pub advice_areas: Option<AdviceAreas>,
-    pub products: Option<Products>
+    pub products: Option<Products>,
+    pub pension_allowances: Option<Vec<PensionAllowanceBasis>>,
+    pub iht_estate: Option<IhtEstateBasis>
 }
 
 impl AdviceAreasAndProducts {
 
     pub async fn from_dto<R>(
         dto: AdviceAreasAndProductsDto,
-        repo: &R
+        repo: &R,
+        clock: &dyn Clock
     ) -> Result<Self, String>
     where
         R: InvestmentPortfoliosRepository<InvestmentPortfolio> +  Sync,
@@ -101,16 +147,199 @@ impl AdviceAreasAndProducts {
         let products = match dto.products {
             Some(products_dto) => {
                 // Products::from_dto returns Products, so wrap in Some
-                Some(Products::from_dto(products_dto, repo).await?)
+                Some(Products::from_dto(products_dto, repo, clock).await?)
             }
             None => None,
         };
 
-        Ok(Self { advice_areas, products })
+        let pension_allowances = dto
+            .pension_allowances
+            .map(|bases| bases.into_iter().map(|basis| PensionAllowanceBasis::try_from((basis, clock))).collect::<Result<_, _>>())
+            .transpose()?;
+
+        let iht_estate = dto
+            .iht_estate
+            .map(|basis| IhtEstateBasis::try_from((basis, clock)))
+            .transpose()?;
+
+        Ok(Self { advice_areas, products, pension_allowances, iht_estate })
     }
 
 }
 
+/// A client's earnings, income and unused allowance for a tax year, against which their
+/// recommended pension contributions are checked.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PensionAllowanceBasis {
+    tax_year: TaxYear,
+    relevant_earnings: ConstrainedMoneyAmountLarge,
+    threshold_income: ConstrainedMoneyAmountLarge,
+    adjusted_income: ConstrainedMoneyAmountLarge,
+    flexibly_accessed: bool,
+    /// Earliest first
+    unused_allowance: [ConstrainedMoneyAmountLarge; 3]
+}
+
+impl PensionAllowanceBasis {
+    pub fn tax_year(&self) -> &TaxYear {
+        &self.tax_year
+    }
+
+    /// Checks the client's recommended `contributions` for the tax year against their allowances.
+    pub fn check(&self, contributions: f64, tax_rules: &TaxRules) -> Result<PensionAllowanceCheck, String> {
+        let circumstances = PensionAllowanceCircumstances {
+            relevant_earnings: self.relevant_earnings.value(),
+            threshold_income: self.threshold_income.value(),
+            adjusted_income: self.adjusted_income.value(),
+            flexibly_accessed: self.flexibly_accessed,
+            unused_allowance: self.unused_allowance.each_ref().map(|unused| unused.value())
+        };
+
+        pension_allowance::check(contributions, &circumstances, &tax_rules.for_tax_year(&self.tax_year)?.pension)
+    }
+}
+
+impl TryFrom<(PensionAllowanceBasisDto, &dyn Clock)> for PensionAllowanceBasis {
+    type Error = String;
+
+    fn try_from((dto, clock): (PensionAllowanceBasisDto, &dyn Clock)) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tax_year: TaxYear::try_from((dto.tax_year, clock))?,
+            relevant_earnings: dto.relevant_earnings.try_into()?,
+            threshold_income: dto.threshold_income.try_into()?,
+            adjusted_income: dto.adjusted_income.try_into()?,
+            flexibly_accessed: dto.flexibly_accessed,
+            unused_allowance: [
+                dto.unused_allowance.three_years_before.try_into()?,
+                dto.unused_allowance.two_years_before.try_into()?,
+                dto.unused_allowance.one_year_before.try_into()?
+            ]
+        })
+    }
+}
+
+/// What a client owns and owes outside the products in the report, and the gifts they have
+/// made, against which Inheritance Tax on their estate is estimated as at the date of the report.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IhtEstateBasis {
+    as_at: NaiveDate,
+    main_residence: Option<MainResidence>,
+    other_assets: Vec<DeclaredAsset>,
+    liabilities: ConstrainedMoneyAmountLarge,
+    gifts: Vec<DeclaredGift>,
+    transferred_nil_rate_band: Percentage,
+    transferred_residence_nil_rate_band: Percentage
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MainResidence {
+    value: ConstrainedMoneyAmountLarge,
+    passes_to_direct_descendants: bool
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclaredAsset {
+    description: ConstrainedString200,
+    value: ConstrainedMoneyAmountLarge
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclaredGift {
+    date: Date,
+    value: ConstrainedMoneyAmountLarge
+}
+
+impl IhtEstateBasis {
+    pub fn as_at(&self) -> NaiveDate {
+        self.as_at
+    }
+
+    pub fn main_residence_value(&self) -> f64 {
+        self.main_residence.as_ref().map_or(0.0, |residence| residence.value.value())
+    }
+
+    pub fn other_assets_value(&self) -> f64 {
+        self.other_assets.iter().map(|asset| asset.value.value()).sum()
+    }
+
+    pub fn liabilities(&self) -> f64 {
+        self.liabilities.value()
+    }
+
+    /// The client's estate with `products` of the given value, and their own nil-rate bands
+    /// plus any transferred to them.
+    pub fn estate(&self, products: f64) -> Estate {
+        Estate {
+            assets: products + self.main_residence_value() + self.other_assets_value(),
+            liabilities: self.liabilities(),
+            residence_to_descendants: self.main_residence
+                .as_ref()
+                .filter(|residence| residence.passes_to_direct_descendants)
+                .map_or(0.0, |residence| residence.value.value()),
+            gifts: self.gifts.iter().map(|gift| Gift { date: *gift.date.value(), value: gift.value.value() }).collect(),
+            nil_rate_bands: 1.0 + f64::from(self.transferred_nil_rate_band.as_fraction()),
+            residence_nil_rate_bands: 1.0 + f64::from(self.transferred_residence_nil_rate_band.as_fraction())
+        }
+    }
+}
+
+impl TryFrom<(IhtEstateBasisDto, &dyn Clock)> for IhtEstateBasis {
+    type Error = String;
+
+    fn try_from((dto, clock): (IhtEstateBasisDto, &dyn Clock)) -> Result<Self, Self::Error> {
+        let as_at = clock.today();
+        let gifts = dto.gifts
+            .into_iter()
+            .map(|gift| {
+                let gift = DeclaredGift::try_from(gift)?;
+                if *gift.date.value() > as_at {
+                    return Err(format!("Gift dated {} is in the future", gift.date));
+                }
+                Ok(gift)
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Self {
+            as_at,
+            main_residence: dto.main_residence.map(MainResidence::try_from).transpose()?,
+            other_assets: dto.other_assets.into_iter().map(DeclaredAsset::try_from).collect::<Result<_, _>>()?,
+            liabilities: dto.liabilities.try_into()?,
+            gifts,
+            transferred_nil_rate_band: Percentage::try_from_percent(dto.transferred_nil_rate_band as f32)?,
+            transferred_residence_nil_rate_band: Percentage::try_from_percent(dto.transferred_residence_nil_rate_band as f32)?
+        })
+    }
+}
+
+impl TryFrom<MainResidenceDto> for MainResidence {
+    type Error = String;
+
+    fn try_from(dto: MainResidenceDto) -> Result<Self, Self::Error> {
+        Ok(Self { value: dto.value.try_into()?, passes_to_direct_descendants: dto.passes_to_direct_descendants })
+    }
+}
+
+impl TryFrom<DeclaredAssetDto> for DeclaredAsset {
+    type Error = String;
+
+    fn try_from(dto: DeclaredAssetDto) -> Result<Self, Self::Error> {
+        Ok(Self { description: ConstrainedString200::try_from(dto.description)?, value: dto.value.try_into()? })
+    }
+}
+
+impl TryFrom<GiftDto> for DeclaredGift {
+    type Error = String;
+
+    fn try_from(dto: GiftDto) -> Result<Self, Self::Error> {
+        Ok(Self { date: dto.date.try_into()?, value: dto.value.try_into()? })
+    }
+}
+
 /// **Helper function to check if required advice areas exist**
 fn has_required_advice(advice_opt: &Option<AdviceAreasAndProducts>) -> bool {
     if let Some(advice_areas_and_products) = advice_opt {
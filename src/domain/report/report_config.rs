@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use super::branding::FirmBranding;
+use super::contents_section::ContentsConfig;
+use super::wording_templates::WordingTemplates;
+use crate::domain::projection::ProjectionAssumptions;
+use crate::domain::tax::TaxRules;
+
+/// Firm-level settings applied to every report, loaded and validated once at startup.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportConfig {
+    pub branding: FirmBranding,
+    pub contents: ContentsConfig,
+    pub templates: WordingTemplates,
+    pub tax_rules: TaxRules,
+    pub projection_assumptions: ProjectionAssumptions
+}
@@ -2,12 +2,20 @@ use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 
+use crate::driven::doc_generator::instructions::{ReportKind, SectionInstructions};
+
 use crate::domain::report::couple_annual_review_report::CoupleAnnualReviewReport;
 use crate::domain::report::individual_annual_review_report::IndividualAnnualReviewReport;
 use crate::domain::report::couple_new_report::CoupleNewReport;
-use crate::driven::repository::InvestmentPortfoliosRepository;
+use crate::domain::find_adviser::find_one_adviser;
+use crate::domain::find_model_portfolio::FindOneError;
+use crate::domain::DomainError;
+use crate::domain::clock::Clock;
+use crate::driven::repository::{AdviserRepository, FindAdviser, InvestmentPortfoliosRepository};
 use crate::driving::data_transfer_object::report_type_data_transfer_object::ReportTypeDataTransferObject;
 
+use super::adviser_profile::AdviserProfile;
+use super::report_config::ReportConfig;
 use super::investment_holdings::InvestmentPortfolio;
 use super::ReportError;
 
@@ -21,29 +29,154 @@ pub enum ReportType {
 
 impl ReportType {
 
-    pub async fn from_dto<R>(
+    pub async fn from_dto<R, A>(
         dto: ReportTypeDataTransferObject,
-        investment_repo: Arc<R>
+        investment_repo: Arc<R>,
+        adviser_repo: Arc<A>,
+        report_config: &ReportConfig,
+        clock: &dyn Clock
     ) -> Result<Self, ReportError>
     where 
-        R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync 
+        R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Sync,
+        A: AdviserRepository<AdviserProfile> + Sync
     {
+        let adviser_profile = match &dto.adviser().id {
+            Some(adviser_id) => Some(
+                find_one_adviser(adviser_repo.as_ref(), FindAdviser { id: adviser_id.clone() })
+                    .await
+                    .map_err(|e| match e {
+                        FindOneError::NotFound => DomainError::ValidationError(format!("Adviser {} not found in the adviser directory", adviser_id)),
+                        FindOneError::Unknown(e) => DomainError::Unexpected(e)
+                    })?
+            ),
+            None => None
+        };
+
         Ok(
             match dto {
                 ReportTypeDataTransferObject::CoupleAnnualReviewReportDataTransferObject(inner_dto) => {
-                    let inner: CoupleAnnualReviewReport = CoupleAnnualReviewReport::from_dto(inner_dto, investment_repo).await?;
+                    let inner: CoupleAnnualReviewReport = CoupleAnnualReviewReport::from_dto(inner_dto, investment_repo, adviser_profile, report_config, clock).await?;
                     ReportType::CoupleAnnualReviewReport(inner)
                 }
                 ReportTypeDataTransferObject::IndividualAnnualReviewReportDataTransferObject(inner_dto) => {
-                    let inner: IndividualAnnualReviewReport = IndividualAnnualReviewReport::from_dto(inner_dto, investment_repo).await?;
+                    let inner: IndividualAnnualReviewReport = IndividualAnnualReviewReport::from_dto(inner_dto, investment_repo, adviser_profile, report_config).await?;
                     ReportType::IndividualAnnualReviewReport(inner)
                 }
                 ReportTypeDataTransferObject::CoupleNewReportDto(inner_dto) => {
-                    let inner: CoupleNewReport = CoupleNewReport::from_dto(inner_dto, investment_repo).await?;
+                    let inner: CoupleNewReport = CoupleNewReport::from_dto(inner_dto, investment_repo, adviser_profile, report_config).await?;
                     ReportType::CoupleNewReport(inner)
                 }
             }
         )
     }
 
+    pub fn instructions(&self) -> (ReportKind, Vec<SectionInstructions>) {
+        match self {
+            ReportType::CoupleAnnualReviewReport(report) => (ReportKind::CoupleAnnualReview, report.instructions()),
+            ReportType::IndividualAnnualReviewReport(report) => (ReportKind::IndividualAnnualReview, report.instructions()),
+            ReportType::CoupleNewReport(report) => (ReportKind::CoupleNew, report.instructions())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use serde_json::json;
+    use crate::domain::clock::FixedClock;
+    use crate::driven::config::parse_firm_branding;
+    use crate::driven::repository::in_memory::{InMemoryAdviserRepo, InMemoryInvestmentPortfolioRepo};
+    use crate::driving::data_transfer_object::report_type_data_transfer_object::adviser_data_transfer_object::AdviserProfileDto;
+
+    fn report_config() -> ReportConfig {
+        ReportConfig {
+            branding: parse_firm_branding(include_str!("../../../config/branding.example.json")).unwrap(),
+            contents: Default::default(),
+            templates: Default::default(),
+            tax_rules: Default::default(),
+            projection_assumptions: Default::default()
+        }
+    }
+
+    fn couple_annual_review(adviser_id: Option<&str>) -> ReportTypeDataTransferObject {
+        let mut adviser = json!({ "adviserFirstName": "Alex", "adviserLastName": "Jones" });
+        if let Some(id) = adviser_id {
+            adviser["id"] = json!(id);
+        }
+        let no_change = json!({ "type": "noChangeRiskTolerance", "riskProfile": "Moderate" });
+
+        serde_json::from_value(json!({
+            "type": "coupleAnnualReviewReport",
+            "individualOneFirstName": "Sam",
+            "individualOneLastName": "Smith",
+            "individualTwoFirstName": "Jo",
+            "individualTwoLastName": "Smith",
+            "adviser": adviser,
+            "sections": {
+                "background": { "meetingLocation": { "type": "Teams" }, "meetingDate": "01/05/2026" },
+                "currentCircumstances": {
+                    "lastMeetingDate": "01/05/2025",
+                    "lastReviewReportDate": "15/05/2025",
+                    "isChangeInCircumstances": { "type": "noChangeInCircumstances" },
+                    "coupleObjectives": {
+                        "sharedObjectives": {
+                            "type": "noChangeInObjectives",
+                            "objectives": [{
+                                "type": "otherObjective",
+                                "id": "7f0c6d1e-2b5a-4c3d-9e8f-1a2b3c4d5e6f",
+                                "objective": "Help with university fees",
+                                "objectiveSummary": "University fees",
+                                "linkedRiskProfile": { "riskProfile": "Moderate" }
+                            }]
+                        }
+                    },
+                    "coupleIsRiskToleranceChange": { "client1": no_change, "client2": no_change }
+                },
+                "recommendations": { "joint": { "adviceAreas": [{ "type": "will", "advice": "Review your wills" }] } }
+            }
+        })).unwrap()
+    }
+
+    async fn report(adviser_id: Option<&str>) -> ReportType {
+        let adviser_repo = InMemoryAdviserRepo::new(vec![
+            AdviserProfile::try_from(AdviserProfileDto {
+                id: "adviser-1".to_string(),
+                first_name: "Alex".to_string(),
+                last_name: "Jones".to_string(),
+                qualifications: vec![],
+                fca_reference_number: "ABC12345".to_string(),
+                phone: "020 7946 0000".to_string(),
+                email: "alex.jones@example.com".to_string(),
+                office: "London".to_string(),
+                signature_image_location: None
+            }).unwrap()
+        ]);
+
+        ReportType::from_dto(
+            couple_annual_review(adviser_id),
+            Arc::new(InMemoryInvestmentPortfolioRepo::default()),
+            Arc::new(adviser_repo),
+            &report_config(),
+            &FixedClock(NaiveDate::from_ymd_opt(2026, 6, 1).unwrap())
+        ).await.unwrap()
+    }
+
+    fn contact_paragraph(sections: &[SectionInstructions]) -> String {
+        sections.iter().find_map(|section| match section {
+            SectionInstructions::CurrentCircumstances(circumstances) => Some(circumstances.if_circumstances_have_changed_paragraph.clone()),
+            _ => None
+        }).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_report_without_adviser_id_does_not_refer_to_contact_details() {
+        let (_, sections) = report(None).await.instructions();
+        assert!(!sections.iter().any(|section| matches!(section, SectionInstructions::ContactDetails(_))));
+        assert!(!contact_paragraph(&sections).contains("details at the end of this report"));
+
+        let (_, sections) = report(Some("adviser-1")).await.instructions();
+        assert!(sections.iter().any(|section| matches!(section, SectionInstructions::ContactDetails(_))));
+        assert!(contact_paragraph(&sections).contains("details at the end of this report"));
+    }
 }
@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::traits::Entity;
+use crate::driven::config::WordingTemplatesConfig;
+
+/// Wording shipped with the service, used for any template a firm has not overridden.
+const DEFAULT_TEMPLATES: &str = include_str!("../../../config/wording_templates.json");
+
+/// Every piece of client-facing prose the section builders produce. The name is the key used
+/// in template files and the database, so it must not change once published.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum TemplateName {
+    BackgroundGreeting,
+    BackgroundLocationTeams,
+    BackgroundLocationHome,
+    BackgroundLocationOffice,
+    BackgroundCompanyAttendees,
+    BackgroundClientAttendees,
+    BackgroundParagraph,
+    CircumstancesChanged,
+    CircumstancesUnchanged,
+    ObjectivesIntroduction,
+    CircumstancesContact,
+    CircumstancesContactWithoutDetails,
+    PreviousReview,
+    RecommendationsIntroduction,
+    ProductRetain,
+    ProductFullyEncash,
+    ProductFullyReplace,
+    ProductPartiallyReplace,
+    DestinationExistingJoint,
+    DestinationExistingSingle,
+    DestinationNew,
+    NewProductExistingPlatform,
+    NewProductNewPlatform,
+    CapitalGainsTaxPayable,
+    CapitalGainsTaxNotPayable,
+    ChargeableGainWithinAllowance,
+    ChargeableGainPayable,
+    ChargeableGainNotPayable,
+    IsaAllowanceRemaining,
+    JuniorIsaAllowanceRemaining,
+    PensionAllowanceRemaining,
+    PensionAllowanceCarryForward,
+    PensionMoneyPurchaseAllowanceRemaining,
+    IncomeProjectionIntroduction,
+    IncomeProjectionOnTrack,
+    IncomeProjectionShortfall,
+    IncomeProjectionProbability,
+    WithdrawalRangeOfOutcomes,
+    EmergencyFundShortfall,
+    EmergencyFundCovered,
+    InheritanceTaxEstimate,
+    InheritanceTaxEstimateCouple,
+    InheritanceTaxAfterGifts
+}
+
+impl TemplateName {
+    pub const ALL: [TemplateName; 43] = [
+        TemplateName::BackgroundGreeting,
+        TemplateName::BackgroundLocationTeams,
+        TemplateName::BackgroundLocationHome,
+        TemplateName::BackgroundLocationOffice,
+        TemplateName::BackgroundCompanyAttendees,
+        TemplateName::BackgroundClientAttendees,
+        TemplateName::BackgroundParagraph,
+        TemplateName::CircumstancesChanged,
+        TemplateName::CircumstancesUnchanged,
+        TemplateName::ObjectivesIntroduction,
+        TemplateName::CircumstancesContact,
+        TemplateName::CircumstancesContactWithoutDetails,
+        TemplateName::PreviousReview,
+        TemplateName::RecommendationsIntroduction,
+        TemplateName::ProductRetain,
+        TemplateName::ProductFullyEncash,
+        TemplateName::ProductFullyReplace,
+        TemplateName::ProductPartiallyReplace,
+        TemplateName::DestinationExistingJoint,
+        TemplateName::DestinationExistingSingle,
+        TemplateName::DestinationNew,
+        TemplateName::NewProductExistingPlatform,
+        TemplateName::NewProductNewPlatform,
+        TemplateName::CapitalGainsTaxPayable,
+        TemplateName::CapitalGainsTaxNotPayable,
+        TemplateName::ChargeableGainWithinAllowance,
+        TemplateName::ChargeableGainPayable,
+        TemplateName::ChargeableGainNotPayable,
+        TemplateName::IsaAllowanceRemaining,
+        TemplateName::JuniorIsaAllowanceRemaining,
+        TemplateName::PensionAllowanceRemaining,
+        TemplateName::PensionAllowanceCarryForward,
+        TemplateName::PensionMoneyPurchaseAllowanceRemaining,
+        TemplateName::IncomeProjectionIntroduction,
+        TemplateName::IncomeProjectionOnTrack,
+        TemplateName::IncomeProjectionShortfall,
+        TemplateName::IncomeProjectionProbability,
+        TemplateName::WithdrawalRangeOfOutcomes,
+        TemplateName::EmergencyFundShortfall,
+        TemplateName::EmergencyFundCovered,
+        TemplateName::InheritanceTaxEstimate,
+        TemplateName::InheritanceTaxEstimateCouple,
+        TemplateName::InheritanceTaxAfterGifts
+    ];
+
+    /// The placeholders the section builders supply when rendering this template.
+    pub fn variables(&self) -> &'static [&'static str] {
+        match self {
+            TemplateName::BackgroundGreeting
+            | TemplateName::BackgroundLocationTeams
+            | TemplateName::BackgroundLocationOffice
+            | TemplateName::ObjectivesIntroduction
+            | TemplateName::CircumstancesContact
+            | TemplateName::CircumstancesContactWithoutDetails
+            | TemplateName::RecommendationsIntroduction => &[],
+            TemplateName::BackgroundLocationHome => &["town"],
+            TemplateName::BackgroundCompanyAttendees | TemplateName::BackgroundClientAttendees => &["names"],
+            TemplateName::BackgroundParagraph => &["greeting", "company_attendees", "client_attendees", "location", "meeting_date"],
+            TemplateName::CircumstancesChanged | TemplateName::CircumstancesUnchanged => &["last_meeting_date"],
+            TemplateName::PreviousReview => &["last_review_date"],
+            TemplateName::ProductRetain | TemplateName::ProductFullyEncash => &["provider", "product"],
+            TemplateName::ProductFullyReplace | TemplateName::ProductPartiallyReplace => &["provider", "product", "destination"],
+            TemplateName::DestinationExistingJoint
+            | TemplateName::DestinationExistingSingle
+            | TemplateName::DestinationNew => &["provider", "tax_wrapper"],
+            TemplateName::NewProductExistingPlatform | TemplateName::NewProductNewPlatform => &["provider", "tax_wrapper", "account_kind"],
+            TemplateName::CapitalGainsTaxPayable => &["disposal", "base_cost", "outcome", "tax_year", "tax"],
+            TemplateName::CapitalGainsTaxNotPayable => &["disposal", "base_cost", "outcome", "tax_year"],
+            TemplateName::ChargeableGainWithinAllowance => &["disposal", "allowance"],
+            TemplateName::ChargeableGainPayable => &["disposal", "gain", "tax_year", "relief", "tax"],
+            TemplateName::ChargeableGainNotPayable => &["disposal", "gain", "tax_year", "relief"],
+            TemplateName::IsaAllowanceRemaining => &["tax_year", "subscribed", "allowance", "remaining"],
+            TemplateName::JuniorIsaAllowanceRemaining => &["child", "tax_year", "subscribed", "allowance", "remaining"],
+            TemplateName::PensionAllowanceRemaining
+            | TemplateName::PensionMoneyPurchaseAllowanceRemaining => &["tax_year", "contributions", "allowance", "remaining"],
+            TemplateName::PensionAllowanceCarryForward => &["tax_year", "contributions", "allowance", "carried_forward", "remaining"],
+            TemplateName::IncomeProjectionIntroduction => &["life_expectancy", "inflation"],
+            TemplateName::IncomeProjectionOnTrack => &["income", "from_year", "until_year", "remaining"],
+            TemplateName::IncomeProjectionShortfall => &["income", "from_year", "until_year", "runs_out_in"],
+            TemplateName::IncomeProjectionProbability => &["probability", "simulations"],
+            TemplateName::WithdrawalRangeOfOutcomes => &["account", "withdrawal", "from_year", "until_year", "probability", "simulations"],
+            TemplateName::EmergencyFundShortfall => &["months", "expenditure", "target", "held", "cash_isas", "shortfall"],
+            TemplateName::EmergencyFundCovered => &["months", "expenditure", "target", "held", "cash_isas", "excess"],
+            TemplateName::InheritanceTaxEstimate
+            | TemplateName::InheritanceTaxEstimateCouple => &["estate", "tax"],
+            TemplateName::InheritanceTaxAfterGifts => &["gifted", "tax"]
+        }
+    }
+
+    pub fn key(&self) -> String {
+        serde_json::to_value(self)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Variable(String)
+}
+
+/// Splits a template into literal text and `{variable}` placeholders. `{{` and `}}` escape braces.
+fn parse(source: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut variable = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) if c.is_ascii_lowercase() || c == '_' => variable.push(c),
+                        Some(c) => return Err(format!("Invalid character '{}' in placeholder", c)),
+                        None => return Err("Unclosed placeholder".to_string())
+                    }
+                }
+                if variable.is_empty() {
+                    return Err("Empty placeholder".to_string());
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Variable(variable));
+            }
+            '}' => return Err("Unmatched '}'".to_string()),
+            c => literal.push(c)
+        }
+    }
+
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+
+    Ok(segments)
+}
+
+/// A versioned, validated set of wording templates. Every template is parsed and checked
+/// against its known variables on construction, so rendering cannot fail.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(try_from = "WordingTemplatesConfig", into = "WordingTemplatesConfig")]
+pub struct WordingTemplates {
+    version: String,
+    sources: HashMap<TemplateName, String>,
+    parsed: HashMap<TemplateName, Vec<Segment>>
+}
+
+impl Entity for WordingTemplates {}
+
+impl WordingTemplates {
+    /// Builds a template set that must define every template.
+    pub fn new(version: String, sources: HashMap<TemplateName, String>) -> Result<Self, String> {
+        if version.trim().is_empty() {
+            return Err("Wording templates must have a version".to_string());
+        }
+
+        let mut parsed = HashMap::new();
+        for name in TemplateName::ALL {
+            let source = sources
+                .get(&name)
+                .ok_or_else(|| format!("Template '{}' is missing", name.key()))?;
+
+            let segments = parse(source)
+                .map_err(|e| format!("Template '{}': {}", name.key(), e))?;
+
+            if let Some(Segment::Variable(unknown)) = segments.iter().find(|segment| match segment {
+                Segment::Variable(variable) => !name.variables().contains(&variable.as_str()),
+                Segment::Literal(_) => false
+            }) {
+                return Err(format!(
+                    "Template '{}' references unknown variable '{}'. Available variables: {}",
+                    name.key(),
+                    unknown,
+                    name.variables().join(", ")
+                ));
+            }
+
+            parsed.insert(name, segments);
+        }
+
+        Ok(Self { version, sources, parsed })
+    }
+
+    /// Builds a template set from a partial set of overrides, falling back to the bundled wording.
+    pub fn with_overrides(version: String, overrides: HashMap<TemplateName, String>) -> Result<Self, String> {
+        let mut sources = Self::default().sources;
+        sources.extend(overrides);
+        Self::new(version, sources)
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Renders a template with the supplied variables. Callers supply every variable listed by
+    /// `TemplateName::variables`; a variable that is not supplied renders as empty text.
+    pub fn render(&self, name: TemplateName, variables: &[(&str, &str)]) -> String {
+        self.parsed
+            .get(&name)
+            .map(|segments| segments
+                .iter()
+                .map(|segment| match segment {
+                    Segment::Literal(text) => text.as_str(),
+                    Segment::Variable(variable) => variables
+                        .iter()
+                        .find(|(key, _)| key == variable)
+                        .map(|(_, value)| *value)
+                        .unwrap_or_default()
+                })
+                .collect()
+            )
+            .unwrap_or_default()
+    }
+}
+
+impl Default for WordingTemplates {
+    fn default() -> Self {
+        let config: WordingTemplatesConfig = serde_json::from_str(DEFAULT_TEMPLATES)
+            .expect("Bundled wording templates are valid JSON");
+        Self::new(config.version, config.templates)
+            .expect("Bundled wording templates are valid")
+    }
+}
+
+impl TryFrom<WordingTemplatesConfig> for WordingTemplates {
+    type Error = String;
+
+    fn try_from(config: WordingTemplatesConfig) -> Result<Self, Self::Error> {
+        Self::new(config.version, config.templates)
+    }
+}
+
+impl From<WordingTemplates> for WordingTemplatesConfig {
+    fn from(templates: WordingTemplates) -> Self {
+        Self { version: templates.version, templates: templates.sources }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_templates_are_valid() {
+        let templates = WordingTemplates::default();
+        assert_eq!(
+            templates.render(TemplateName::ProductRetain, &[("provider", "Transact"), ("product", "Individual Savings Account (ISA)")]),
+            "I recommend you continue to retain the Transact Individual Savings Account (ISA)."
+        );
+    }
+
+    #[test]
+    fn test_overrides_are_rendered() {
+        let templates = WordingTemplates::with_overrides(
+            "2025.1".to_string(),
+            HashMap::from([(TemplateName::PreviousReview, "We last reviewed your plans on the {last_review_date} {{ref}}.".to_string())])
+        ).unwrap();
+
+        assert_eq!(templates.version(), "2025.1");
+        assert_eq!(
+            templates.render(TemplateName::PreviousReview, &[("last_review_date", "1st March 2024")]),
+            "We last reviewed your plans on the 1st March 2024 {ref}."
+        );
+        assert_eq!(templates.render(TemplateName::BackgroundGreeting, &[]), "It was lovely to see you");
+    }
+
+    #[test]
+    fn test_unknown_variable_fails_at_load() {
+        let result = WordingTemplates::with_overrides(
+            "2025.1".to_string(),
+            HashMap::from([(TemplateName::ProductRetain, "Keep the {provider} {account_number}.".to_string())])
+        );
+        assert!(result.unwrap_err().contains("unknown variable 'account_number'"));
+    }
+
+    #[test]
+    fn test_malformed_templates_fail_at_load() {
+        for source in ["Keep the {provider", "Keep the {}", "Keep the provider}", "Keep the {Provider}"] {
+            let result = WordingTemplates::with_overrides(
+                "2025.1".to_string(),
+                HashMap::from([(TemplateName::ProductRetain, source.to_string())])
+            );
+            assert!(result.is_err(), "{} should be rejected", source);
+        }
+        assert!(WordingTemplates::with_overrides(" ".to_string(), HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_incomplete_set_fails() {
+        let result = WordingTemplates::new(
+            "2025.1".to_string(),
+            HashMap::from([(TemplateName::BackgroundGreeting, "Hello".to_string())])
+        );
+        assert!(result.unwrap_err().contains("is missing"));
+    }
+}
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+use super::{check_amount, check_rate};
+
+/// Capital Gains Tax allowances and rates for one tax year.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CapitalGainsTaxRules {
+    pub annual_exempt_amount: f64,
+    /// The income tax basic rate band. Gains within the part of it left unused by the client's
+    /// taxable income are taxed at `basic_rate`, the rest at `higher_rate`.
+    pub basic_rate_band: f64,
+    pub basic_rate: f64,
+    pub higher_rate: f64
+}
+
+impl CapitalGainsTaxRules {
+    pub(super) fn validate(&self) -> Result<(), String> {
+        check_amount("annualExemptAmount", self.annual_exempt_amount)?;
+        check_amount("basicRateBand", self.basic_rate_band)?;
+        check_rate("basicRate", self.basic_rate)?;
+        check_rate("higherRate", self.higher_rate)
+    }
+
+    /// Tax on the client's net gains for the year.
+    fn tax_on(&self, net_gains: f64, taxable_income: f64) -> f64 {
+        let taxable_gains = (net_gains - self.annual_exempt_amount).max(0.0);
+        let unused_basic_rate_band = (self.basic_rate_band - taxable_income).max(0.0);
+        let at_basic_rate = taxable_gains.min(unused_basic_rate_band);
+
+        at_basic_rate * self.basic_rate + (taxable_gains - at_basic_rate) * self.higher_rate
+    }
+}
+
+/// The client's position for the tax year, apart from the disposal being calculated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapitalGainsCircumstances {
+    pub taxable_income: f64,
+    pub other_gains: f64,
+    pub other_losses: f64
+}
+
+/// A disposal from a holding and the tax it gives rise to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapitalGainsTaxCalculation {
+    pub proceeds: f64,
+    /// The part of the base cost set against the proceeds
+    pub allowable_cost: f64,
+    /// Negative when the disposal realises a loss
+    pub gain: f64,
+    /// How much the disposal adds to the client's Capital Gains Tax for the year
+    pub tax_due: f64
+}
+
+/// Calculates the tax on taking `proceeds` from a holding worth `value` that cost `base_cost`.
+///
+/// A part disposal sets the base cost against the proceeds in proportion to the share of the
+/// holding disposed of. Losses in the year are set against gains before the annual exempt amount,
+/// and the disposal is taxed as the top slice of the client's gains.
+pub fn calculate(
+    base_cost: f64,
+    value: f64,
+    proceeds: f64,
+    circumstances: &CapitalGainsCircumstances,
+    rules: &CapitalGainsTaxRules
+) -> Result<CapitalGainsTaxCalculation, String> {
+    if proceeds < 0.0 || proceeds > value {
+        return Err(format!("Cannot take £{:.2} from a holding worth £{:.2}", proceeds, value));
+    }
+
+    let allowable_cost = if value > 0.0 { base_cost * proceeds / value } else { 0.0 };
+    let gain = proceeds - allowable_cost;
+
+    let other_net_gains = circumstances.other_gains - circumstances.other_losses;
+    let tax_before = rules.tax_on(other_net_gains.max(0.0), circumstances.taxable_income);
+    let tax_after = rules.tax_on((other_net_gains + gain).max(0.0), circumstances.taxable_income);
+
+    Ok(CapitalGainsTaxCalculation {
+        proceeds,
+        allowable_cost,
+        gain,
+        tax_due: ((tax_after - tax_before).max(0.0) * 100.0).round() / 100.0
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> CapitalGainsTaxRules {
+        CapitalGainsTaxRules { annual_exempt_amount: 3000.0, basic_rate_band: 37700.0, basic_rate: 0.18, higher_rate: 0.24 }
+    }
+
+    fn circumstances(taxable_income: f64, other_gains: f64, other_losses: f64) -> CapitalGainsCircumstances {
+        CapitalGainsCircumstances { taxable_income, other_gains, other_losses }
+    }
+
+    #[test]
+    fn test_full_disposal_by_higher_rate_taxpayer() {
+        let calculation = calculate(20000.0, 50000.0, 50000.0, &circumstances(60000.0, 0.0, 0.0), &rules()).unwrap();
+
+        assert_eq!(calculation.gain, 30000.0);
+        assert_eq!(calculation.tax_due, 6480.0);
+    }
+
+    #[test]
+    fn test_part_disposal_spans_both_rates() {
+        let calculation = calculate(40000.0, 100000.0, 25000.0, &circumstances(30000.0, 0.0, 0.0), &rules()).unwrap();
+
+        assert_eq!(calculation.allowable_cost, 10000.0);
+        assert_eq!(calculation.gain, 15000.0);
+        // 7,700 of the 12,000 taxable gain falls in the unused basic rate band
+        assert_eq!(calculation.tax_due, 2418.0);
+    }
+
+    #[test]
+    fn test_other_gains_and_losses() {
+        let covered = calculate(8000.0, 10000.0, 10000.0, &circumstances(60000.0, 0.0, 500.0), &rules()).unwrap();
+        assert_eq!(covered.tax_due, 0.0);
+
+        let exempt_amount_used = calculate(9000.0, 10000.0, 10000.0, &circumstances(60000.0, 3000.0, 0.0), &rules()).unwrap();
+        assert_eq!(exempt_amount_used.tax_due, 240.0);
+
+        let loss = calculate(12000.0, 10000.0, 10000.0, &circumstances(60000.0, 5000.0, 0.0), &rules()).unwrap();
+        assert_eq!(loss.gain, -2000.0);
+        assert_eq!(loss.tax_due, 0.0);
+    }
+
+    #[test]
+    fn test_proceeds_above_value_are_rejected() {
+        assert!(calculate(1000.0, 5000.0, 6000.0, &circumstances(0.0, 0.0, 0.0), &rules()).is_err());
+    }
+}
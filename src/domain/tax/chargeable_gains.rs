@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+
+use super::income_tax::IncomeTaxRules;
+
+/// Share of the premiums that may be withdrawn each policy year without an immediate charge.
+const TAX_DEFERRED_ALLOWANCE_RATE: f64 = 0.05;
+
+/// Where the bond is issued, which decides whether its gains carry a basic rate tax credit.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum BondSitus {
+    /// Gains are treated as having had basic rate tax paid, as the insurer pays tax on the fund
+    Onshore,
+    Offshore
+}
+
+/// The bond's history up to the withdrawal or surrender being calculated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BondHistory {
+    pub premiums_paid: f64,
+    /// Policy years begun since the bond started, the current year included. Each adds 5% of
+    /// the premiums to the tax-deferred allowance.
+    pub policy_years: u32,
+    pub previous_withdrawals: f64,
+    /// Gains already charged on earlier withdrawals over the allowance
+    pub previous_chargeable_gains: f64,
+    /// The policy year at whose end the last excess over the allowance was charged, if any
+    pub last_excess_event_policy_year: Option<u32>
+}
+
+impl BondHistory {
+    /// The cumulative 5% allowance not yet used by earlier withdrawals.
+    pub fn allowance_remaining(&self) -> f64 {
+        let allowance = (self.premiums_paid * TAX_DEFERRED_ALLOWANCE_RATE * self.policy_years as f64).min(self.premiums_paid);
+        let allowance_used = self.previous_withdrawals - self.previous_chargeable_gains;
+
+        (allowance - allowance_used).max(0.0)
+    }
+
+    /// Years a full surrender's gain is top-sliced over: the complete policy years since the bond
+    /// started, so the current year does not count.
+    pub fn full_surrender_slicing_years(&self) -> u32 {
+        self.policy_years.saturating_sub(1)
+    }
+
+    /// Years a withdrawal's excess is top-sliced over. The excess arises at the end of the current
+    /// policy year, which therefore counts, and years are counted from the last excess event or,
+    /// when there has been none, from the start of the bond.
+    pub fn partial_surrender_slicing_years(&self) -> u32 {
+        self.policy_years - self.last_excess_event_policy_year.unwrap_or(0).min(self.policy_years)
+    }
+}
+
+/// A chargeable event and the income tax it gives rise to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChargeableGainCalculation {
+    pub allowance_remaining: f64,
+    pub gain: f64,
+    pub top_slicing_relief: f64,
+    /// Income tax due on the gain after top-slicing relief and, for onshore bonds, the basic rate credit
+    pub tax_due: f64
+}
+
+/// Calculates the gain on withdrawing `withdrawal` from the bond, which is whatever exceeds the
+/// remaining cumulative 5% allowance.
+pub fn partial_surrender(
+    history: &BondHistory,
+    withdrawal: f64,
+    situs: BondSitus,
+    taxable_income: f64,
+    rules: &IncomeTaxRules
+) -> ChargeableGainCalculation {
+    let allowance_remaining = history.allowance_remaining();
+    let gain = (withdrawal - allowance_remaining).max(0.0);
+
+    charge(allowance_remaining, gain, history.partial_surrender_slicing_years(), situs, taxable_income, rules)
+}
+
+/// Calculates the gain on surrendering the whole bond for `surrender_value`. A loss gives no gain.
+pub fn full_surrender(
+    history: &BondHistory,
+    surrender_value: f64,
+    situs: BondSitus,
+    taxable_income: f64,
+    rules: &IncomeTaxRules
+) -> ChargeableGainCalculation {
+    let gain = (surrender_value + history.previous_withdrawals - history.premiums_paid - history.previous_chargeable_gains).max(0.0);
+
+    charge(history.allowance_remaining(), gain, history.full_surrender_slicing_years(), situs, taxable_income, rules)
+}
+
+/// Taxes the gain on top of the client's income, with top-slicing relief limiting the tax to what
+/// would be due if the gain had arisen evenly over `slicing_years`, which are at least one.
+fn charge(
+    allowance_remaining: f64,
+    gain: f64,
+    slicing_years: u32,
+    situs: BondSitus,
+    taxable_income: f64,
+    rules: &IncomeTaxRules
+) -> ChargeableGainCalculation {
+    let credit_rate = match situs {
+        BondSitus::Onshore => rules.basic_rate,
+        BondSitus::Offshore => 0.0
+    };
+    let tax = |amount: f64| (rules.tax_on_top(amount, taxable_income) - amount * credit_rate).max(0.0);
+
+    let years = slicing_years.max(1) as f64;
+    let tax_before_relief = tax(gain);
+    let top_slicing_relief = (tax_before_relief - tax(gain / years) * years).max(0.0);
+
+    ChargeableGainCalculation {
+        allowance_remaining,
+        gain,
+        top_slicing_relief: pence(top_slicing_relief),
+        tax_due: pence(tax_before_relief - top_slicing_relief)
+    }
+}
+
+fn pence(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> IncomeTaxRules {
+        IncomeTaxRules { basic_rate_band: 37700.0, basic_rate: 0.2, higher_rate_limit: 125140.0, higher_rate: 0.4, additional_rate: 0.45 }
+    }
+
+    fn history(policy_years: u32, previous_withdrawals: f64) -> BondHistory {
+        BondHistory { premiums_paid: 100000.0, policy_years, previous_withdrawals, previous_chargeable_gains: 0.0, last_excess_event_policy_year: None }
+    }
+
+    #[test]
+    fn test_withdrawal_within_allowance() {
+        let calculation = partial_surrender(&history(4, 10000.0), 10000.0, BondSitus::Onshore, 60000.0, &rules());
+
+        assert_eq!(calculation.allowance_remaining, 10000.0);
+        assert_eq!(calculation.gain, 0.0);
+        assert_eq!(calculation.tax_due, 0.0);
+    }
+
+    #[test]
+    fn test_allowance_is_capped_at_premiums() {
+        assert_eq!(history(25, 0.0).allowance_remaining(), 100000.0);
+    }
+
+    #[test]
+    fn test_top_slicing_keeps_gain_in_basic_rate_band() {
+        // A 50,000 gain on 30,000 of income reaches the higher rate, but each 5,000 slice does not
+        let offshore = partial_surrender(&history(10, 0.0), 100000.0, BondSitus::Offshore, 30000.0, &rules());
+        assert_eq!(offshore.gain, 50000.0);
+        assert_eq!(offshore.top_slicing_relief, 8460.0);
+        assert_eq!(offshore.tax_due, 10000.0);
+
+        let onshore = partial_surrender(&history(10, 0.0), 100000.0, BondSitus::Onshore, 30000.0, &rules());
+        assert_eq!(onshore.top_slicing_relief, 8460.0);
+        assert_eq!(onshore.tax_due, 0.0);
+    }
+
+    #[test]
+    fn test_full_surrender() {
+        let calculation = full_surrender(&history(5, 20000.0), 110000.0, BondSitus::Onshore, 60000.0, &rules());
+
+        // 110,000 + 20,000 withdrawn - 100,000 paid, all taxed at 40% less the 20% credit
+        assert_eq!(calculation.gain, 30000.0);
+        assert_eq!(calculation.top_slicing_relief, 0.0);
+        assert_eq!(calculation.tax_due, 6000.0);
+
+        assert_eq!(full_surrender(&history(5, 0.0), 90000.0, BondSitus::Offshore, 60000.0, &rules()).gain, 0.0);
+    }
+
+    #[test]
+    fn test_full_surrender_just_after_anniversary_is_sliced_over_complete_years() {
+        // The sixth policy year has just begun, so five years are complete
+        let calculation = full_surrender(&history(6, 0.0), 150000.0, BondSitus::Offshore, 30000.0, &rules());
+
+        assert_eq!(calculation.gain, 50000.0);
+        // Each 10,000 slice puts 2,300 into the higher rate band
+        assert_eq!(calculation.tax_due, 12300.0);
+        assert_eq!(calculation.top_slicing_relief, 6160.0);
+    }
+
+    #[test]
+    fn test_second_excess_event_is_sliced_from_the_first() {
+        let history = BondHistory {
+            premiums_paid: 100000.0,
+            policy_years: 10,
+            previous_withdrawals: 60000.0,
+            previous_chargeable_gains: 25000.0,
+            last_excess_event_policy_year: Some(7)
+        };
+        let calculation = partial_surrender(&history, 45000.0, BondSitus::Offshore, 30000.0, &rules());
+
+        // 50% allowance less the 35,000 already used leaves 15,000; the excess is sliced over years 8 to 10
+        assert_eq!(calculation.allowance_remaining, 15000.0);
+        assert_eq!(calculation.gain, 30000.0);
+        assert_eq!(calculation.tax_due, 7380.0);
+        assert_eq!(calculation.top_slicing_relief, 3080.0);
+    }
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+use super::{check_amount, check_rate};
+
+/// Income tax bands and rates for one tax year, applied to taxable income after allowances.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct IncomeTaxRules {
+    pub basic_rate_band: f64,
+    pub basic_rate: f64,
+    /// Taxable income above which the additional rate applies
+    pub higher_rate_limit: f64,
+    pub higher_rate: f64,
+    pub additional_rate: f64
+}
+
+impl IncomeTaxRules {
+    pub(super) fn validate(&self) -> Result<(), String> {
+        check_amount("basicRateBand", self.basic_rate_band)?;
+        check_amount("higherRateLimit", self.higher_rate_limit)?;
+        check_rate("basicRate", self.basic_rate)?;
+        check_rate("higherRate", self.higher_rate)?;
+        check_rate("additionalRate", self.additional_rate)?;
+
+        if self.higher_rate_limit < self.basic_rate_band {
+            return Err("higherRateLimit must not be below basicRateBand".to_string());
+        }
+
+        Ok(())
+    }
+
+    fn tax(&self, taxable_income: f64) -> f64 {
+        let at_basic_rate = taxable_income.clamp(0.0, self.basic_rate_band);
+        let at_higher_rate = taxable_income.clamp(self.basic_rate_band, self.higher_rate_limit) - self.basic_rate_band;
+        let at_additional_rate = (taxable_income - self.higher_rate_limit).max(0.0);
+
+        at_basic_rate * self.basic_rate + at_higher_rate * self.higher_rate + at_additional_rate * self.additional_rate
+    }
+
+    /// Tax on `amount` of income received on top of the client's other taxable income.
+    pub fn tax_on_top(&self, amount: f64, taxable_income: f64) -> f64 {
+        self.tax(taxable_income + amount) - self.tax(taxable_income)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pence(amount: f64) -> f64 {
+        (amount * 100.0).round() / 100.0
+    }
+
+    #[test]
+    fn test_tax_on_top_crosses_bands() {
+        let rules = IncomeTaxRules { basic_rate_band: 37700.0, basic_rate: 0.2, higher_rate_limit: 125140.0, higher_rate: 0.4, additional_rate: 0.45 };
+
+        assert_eq!(pence(rules.tax_on_top(10000.0, 0.0)), 2000.0);
+        assert_eq!(pence(rules.tax_on_top(10000.0, 32700.0)), 3000.0);
+        assert_eq!(pence(rules.tax_on_top(10000.0, 120140.0)), 4250.0);
+    }
+}
@@ -0,0 +1,225 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::{check_amount, check_rate};
+
+/// Gifts made this many years or more before death fall outside the estate.
+const GIFT_YEARS: u32 = 7;
+
+/// Inheritance Tax bands and rates for one tax year.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct InheritanceTaxRules {
+    pub nil_rate_band: f64,
+    /// Set against a home passing to direct descendants
+    pub residence_nil_rate_band: f64,
+    /// Net estate above which the residence nil-rate band falls by £1 for every £2
+    pub residence_nil_rate_band_taper_threshold: f64,
+    pub rate: f64,
+    /// Whether unused pension funds form part of the estate
+    pub pensions_in_estate: bool
+}
+
+impl InheritanceTaxRules {
+    pub(super) fn validate(&self) -> Result<(), String> {
+        check_amount("nilRateBand", self.nil_rate_band)?;
+        check_amount("residenceNilRateBand", self.residence_nil_rate_band)?;
+        check_amount("residenceNilRateBandTaperThreshold", self.residence_nil_rate_band_taper_threshold)?;
+        check_rate("rate", self.rate)
+    }
+}
+
+/// A gift that counts towards the estate if the giver dies within seven years, after any
+/// exemptions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gift {
+    pub date: NaiveDate,
+    pub value: f64
+}
+
+/// An estate as it would stand on one death.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Estate {
+    /// Everything the estate owns, including the home
+    pub assets: f64,
+    pub liabilities: f64,
+    /// The value of the home passing to direct descendants, against which the residence
+    /// nil-rate band can be set
+    pub residence_to_descendants: f64,
+    pub gifts: Vec<Gift>,
+    /// Nil-rate bands available as a multiple of one band: the deceased's own and any
+    /// transferred from a spouse or civil partner, up to two in all
+    pub nil_rate_bands: f64,
+    pub residence_nil_rate_bands: f64
+}
+
+impl Estate {
+    /// The estate on the second death of a married couple or civil partners, the first having
+    /// left everything to the other. The survivor inherits the first's unused bands, and gifts
+    /// by either are set against the bands together.
+    pub fn with_spouse(self, spouse: Estate) -> Estate {
+        Estate {
+            assets: self.assets + spouse.assets,
+            liabilities: self.liabilities + spouse.liabilities,
+            residence_to_descendants: self.residence_to_descendants + spouse.residence_to_descendants,
+            gifts: self.gifts.into_iter().chain(spouse.gifts).collect(),
+            nil_rate_bands: self.nil_rate_bands + spouse.nil_rate_bands,
+            residence_nil_rate_bands: self.residence_nil_rate_bands + spouse.residence_nil_rate_bands
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InheritanceTaxEstimate {
+    pub net_estate: f64,
+    pub gifts_within_seven_years: f64,
+    /// The nil-rate band left for the estate once gifts have used theirs
+    pub nil_rate_band: f64,
+    /// The residence nil-rate band after tapering, up to the value of the home
+    pub residence_nil_rate_band: f64,
+    pub taxable_estate: f64,
+    /// Tax on gifts above the nil-rate band, after taper relief
+    pub tax_on_gifts: f64,
+    pub tax_on_estate: f64
+}
+
+impl InheritanceTaxEstimate {
+    pub fn total_tax(&self) -> f64 {
+        self.tax_on_gifts + self.tax_on_estate
+    }
+}
+
+/// Estimates the Inheritance Tax due were death to occur on `date`. Gifts within seven years
+/// use the nil-rate band first, oldest first, and any left is set against the estate.
+pub fn estimate(estate: &Estate, date: NaiveDate, rules: &InheritanceTaxRules) -> InheritanceTaxEstimate {
+    let mut gifts: Vec<&Gift> = estate.gifts
+        .iter()
+        .filter(|gift| gift.date <= date && date.years_since(gift.date).is_some_and(|years| years < GIFT_YEARS))
+        .collect();
+    gifts.sort_by_key(|gift| gift.date);
+
+    let mut nil_rate_band = rules.nil_rate_band * estate.nil_rate_bands.min(2.0);
+    let mut tax_on_gifts = 0.0;
+    for gift in &gifts {
+        let covered = gift.value.min(nil_rate_band);
+        nil_rate_band -= covered;
+        let relief = taper_relief(date.years_since(gift.date).unwrap_or(0));
+        tax_on_gifts += (gift.value - covered) * rules.rate * (1.0 - relief);
+    }
+
+    let net_estate = (estate.assets - estate.liabilities).max(0.0);
+    let taper = ((net_estate - rules.residence_nil_rate_band_taper_threshold) / 2.0).max(0.0);
+    let residence_nil_rate_band = (rules.residence_nil_rate_band * estate.residence_nil_rate_bands.min(2.0) - taper)
+        .max(0.0)
+        .min(estate.residence_to_descendants);
+    let taxable_estate = (net_estate - nil_rate_band - residence_nil_rate_band).max(0.0);
+
+    InheritanceTaxEstimate {
+        net_estate,
+        gifts_within_seven_years: gifts.iter().map(|gift| gift.value).sum(),
+        nil_rate_band,
+        residence_nil_rate_band,
+        taxable_estate,
+        tax_on_gifts,
+        tax_on_estate: taxable_estate * rules.rate
+    }
+}
+
+/// The reduction in tax on a gift made three to seven years before death.
+fn taper_relief(years: u32) -> f64 {
+    match years {
+        0..=2 => 0.0,
+        3 => 0.2,
+        4 => 0.4,
+        5 => 0.6,
+        _ => 0.8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> InheritanceTaxRules {
+        InheritanceTaxRules {
+            nil_rate_band: 325000.0,
+            residence_nil_rate_band: 175000.0,
+            residence_nil_rate_band_taper_threshold: 2000000.0,
+            rate: 0.4,
+            pensions_in_estate: false
+        }
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn estate(assets: f64) -> Estate {
+        Estate {
+            assets,
+            liabilities: 0.0,
+            residence_to_descendants: 400000.0,
+            gifts: Vec::new(),
+            nil_rate_bands: 1.0,
+            residence_nil_rate_bands: 1.0
+        }
+    }
+
+    #[test]
+    fn test_estate_within_bands() {
+        let estimate = estimate(&estate(500000.0), date(2026, 6, 1), &rules());
+
+        assert_eq!(estimate.taxable_estate, 0.0);
+        assert_eq!(estimate.total_tax(), 0.0);
+    }
+
+    #[test]
+    fn test_transferred_bands() {
+        let single = estimate(&estate(1200000.0), date(2026, 6, 1), &rules());
+        let transferred = estimate(&Estate { nil_rate_bands: 2.0, residence_nil_rate_bands: 2.0, ..estate(1200000.0) }, date(2026, 6, 1), &rules());
+
+        // £1.2m less £325,000 and £175,000
+        assert_eq!(single.tax_on_estate, 280000.0);
+        // The home caps the residence nil-rate band at £400,000
+        assert_eq!(transferred.residence_nil_rate_band, 350000.0);
+        assert_eq!(transferred.tax_on_estate, 80000.0);
+    }
+
+    #[test]
+    fn test_estate_with_spouse() {
+        let couple = estate(600000.0).with_spouse(Estate { residence_to_descendants: 0.0, ..estate(700000.0) });
+        let estimate = estimate(&couple, date(2026, 6, 1), &rules());
+
+        assert_eq!(couple.assets, 1300000.0);
+        // Both nil-rate bands, and the residence nil-rate band up to the one home passing on
+        assert_eq!(estimate.nil_rate_band, 650000.0);
+        assert_eq!(estimate.residence_nil_rate_band, 350000.0);
+        assert_eq!(estimate.tax_on_estate, 120000.0);
+    }
+
+    #[test]
+    fn test_residence_nil_rate_band_taper() {
+        let estimate = estimate(&Estate { liabilities: 100000.0, ..estate(2250000.0) }, date(2026, 6, 1), &rules());
+
+        // A net estate of £2.15m loses £75,000 of the band
+        assert_eq!(estimate.residence_nil_rate_band, 100000.0);
+        assert_eq!(estimate.taxable_estate, 1725000.0);
+    }
+
+    #[test]
+    fn test_gifts_within_seven_years() {
+        let gifts = vec![
+            Gift { date: date(2018, 1, 1), value: 100000.0 },
+            Gift { date: date(2022, 3, 1), value: 400000.0 },
+            Gift { date: date(2025, 1, 1), value: 25000.0 }
+        ];
+        let estimate = estimate(&Estate { gifts, ..estate(500000.0) }, date(2026, 6, 1), &rules());
+
+        // The 2018 gift has fallen out. The 2022 gift uses the whole nil-rate band, and the
+        // £75,000 above it gets 40% taper relief as it was made four years before
+        assert_eq!(estimate.gifts_within_seven_years, 425000.0);
+        assert_eq!(estimate.nil_rate_band, 0.0);
+        assert_eq!(estimate.tax_on_gifts, 75000.0 * 0.4 * 0.6 + 25000.0 * 0.4);
+        assert_eq!(estimate.taxable_estate, 325000.0);
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::constrained_types::tax_year::TaxYear;
+
+use super::{check_amount, TaxRules};
+
+/// ISA subscription limits for one tax year.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct IsaAllowanceRules {
+    /// Shared by all of a client's adult ISAs
+    pub annual_allowance: f64,
+    /// Applies to each child's Junior ISA
+    pub junior_annual_allowance: f64
+}
+
+impl IsaAllowanceRules {
+    pub(super) fn validate(&self) -> Result<(), String> {
+        check_amount("annualAllowance", self.annual_allowance)?;
+        check_amount("juniorAnnualAllowance", self.junior_annual_allowance)
+    }
+}
+
+/// The allowance a subscription counts against.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IsaAllowance {
+    Adult,
+    /// The Junior ISAs held for the named child
+    Junior(String)
+}
+
+/// How much of an allowance the recommended subscriptions use in a tax year.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IsaAllowanceUsage {
+    pub tax_year: TaxYear,
+    pub allowance: IsaAllowance,
+    pub limit: f64,
+    pub subscribed: f64,
+    pub remaining: f64
+}
+
+/// Totals a client's recommended ISA subscriptions by tax year and allowance.
+#[derive(Debug, Clone, Default)]
+pub struct IsaAllowanceLedger {
+    subscriptions: BTreeMap<(TaxYear, IsaAllowance), f64>
+}
+
+impl IsaAllowanceLedger {
+    pub fn subscribe(&mut self, tax_year: TaxYear, allowance: IsaAllowance, amount: f64) {
+        *self.subscriptions.entry((tax_year, allowance)).or_default() += amount;
+    }
+
+    /// Checks the subscriptions in each tax year against its limits, failing on the first breach.
+    pub fn usage(&self, tax_rules: &TaxRules) -> Result<Vec<IsaAllowanceUsage>, String> {
+        self.subscriptions
+            .iter()
+            .map(|((tax_year, allowance), subscribed)| {
+                let rules = &tax_rules.for_tax_year(tax_year)?.isa;
+                let (limit, name) = match allowance {
+                    IsaAllowance::Adult => (rules.annual_allowance, "ISA allowance".to_string()),
+                    IsaAllowance::Junior(child) => (rules.junior_annual_allowance, format!("Junior ISA allowance for {}", child))
+                };
+                // Compare in pence so amounts summed from regular subscriptions can use the whole allowance
+                if (subscribed * 100.0).round() > (limit * 100.0).round() {
+                    return Err(format!(
+                        "Recommended subscriptions of £{:.2} exceed the £{:.2} {} in the {} tax year",
+                        subscribed, limit, name, tax_year
+                    ));
+                }
+
+                Ok(IsaAllowanceUsage {
+                    tax_year: tax_year.clone(),
+                    allowance: allowance.clone(),
+                    limit,
+                    subscribed: *subscribed,
+                    remaining: (limit - subscribed).max(0.0)
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tax_year() -> TaxYear {
+        TaxYear::parse("2026/2027").unwrap()
+    }
+
+    #[test]
+    fn test_subscriptions_are_totalled_per_allowance() {
+        let mut ledger = IsaAllowanceLedger::default();
+        ledger.subscribe(tax_year(), IsaAllowance::Adult, 12000.0);
+        ledger.subscribe(tax_year(), IsaAllowance::Adult, 6000.0);
+        ledger.subscribe(tax_year(), IsaAllowance::Junior("Sam".to_string()), 9000.0);
+
+        let usage = ledger.usage(&TaxRules::default()).unwrap();
+
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[0].subscribed, 18000.0);
+        assert_eq!(usage[0].remaining, 2000.0);
+        assert_eq!(usage[1].remaining, 0.0);
+    }
+
+    #[test]
+    fn test_breach_is_rejected() {
+        let mut ledger = IsaAllowanceLedger::default();
+        ledger.subscribe(tax_year(), IsaAllowance::Junior("Sam".to_string()), 9000.01);
+
+        assert_eq!(
+            ledger.usage(&TaxRules::default()).unwrap_err(),
+            "Recommended subscriptions of £9000.01 exceed the £9000.00 Junior ISA allowance for Sam in the 2026/2027 tax year"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::constrained_types::tax_year::TaxYear;
+use crate::driven::config::TaxRulesConfig;
+
+use capital_gains::CapitalGainsTaxRules;
+use income_tax::IncomeTaxRules;
+use inheritance_tax::InheritanceTaxRules;
+use isa_allowance::IsaAllowanceRules;
+use pension_allowance::PensionAllowanceRules;
+
+pub mod capital_gains;
+pub mod chargeable_gains;
+pub mod income_tax;
+pub mod inheritance_tax;
+pub mod isa_allowance;
+pub mod pension_allowance;
+
+/// Rules shipped with the service, used unless a deployment supplies its own.
+const DEFAULT_TAX_RULES: &str = include_str!("../../../config/tax_rules.json");
+
+/// Allowances, bands and rates for one tax year.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TaxYearRules {
+    pub capital_gains: CapitalGainsTaxRules,
+    pub income_tax: IncomeTaxRules,
+    pub isa: IsaAllowanceRules,
+    pub pension: PensionAllowanceRules,
+    pub inheritance_tax: InheritanceTaxRules
+}
+
+impl TaxYearRules {
+    fn validate(&self) -> Result<(), String> {
+        self.capital_gains.validate().map_err(|e| format!("capital gains: {}", e))?;
+        self.income_tax.validate().map_err(|e| format!("income tax: {}", e))?;
+        self.isa.validate().map_err(|e| format!("isa: {}", e))?;
+        self.pension.validate().map_err(|e| format!("pension: {}", e))?;
+        self.inheritance_tax.validate().map_err(|e| format!("inheritance tax: {}", e))
+    }
+}
+
+/// The rules for every tax year a report may refer to, keyed by tax year. Past tax years may
+/// be configured, as reports look back over them.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "TaxRulesConfig", into = "TaxRulesConfig")]
+pub struct TaxRules {
+    tax_years: BTreeMap<TaxYear, TaxYearRules>
+}
+
+impl TaxRules {
+    /// Takes the rules keyed by tax year in `YYYY/YYYY` form, as they are configured.
+    pub fn new(tax_years: BTreeMap<String, TaxYearRules>) -> Result<Self, String> {
+        let tax_years = tax_years
+            .into_iter()
+            .map(|(tax_year, rules)| {
+                let parsed = TaxYear::parse(&tax_year).map_err(|e| format!("'{}': {}", tax_year, e))?;
+                rules.validate().map_err(|e| format!("{}: {}", tax_year, e))?;
+                Ok((parsed, rules))
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Self { tax_years })
+    }
+
+    pub fn for_tax_year(&self, tax_year: &TaxYear) -> Result<&TaxYearRules, String> {
+        self.tax_years
+            .get(tax_year)
+            .ok_or_else(|| format!("No tax rules are configured for the {} tax year", tax_year))
+    }
+
+    /// The rules for the tax year `date` falls in.
+    pub fn for_date(&self, date: NaiveDate) -> Result<&TaxYearRules, String> {
+        self.for_tax_year(&TaxYear::containing(date))
+    }
+}
+
+impl Default for TaxRules {
+    fn default() -> Self {
+        let config: TaxRulesConfig = serde_json::from_str(DEFAULT_TAX_RULES)
+            .expect("Bundled tax rules are valid JSON");
+        Self::new(config.tax_years)
+            .expect("Bundled tax rules are valid")
+    }
+}
+
+impl TryFrom<TaxRulesConfig> for TaxRules {
+    type Error = String;
+
+    fn try_from(config: TaxRulesConfig) -> Result<Self, Self::Error> {
+        Self::new(config.tax_years)
+    }
+}
+
+impl From<TaxRules> for TaxRulesConfig {
+    fn from(rules: TaxRules) -> Self {
+        Self {
+            tax_years: rules.tax_years
+                .into_iter()
+                .map(|(tax_year, rules)| (tax_year.to_string(), rules))
+                .collect()
+        }
+    }
+}
+
+/// Checks that an amount configured for a tax year is a sum of money.
+fn check_amount(name: &str, amount: f64) -> Result<(), String> {
+    if amount.is_finite() && amount >= 0.0 {
+        Ok(())
+    } else {
+        Err(format!("{} must not be negative", name))
+    }
+}
+
+/// Checks that a rate configured for a tax year is a fraction, such as `0.2` for 20%.
+fn check_rate(name: &str, rate: f64) -> Result<(), String> {
+    if (0.0..=1.0).contains(&rate) {
+        Ok(())
+    } else {
+        Err(format!("{} must be between 0 and 1", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_rules_are_valid() {
+        let rules = TaxRules::default();
+        let tax_year = TaxYear::parse("2027/2028").unwrap();
+
+        assert_eq!(rules.for_tax_year(&tax_year).unwrap().capital_gains.annual_exempt_amount, 3000.0);
+    }
+
+    #[test]
+    fn test_invalid_rules_are_rejected() {
+        let rules = TaxRules::default().tax_years[&TaxYear::parse("2026/2027").unwrap()].clone();
+        let mut negative = rules.clone();
+        negative.capital_gains.annual_exempt_amount = -1.0;
+        let mut percentage = rules.clone();
+        percentage.capital_gains.higher_rate = 24.0;
+
+        assert!(TaxRules::new(BTreeMap::from([("2026/27".to_string(), rules)])).unwrap_err().contains("YYYY/YYYY"));
+        assert!(TaxRules::new(BTreeMap::from([("2026/2027".to_string(), negative)])).unwrap_err().contains("annualExemptAmount"));
+        assert!(TaxRules::new(BTreeMap::from([("2026/2027".to_string(), percentage)])).unwrap_err().contains("higherRate"));
+    }
+
+    #[test]
+    fn test_missing_tax_year() {
+        let rules = TaxRules::new(BTreeMap::new()).unwrap();
+        let tax_year = TaxYear::parse("2027/2028").unwrap();
+
+        assert_eq!(rules.for_tax_year(&tax_year).unwrap_err(), "No tax rules are configured for the 2027/2028 tax year");
+    }
+
+    #[test]
+    fn test_rules_for_date() {
+        let rules = TaxRules::default();
+
+        // The 2025/2026 tax year has ended, but its rules are still configured
+        let date = NaiveDate::from_ymd_opt(2026, 4, 5).unwrap();
+        assert_eq!(rules.for_date(date).unwrap(), rules.for_tax_year(&TaxYear::parse("2025/2026").unwrap()).unwrap());
+    }
+}
@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+
+use super::check_amount;
+
+/// Pension annual allowances for one tax year.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PensionAllowanceRules {
+    pub annual_allowance: f64,
+    /// Replaces the annual allowance for money purchase savings once they have been flexibly accessed
+    pub money_purchase_annual_allowance: f64,
+    /// Threshold income at or below which the allowance is not tapered
+    pub taper_threshold_income: f64,
+    /// Adjusted income above which the allowance falls by £1 for every £2
+    pub taper_adjusted_income: f64,
+    pub minimum_tapered_allowance: f64,
+    /// Personal contributions up to this amount get tax relief whatever the client's earnings
+    pub basic_amount: f64
+}
+
+impl PensionAllowanceRules {
+    pub(super) fn validate(&self) -> Result<(), String> {
+        check_amount("annualAllowance", self.annual_allowance)?;
+        check_amount("moneyPurchaseAnnualAllowance", self.money_purchase_annual_allowance)?;
+        check_amount("taperThresholdIncome", self.taper_threshold_income)?;
+        check_amount("taperAdjustedIncome", self.taper_adjusted_income)?;
+        check_amount("minimumTaperedAllowance", self.minimum_tapered_allowance)?;
+        check_amount("basicAmount", self.basic_amount)?;
+
+        if self.minimum_tapered_allowance > self.annual_allowance {
+            return Err("minimumTaperedAllowance must not be above annualAllowance".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// The annual allowance after tapering for high incomes.
+    fn tapered_allowance(&self, threshold_income: f64, adjusted_income: f64) -> f64 {
+        if threshold_income <= self.taper_threshold_income || adjusted_income <= self.taper_adjusted_income {
+            return self.annual_allowance;
+        }
+
+        (self.annual_allowance - (adjusted_income - self.taper_adjusted_income) / 2.0).max(self.minimum_tapered_allowance)
+    }
+}
+
+/// The client's position for the tax year the contributions are made in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PensionAllowanceCircumstances {
+    pub relevant_earnings: f64,
+    pub threshold_income: f64,
+    pub adjusted_income: f64,
+    /// Whether the client has flexibly accessed money purchase savings, triggering the money
+    /// purchase annual allowance
+    pub flexibly_accessed: bool,
+    /// Unused allowance from each of the three previous tax years, earliest first
+    pub unused_allowance: [f64; 3]
+}
+
+/// One of the three previous tax years' unused allowance, and how much of it is carried forward.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CarryForward {
+    /// How many tax years before the contributions this allowance went unused
+    pub years_before: u32,
+    pub unused: f64,
+    pub used: f64
+}
+
+/// Recommended contributions checked against the allowances for their tax year.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PensionAllowanceCheck {
+    /// The allowance for the tax year after tapering, or the money purchase annual allowance
+    pub annual_allowance: f64,
+    pub money_purchase_annual_allowance_applies: bool,
+    pub contributions: f64,
+    /// Earliest first, or empty when unused allowance cannot be carried forward
+    pub carry_forward: Vec<CarryForward>,
+    /// What is left of this year's allowance and the unused allowance carried forward
+    pub remaining: f64
+}
+
+impl PensionAllowanceCheck {
+    pub fn uses_carry_forward(&self) -> bool {
+        self.carry_forward.iter().any(|year| year.used > 0.0)
+    }
+}
+
+/// Checks the client's personal `contributions` to money purchase pensions in a tax year.
+///
+/// Contributions must fall within the client's relevant earnings, or the basic amount, to get tax
+/// relief. Those above the annual allowance use unused allowance from the three previous tax
+/// years, earliest first. Once savings have been flexibly accessed, the money purchase annual
+/// allowance applies instead and nothing can be carried forward.
+pub fn check(
+    contributions: f64,
+    circumstances: &PensionAllowanceCircumstances,
+    rules: &PensionAllowanceRules
+) -> Result<PensionAllowanceCheck, String> {
+    let relievable = circumstances.relevant_earnings.max(rules.basic_amount);
+    if exceeds(contributions, relievable) {
+        return Err(format!(
+            "Recommended pension contributions of £{:.2} exceed the £{:.2} that can get tax relief, the greater of relevant earnings and £{:.2}",
+            contributions, relievable, rules.basic_amount
+        ));
+    }
+
+    let tapered_allowance = rules.tapered_allowance(circumstances.threshold_income, circumstances.adjusted_income);
+
+    if circumstances.flexibly_accessed {
+        let allowance = rules.money_purchase_annual_allowance.min(tapered_allowance);
+        if exceeds(contributions, allowance) {
+            return Err(format!(
+                "Recommended pension contributions of £{:.2} exceed the £{:.2} money purchase annual allowance",
+                contributions, allowance
+            ));
+        }
+
+        return Ok(PensionAllowanceCheck {
+            annual_allowance: allowance,
+            money_purchase_annual_allowance_applies: true,
+            contributions,
+            carry_forward: Vec::new(),
+            remaining: (allowance - contributions).max(0.0)
+        });
+    }
+
+    let mut excess = (contributions - tapered_allowance).max(0.0);
+    let carry_forward: Vec<CarryForward> = circumstances.unused_allowance
+        .iter()
+        .zip([3, 2, 1])
+        .map(|(&unused, years_before)| {
+            let used = excess.min(unused);
+            excess -= used;
+            CarryForward { years_before, unused, used }
+        })
+        .collect();
+
+    if exceeds(excess, 0.0) {
+        return Err(format!(
+            "Recommended pension contributions of £{:.2} exceed the £{:.2} annual allowance and £{:.2} of unused allowance carried forward",
+            contributions, tapered_allowance, circumstances.unused_allowance.iter().sum::<f64>()
+        ));
+    }
+
+    let remaining_carry_forward: f64 = carry_forward.iter().map(|year| year.unused - year.used).sum();
+
+    Ok(PensionAllowanceCheck {
+        annual_allowance: tapered_allowance,
+        money_purchase_annual_allowance_applies: false,
+        contributions,
+        carry_forward,
+        remaining: (tapered_allowance - contributions).max(0.0) + remaining_carry_forward
+    })
+}
+
+/// Compares in pence, so contributions summed from regular payments can use the whole allowance.
+fn exceeds(amount: f64, limit: f64) -> bool {
+    (amount * 100.0).round() > (limit * 100.0).round()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> PensionAllowanceRules {
+        PensionAllowanceRules {
+            annual_allowance: 60000.0,
+            money_purchase_annual_allowance: 10000.0,
+            taper_threshold_income: 200000.0,
+            taper_adjusted_income: 260000.0,
+            minimum_tapered_allowance: 10000.0,
+            basic_amount: 3600.0
+        }
+    }
+
+    fn circumstances(adjusted_income: f64, flexibly_accessed: bool) -> PensionAllowanceCircumstances {
+        PensionAllowanceCircumstances {
+            relevant_earnings: 150000.0,
+            threshold_income: adjusted_income,
+            adjusted_income,
+            flexibly_accessed,
+            unused_allowance: [20000.0, 15000.0, 10000.0]
+        }
+    }
+
+    #[test]
+    fn test_within_annual_allowance() {
+        let check = check(50000.0, &circumstances(120000.0, false), &rules()).unwrap();
+
+        assert_eq!(check.annual_allowance, 60000.0);
+        assert!(!check.uses_carry_forward());
+        assert_eq!(check.remaining, 55000.0);
+    }
+
+    #[test]
+    fn test_carry_forward_uses_earliest_year_first() {
+        let check = check(90000.0, &circumstances(120000.0, false), &rules()).unwrap();
+
+        let used: Vec<f64> = check.carry_forward.iter().map(|year| year.used).collect();
+        assert_eq!(used, vec![20000.0, 10000.0, 0.0]);
+        assert_eq!(check.remaining, 15000.0);
+
+        assert!(super::check(110000.0, &circumstances(120000.0, false), &rules()).unwrap_err().contains("£45000.00 of unused allowance"));
+    }
+
+    #[test]
+    fn test_taper() {
+        assert_eq!(check(0.0, &circumstances(300000.0, false), &rules()).unwrap().annual_allowance, 40000.0);
+        assert_eq!(check(0.0, &circumstances(400000.0, false), &rules()).unwrap().annual_allowance, 10000.0);
+
+        let mut below_threshold = circumstances(300000.0, false);
+        below_threshold.threshold_income = 190000.0;
+        assert_eq!(check(0.0, &below_threshold, &rules()).unwrap().annual_allowance, 60000.0);
+    }
+
+    #[test]
+    fn test_money_purchase_annual_allowance() {
+        let check = check(8000.0, &circumstances(120000.0, true), &rules()).unwrap();
+        assert!(check.money_purchase_annual_allowance_applies);
+        assert!(check.carry_forward.is_empty());
+        assert_eq!(check.remaining, 2000.0);
+
+        assert!(super::check(12000.0, &circumstances(120000.0, true), &rules()).unwrap_err().contains("money purchase annual allowance"));
+    }
+
+    #[test]
+    fn test_contributions_above_relevant_earnings() {
+        let mut no_earnings = circumstances(0.0, false);
+        no_earnings.relevant_earnings = 0.0;
+
+        assert!(check(3600.0, &no_earnings, &rules()).is_ok());
+        assert!(check(3601.0, &no_earnings, &rules()).unwrap_err().contains("tax relief"));
+    }
+}
@@ -0,0 +1,255 @@
+use std::{collections::{BTreeMap, HashMap}, env, fs};
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::domain::report::{branding::FirmBranding, contents_section::{ContentsConfig, SectionId}, report_config::ReportConfig, wording_templates::{TemplateName, WordingTemplates}};
+use crate::domain::projection::{ModelPortfolioAssumption, ProjectionAssumptions, RiskProfileRates};
+use crate::domain::tax::{TaxRules, TaxYearRules};
+use crate::driven::repository::{FindWordingTemplates, RepoSelectError, WordingTemplatesRepository};
+
+/// Environment variable naming the firm branding JSON file.
+pub const FIRM_BRANDING_PATH_ENV: &str = "FIRM_BRANDING_PATH";
+
+/// Optional environment variable naming a JSON file of section title and ordering overrides.
+pub const CONTENTS_CONFIG_PATH_ENV: &str = "CONTENTS_CONFIG_PATH";
+
+/// Optional environment variable naming a JSON file of wording template overrides.
+pub const WORDING_TEMPLATES_PATH_ENV: &str = "WORDING_TEMPLATES_PATH";
+
+/// Optional environment variable naming a version of the wording templates stored in the database.
+/// Takes precedence over `WORDING_TEMPLATES_PATH`.
+pub const WORDING_TEMPLATES_VERSION_ENV: &str = "WORDING_TEMPLATES_VERSION";
+
+/// Optional environment variable naming a JSON file of tax rules, replacing the bundled rules.
+pub const TAX_RULES_PATH_ENV: &str = "TAX_RULES_PATH";
+
+/// Optional environment variable naming a JSON file of projection assumptions, replacing the bundled assumptions.
+pub const PROJECTION_ASSUMPTIONS_PATH_ENV: &str = "PROJECTION_ASSUMPTIONS_PATH";
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FirmBrandingConfig {
+    pub logo_location: String,
+    pub firm_name: String,
+    pub registered_address: Vec<String>,
+    pub regulatory_disclosure: String,
+    pub colour_theme: ColourThemeConfig
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ColourThemeConfig {
+    pub primary: String,
+    pub secondary: String,
+    pub text: String
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ContentsOverridesConfig {
+    #[serde(default)]
+    pub titles: HashMap<SectionId, String>,
+    #[serde(default)]
+    pub order: Vec<SectionId>
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct WordingTemplatesConfig {
+    pub version: String,
+    #[serde(default)]
+    pub templates: HashMap<TemplateName, String>
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TaxRulesConfig {
+    pub tax_years: BTreeMap<String, TaxYearRules>
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProjectionAssumptionsConfig {
+    pub growth_rates: RiskProfileRates,
+    pub volatilities: RiskProfileRates,
+    #[serde(default)]
+    pub model_portfolios: Vec<ModelPortfolioAssumption>,
+    pub inflation: f64,
+    pub life_expectancy: i32,
+    pub simulations: u32,
+    pub seed: u64,
+    pub withdrawal_years: i32
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Missing(String),
+    Unreadable(String),
+    Invalid(String)
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Missing(e) => write!(f, "Missing configuration: {}", e),
+            ConfigError::Unreadable(e) => write!(f, "Unreadable configuration: {}", e),
+            ConfigError::Invalid(e) => write!(f, "Invalid configuration: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+pub fn parse_firm_branding(json: &str) -> Result<FirmBranding, ConfigError> {
+    let config: FirmBrandingConfig = serde_json::from_str(json)
+        .map_err(|e| ConfigError::Invalid(format!("firm branding: {}", e)))?;
+
+    FirmBranding::try_from(config)
+        .map_err(|e| ConfigError::Invalid(format!("firm branding: {}", e)))
+}
+
+pub fn parse_contents_config(json: &str) -> Result<ContentsConfig, ConfigError> {
+    let config: ContentsOverridesConfig = serde_json::from_str(json)
+        .map_err(|e| ConfigError::Invalid(format!("contents: {}", e)))?;
+
+    ContentsConfig::new(config.titles, config.order)
+        .map_err(|e| ConfigError::Invalid(format!("contents: {}", e)))
+}
+
+/// Templates missing from the file fall back to the bundled wording.
+pub fn parse_wording_templates(json: &str) -> Result<WordingTemplates, ConfigError> {
+    let config: WordingTemplatesConfig = serde_json::from_str(json)
+        .map_err(|e| ConfigError::Invalid(format!("wording templates: {}", e)))?;
+
+    WordingTemplates::with_overrides(config.version, config.templates)
+        .map_err(|e| ConfigError::Invalid(format!("wording templates: {}", e)))
+}
+
+pub fn parse_tax_rules(json: &str) -> Result<TaxRules, ConfigError> {
+    let config: TaxRulesConfig = serde_json::from_str(json)
+        .map_err(|e| ConfigError::Invalid(format!("tax rules: {}", e)))?;
+
+    TaxRules::new(config.tax_years)
+        .map_err(|e| ConfigError::Invalid(format!("tax rules: {}", e)))
+}
+
+pub fn parse_projection_assumptions(json: &str) -> Result<ProjectionAssumptions, ConfigError> {
+    let config: ProjectionAssumptionsConfig = serde_json::from_str(json)
+        .map_err(|e| ConfigError::Invalid(format!("projection assumptions: {}", e)))?;
+
+    ProjectionAssumptions::try_from(config)
+        .map_err(|e| ConfigError::Invalid(format!("projection assumptions: {}", e)))
+}
+
+/// Loads the named version of the wording templates from the template repository.
+pub async fn load_stored_wording_templates<T>(repo: &T, version: String) -> Result<WordingTemplates, ConfigError>
+where
+    T: WordingTemplatesRepository<WordingTemplates> + Sync
+{
+    let templates = repo
+        .find_wording_templates(FindWordingTemplates { version: version.clone() })
+        .await
+        .map_err(|e| match e {
+            RepoSelectError::NotFound => ConfigError::Missing(format!("wording templates version {} not found", version)),
+            RepoSelectError::Unknown(e) => ConfigError::Invalid(format!("wording templates: {}", e))
+        })?;
+
+    info!(version = %templates.version(), "Loaded stored wording templates");
+    Ok(templates)
+}
+
+fn read_config_file(path: &str) -> Result<String, ConfigError> {
+    fs::read_to_string(path)
+        .map_err(|e| ConfigError::Unreadable(format!("{}: {}", path, e)))
+}
+
+/// Loads the report configuration from the files named in the environment.
+/// Called once at startup so a bad deployment fails before serving any request.
+pub fn load_report_config() -> Result<ReportConfig, ConfigError> {
+    let branding_path = env::var(FIRM_BRANDING_PATH_ENV)
+        .map_err(|_| ConfigError::Missing(format!("{} is not set", FIRM_BRANDING_PATH_ENV)))?;
+
+    let branding = parse_firm_branding(&read_config_file(&branding_path)?)?;
+    info!(firm_name = %branding.firm_name(), "Loaded firm branding");
+
+    let contents = match env::var(CONTENTS_CONFIG_PATH_ENV) {
+        Ok(contents_path) => parse_contents_config(&read_config_file(&contents_path)?)?,
+        Err(_) => ContentsConfig::default()
+    };
+
+    let templates = match env::var(WORDING_TEMPLATES_PATH_ENV) {
+        Ok(templates_path) => parse_wording_templates(&read_config_file(&templates_path)?)?,
+        Err(_) => WordingTemplates::default()
+    };
+    info!(version = %templates.version(), "Loaded wording templates");
+
+    let tax_rules = match env::var(TAX_RULES_PATH_ENV) {
+        Ok(tax_rules_path) => parse_tax_rules(&read_config_file(&tax_rules_path)?)?,
+        Err(_) => TaxRules::default()
+    };
+
+    let projection_assumptions = match env::var(PROJECTION_ASSUMPTIONS_PATH_ENV) {
+        Ok(projection_assumptions_path) => parse_projection_assumptions(&read_config_file(&projection_assumptions_path)?)?,
+        Err(_) => ProjectionAssumptions::default()
+    };
+
+    Ok(ReportConfig { branding, contents, templates, tax_rules, projection_assumptions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_BRANDING: &str = include_str!("../../../config/branding.example.json");
+
+    #[test]
+    fn test_example_branding_is_valid() {
+        let branding = parse_firm_branding(EXAMPLE_BRANDING).unwrap();
+        assert_eq!(branding.colour_theme().primary().value(), "#1F3864");
+    }
+
+    #[test]
+    fn test_invalid_branding_is_rejected() {
+        let invalid = EXAMPLE_BRANDING.replace("#1F3864", "navy");
+        assert!(matches!(parse_firm_branding(&invalid), Err(ConfigError::Invalid(_))));
+        assert!(matches!(parse_firm_branding("{}"), Err(ConfigError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_contents_overrides() {
+        let contents = parse_contents_config(r#"{ "titles": { "recommendations": "Our advice" }, "order": ["recommendations"] }"#).unwrap();
+        assert_eq!(contents.title(SectionId::Recommendations), "Our advice");
+        assert_eq!(contents.title(SectionId::Background), "Background");
+
+        assert!(matches!(parse_contents_config(r#"{ "order": ["executive-summary"] }"#), Err(ConfigError::Invalid(_))));
+        assert!(matches!(parse_contents_config(r#"{ "order": ["background", "background"] }"#), Err(ConfigError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_wording_template_overrides() {
+        let templates = parse_wording_templates(r#"{ "version": "2025.1", "templates": { "background-greeting": "It was good to catch up" } }"#).unwrap();
+        assert_eq!(templates.version(), "2025.1");
+        assert_eq!(templates.render(TemplateName::BackgroundGreeting, &[]), "It was good to catch up");
+
+        assert!(matches!(parse_wording_templates(r#"{ "version": "2025.1", "templates": { "product-retain": "Keep {fund}" } }"#), Err(ConfigError::Invalid(_))));
+        assert!(matches!(parse_wording_templates(r#"{ "version": "2025.1", "templates": { "executive-summary": "Hello" } }"#), Err(ConfigError::Invalid(_))));
+        assert!(matches!(parse_wording_templates(r#"{ "templates": {} }"#), Err(ConfigError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_tax_rules() {
+        assert_eq!(parse_tax_rules(include_str!("../../../config/tax_rules.json")).unwrap(), TaxRules::default());
+
+        assert!(matches!(parse_tax_rules(r#"{ "taxYears": { "2026/2027": { "capitalGains": { "annualExemptAmount": 3000 } } } }"#), Err(ConfigError::Invalid(_))));
+        assert!(matches!(parse_tax_rules(r#"{ "taxYears": {}, "version": "1" }"#), Err(ConfigError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_projection_assumptions() {
+        assert_eq!(parse_projection_assumptions(include_str!("../../../config/projection_assumptions.json")).unwrap(), ProjectionAssumptions::default());
+
+        assert!(matches!(parse_projection_assumptions(r#"{ "growthRates": { "moderate": 0.05 }, "inflation": 0.025, "lifeExpectancy": 95 }"#), Err(ConfigError::Invalid(_))));
+        assert!(matches!(parse_projection_assumptions(&include_str!("../../../config/projection_assumptions.json").replace("0.025", "2.5")), Err(ConfigError::Invalid(_))));
+    }
+}
@@ -0,0 +1,231 @@
+// driven/doc_generator/docx.rs
+use std::io::{Cursor, Write};
+
+use serde_json::Value;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use super::layout::{layout_report, Block, DocumentLayout};
+use super::DocGenError;
+
+const DEFAULT_PRIMARY_COLOUR: &str = "1F3864";
+const DEFAULT_TEXT_COLOUR: &str = "262626";
+
+/// A4 text width in twentieths of a point with one inch margins.
+const TEXT_WIDTH: usize = 9026;
+
+/// Renders the report JSON to the bytes of a .docx package.
+pub fn render_docx(instructions: &Value) -> Result<Vec<u8>, DocGenError> {
+    let layout = layout_report(instructions)?;
+
+    let parts = [
+        ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+        ("_rels/.rels", PACKAGE_RELATIONSHIPS.to_string()),
+        ("docProps/core.xml", core_properties(&layout)),
+        ("word/_rels/document.xml.rels", DOCUMENT_RELATIONSHIPS.to_string()),
+        ("word/styles.xml", styles(&layout)),
+        ("word/numbering.xml", NUMBERING.to_string()),
+        ("word/document.xml", document(&layout)),
+    ];
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for (name, content) in parts {
+        zip.start_file(name, options)
+            .map_err(|e| DocGenError::RenderError(e.to_string()))?;
+        zip.write_all(content.as_bytes())
+            .map_err(|e| DocGenError::RenderError(e.to_string()))?;
+    }
+
+    zip.finish()
+        .map(Cursor::into_inner)
+        .map_err(|e| DocGenError::RenderError(e.to_string()))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Word bookmark names may only contain letters, digits and underscores.
+fn bookmark(anchor: &str) -> String {
+    anchor.replace('-', "_")
+}
+
+fn run(text: &str, bold: bool) -> String {
+    let properties = if bold { "<w:rPr><w:b/></w:rPr>" } else { "" };
+    format!(r#"<w:r>{}<w:t xml:space="preserve">{}</w:t></w:r>"#, properties, escape(text))
+}
+
+fn paragraph(style: Option<&str>, content: &str) -> String {
+    match style {
+        Some(style) => format!(r#"<w:p><w:pPr><w:pStyle w:val="{}"/></w:pPr>{}</w:p>"#, style, content),
+        None => format!("<w:p>{}</w:p>", content)
+    }
+}
+
+fn table(header: &Option<Vec<String>>, rows: &[Vec<String>]) -> String {
+    let columns = header
+        .as_ref()
+        .map(Vec::len)
+        .into_iter()
+        .chain(rows.iter().map(Vec::len))
+        .max()
+        .unwrap_or(1)
+        .max(1);
+    let width = TEXT_WIDTH / columns;
+
+    let row = |cells: &[String], is_header: bool| {
+        let properties = if is_header { "<w:trPr><w:tblHeader/></w:trPr>" } else { "" };
+        let cells: String = (0..columns)
+            .map(|index| format!(
+                r#"<w:tc><w:tcPr><w:tcW w:w="{}" w:type="dxa"/></w:tcPr>{}</w:tc>"#,
+                width,
+                paragraph(None, &run(cells.get(index).map(String::as_str).unwrap_or_default(), is_header))
+            ))
+            .collect();
+        format!("<w:tr>{}{}</w:tr>", properties, cells)
+    };
+
+    let grid: String = (0..columns).map(|_| format!(r#"<w:gridCol w:w="{}"/>"#, width)).collect();
+    let header = header.as_ref().map(|header| row(header, true)).unwrap_or_default();
+    let body: String = rows.iter().map(|cells| row(cells, false)).collect();
+
+    // A table cannot be directly followed by another table, so it is closed with an empty paragraph
+    format!(
+        r#"<w:tbl><w:tblPr><w:tblStyle w:val="ReportTable"/><w:tblW w:w="5000" w:type="pct"/></w:tblPr><w:tblGrid>{}</w:tblGrid>{}{}</w:tbl><w:p/>"#,
+        grid, header, body
+    )
+}
+
+fn block(block: &Block, bookmark_id: &mut usize) -> String {
+    match block {
+        Block::Title(text) => paragraph(Some("Title"), &run(text, false)),
+        Block::Heading { level, text, anchor } => {
+            let style = format!("Heading{}", (*level).clamp(1, 3));
+            match anchor {
+                Some(anchor) => {
+                    *bookmark_id += 1;
+                    paragraph(Some(&style), &format!(
+                        r#"<w:bookmarkStart w:id="{id}" w:name="{name}"/>{run}<w:bookmarkEnd w:id="{id}"/>"#,
+                        id = bookmark_id,
+                        name = bookmark(anchor),
+                        run = run(text, false)
+                    ))
+                }
+                None => paragraph(Some(&style), &run(text, false))
+            }
+        }
+        Block::Paragraph(text) => paragraph(None, &run(text, false)),
+        Block::SmallPrint(text) => paragraph(Some("SmallPrint"), &run(text, false)),
+        Block::Bullets(items) => items
+            .iter()
+            .map(|item| format!(
+                r#"<w:p><w:pPr><w:pStyle w:val="ListBullet"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr>{}</w:p>"#,
+                run(item, false)
+            ))
+            .collect(),
+        Block::Table { header, rows } => table(header, rows),
+        Block::Contents(entries) => entries
+            .iter()
+            .map(|(title, anchor)| paragraph(Some("ContentsEntry"), &format!(
+                r#"<w:hyperlink w:anchor="{}" w:history="1"><w:r><w:rPr><w:rStyle w:val="Hyperlink"/></w:rPr><w:t xml:space="preserve">{}</w:t></w:r></w:hyperlink>"#,
+                bookmark(anchor),
+                escape(title)
+            )))
+            .collect(),
+        Block::PageBreak => r#"<w:p><w:r><w:br w:type="page"/></w:r></w:p>"#.to_string()
+    }
+}
+
+fn document(layout: &DocumentLayout) -> String {
+    let mut bookmark_id = 0;
+    let body: String = layout.blocks.iter().map(|b| block(b, &mut bookmark_id)).collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><w:body>{}<w:sectPr><w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1440" w:right="1440" w:bottom="1440" w:left="1440" w:header="708" w:footer="708" w:gutter="0"/></w:sectPr></w:body></w:document>"#,
+        body
+    )
+}
+
+fn styles(layout: &DocumentLayout) -> String {
+    let primary = layout.primary_colour.as_deref().unwrap_or(DEFAULT_PRIMARY_COLOUR);
+    let text = layout.text_colour.as_deref().unwrap_or(DEFAULT_TEXT_COLOUR);
+
+    let heading = |level: u8, size: u8| format!(
+        r#"<w:style w:type="paragraph" w:styleId="Heading{level}"><w:name w:val="heading {level}"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="120"/><w:outlineLvl w:val="{outline}"/></w:pPr><w:rPr><w:b/><w:color w:val="{primary}"/><w:sz w:val="{size}"/></w:rPr></w:style>"#,
+        level = level,
+        outline = level - 1,
+        primary = primary,
+        size = size
+    );
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri" w:hAnsi="Calibri" w:cs="Calibri"/><w:color w:val="{text}"/><w:sz w:val="22"/><w:lang w:val="en-GB"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:after="160" w:line="259" w:lineRule="auto"/></w:pPr></w:pPrDefault></w:docDefaults><w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/><w:qFormat/></w:style><w:style w:type="paragraph" w:styleId="Title"><w:name w:val="Title"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:spacing w:before="2400" w:after="480"/></w:pPr><w:rPr><w:color w:val="{primary}"/><w:sz w:val="56"/></w:rPr></w:style>{h1}{h2}{h3}<w:style w:type="paragraph" w:styleId="SmallPrint"><w:name w:val="Small Print"/><w:basedOn w:val="Normal"/><w:pPr><w:spacing w:after="0"/></w:pPr><w:rPr><w:sz w:val="16"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="ListBullet"><w:name w:val="List Bullet"/><w:basedOn w:val="Normal"/><w:pPr><w:spacing w:after="60"/></w:pPr></w:style><w:style w:type="paragraph" w:styleId="ContentsEntry"><w:name w:val="Contents Entry"/><w:basedOn w:val="Normal"/></w:style><w:style w:type="character" w:styleId="Hyperlink"><w:name w:val="Hyperlink"/><w:rPr><w:color w:val="{primary}"/><w:u w:val="single"/></w:rPr></w:style><w:style w:type="table" w:styleId="ReportTable"><w:name w:val="Report Table"/><w:tblPr><w:tblBorders><w:top w:val="single" w:sz="4" w:space="0" w:color="{primary}"/><w:left w:val="single" w:sz="4" w:space="0" w:color="{primary}"/><w:bottom w:val="single" w:sz="4" w:space="0" w:color="{primary}"/><w:right w:val="single" w:sz="4" w:space="0" w:color="{primary}"/><w:insideH w:val="single" w:sz="4" w:space="0" w:color="{primary}"/><w:insideV w:val="single" w:sz="4" w:space="0" w:color="{primary}"/></w:tblBorders><w:tblCellMar><w:left w:w="108" w:type="dxa"/><w:right w:w="108" w:type="dxa"/></w:tblCellMar></w:tblPr></w:style></w:styles>"#,
+        text = text,
+        primary = primary,
+        h1 = heading(1, 32),
+        h2 = heading(2, 26),
+        h3 = heading(3, 24)
+    )
+}
+
+fn core_properties(layout: &DocumentLayout) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>{}</dc:title></cp:coreProperties>"#,
+        escape(&layout.title)
+    )
+}
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/><Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/><Override PartName="/word/numbering.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml"/><Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/></Types>"#;
+
+const PACKAGE_RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/></Relationships>"#;
+
+const DOCUMENT_RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering" Target="numbering.xml"/></Relationships>"#;
+
+const NUMBERING: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:abstractNum w:abstractNumId="0"><w:multiLevelType w:val="singleLevel"/><w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="bullet"/><w:lvlText w:val="•"/><w:lvlJc w:val="left"/><w:pPr><w:ind w:left="720" w:hanging="360"/></w:pPr></w:lvl></w:abstractNum><w:num w:numId="1"><w:abstractNumId w:val="0"/></w:num></w:numbering>"#;
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+    use crate::driven::doc_generator::layout::tests::sample_document;
+
+    fn part(bytes: &[u8], name: &str) -> String {
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut content = String::new();
+        archive.by_name(name).unwrap().read_to_string(&mut content).unwrap();
+        content
+    }
+
+    #[test]
+    fn test_render_docx_package() {
+        let bytes = render_docx(&sample_document()).unwrap();
+
+        let archive = zip::ZipArchive::new(Cursor::new(bytes.as_slice())).unwrap();
+        for name in ["[Content_Types].xml", "_rels/.rels", "word/document.xml", "word/styles.xml", "word/numbering.xml"] {
+            assert!(archive.file_names().any(|file| file == name), "{} missing", name);
+        }
+
+        let document = part(&bytes, "word/document.xml");
+        assert!(document.contains(r#"<w:pStyle w:val="Title"/></w:pPr><w:r><w:t xml:space="preserve">Annual Review Report</w:t>"#));
+        assert!(document.contains(r#"<w:bookmarkStart w:id="3" w:name="recommendations"/>"#));
+        assert!(document.contains(r#"<w:hyperlink w:anchor="recommendations" w:history="1">"#));
+        assert!(document.contains(r#"<w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t xml:space="preserve">Retired</w:t>"#));
+        assert!(document.contains("<w:tbl>"));
+        assert!(document.contains(r#"<w:br w:type="page"/>"#));
+        assert!(document.contains("John &amp; Jane Smith"));
+
+        assert!(part(&bytes, "word/styles.xml").contains(r#"<w:color w:val="1F3864"/>"#));
+    }
+}
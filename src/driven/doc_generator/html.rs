@@ -0,0 +1,121 @@
+// driven/doc_generator/html.rs
+use serde_json::Value;
+
+use super::layout::{layout_report, Block, DocumentLayout};
+use super::DocGenError;
+
+const DEFAULT_PRIMARY_COLOUR: &str = "1F3864";
+const DEFAULT_TEXT_COLOUR: &str = "262626";
+
+/// Renders the report JSON to a standalone HTML page for the client portal.
+pub fn render_html(instructions: &Value) -> Result<String, DocGenError> {
+    Ok(html(&layout_report(instructions)?))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn block(block: &Block) -> String {
+    match block {
+        Block::Title(text) => format!("<h1 class=\"title\">{}</h1>\n", escape(text)),
+        Block::Heading { level, text, anchor } => {
+            // The title is the page's h1, so section headings start at h2
+            let tag = format!("h{}", (*level).clamp(1, 5) + 1);
+            match anchor {
+                Some(anchor) => format!("<{tag} id=\"{}\">{}</{tag}>\n", escape(anchor), escape(text), tag = tag),
+                None => format!("<{tag}>{}</{tag}>\n", escape(text), tag = tag)
+            }
+        }
+        Block::Paragraph(text) => format!("<p>{}</p>\n", escape(text)),
+        Block::SmallPrint(text) => format!("<p class=\"small-print\">{}</p>\n", escape(text)),
+        Block::Bullets(items) => format!(
+            "<ul>\n{}</ul>\n",
+            items.iter().map(|item| format!("<li>{}</li>\n", escape(item))).collect::<String>()
+        ),
+        Block::Table { header, rows } => {
+            let header = header
+                .as_ref()
+                .map(|header| format!(
+                    "<thead><tr>{}</tr></thead>\n",
+                    header.iter().map(|cell| format!("<th>{}</th>", escape(cell))).collect::<String>()
+                ))
+                .unwrap_or_default();
+            let body: String = rows
+                .iter()
+                .map(|row| format!(
+                    "<tr>{}</tr>\n",
+                    row.iter().map(|cell| format!("<td>{}</td>", escape(cell))).collect::<String>()
+                ))
+                .collect();
+            format!("<table>\n{}<tbody>\n{}</tbody>\n</table>\n", header, body)
+        }
+        Block::Contents(entries) => format!(
+            "<nav class=\"contents\">\n<ol>\n{}</ol>\n</nav>\n",
+            entries
+                .iter()
+                .map(|(title, anchor)| format!("<li><a href=\"#{}\">{}</a></li>\n", escape(anchor), escape(title)))
+                .collect::<String>()
+        ),
+        Block::PageBreak => "<div class=\"page-break\"></div>\n".to_string()
+    }
+}
+
+fn html(layout: &DocumentLayout) -> String {
+    let primary = layout.primary_colour.as_deref().unwrap_or(DEFAULT_PRIMARY_COLOUR);
+    let text = layout.text_colour.as_deref().unwrap_or(DEFAULT_TEXT_COLOUR);
+    let body: String = layout.blocks.iter().map(block).collect();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en-GB">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<style>
+body {{ font-family: Calibri, Arial, sans-serif; color: #{text}; max-width: 50rem; margin: 0 auto; padding: 2rem; line-height: 1.5; }}
+h1, h2, h3, h4, h5, h6 {{ color: #{primary}; }}
+.title {{ font-size: 2.5rem; margin-top: 6rem; }}
+.small-print {{ font-size: 0.75rem; margin: 0; }}
+table {{ border-collapse: collapse; width: 100%; margin-bottom: 1rem; }}
+th, td {{ border: 1px solid #{primary}; padding: 0.25rem 0.5rem; text-align: left; vertical-align: top; }}
+.contents a {{ color: #{primary}; }}
+.page-break {{ break-after: page; }}
+</style>
+</head>
+<body>
+{body}</body>
+</html>
+"#,
+        title = escape(&layout.title),
+        text = text,
+        primary = primary,
+        body = body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driven::doc_generator::layout::tests::sample_document;
+
+    #[test]
+    fn test_render_html() {
+        let html = render_html(&sample_document()).unwrap();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Annual Review Report</title>"));
+        assert!(html.contains("<h1 class=\"title\">Annual Review Report</h1>"));
+        assert!(html.contains("<li><a href=\"#recommendations\">Our advice</a></li>"));
+        assert!(html.contains("<h2 id=\"recommendations\">Our advice</h2>"));
+        assert!(html.contains("<li>Retired</li>"));
+        assert!(html.contains("<td>Will</td>"));
+        assert!(html.contains("Prepared for John &amp; Jane Smith"));
+        assert!(html.contains("color: #1F3864"));
+    }
+}
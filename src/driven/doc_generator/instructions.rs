@@ -0,0 +1,450 @@
+// driven/doc_generator/instructions.rs
+//! The document instructions contract: what the service sends to document generators.
+//!
+//! These types are kept apart from the domain structs so that renaming a domain field cannot
+//! change what generators receive. Any change here is a contract change: bump
+//! `SCHEMA_VERSION` (major for breaking changes) and regenerate the schema and example with
+//! `UPDATE_GOLDEN=1 cargo test instructions`.
+
+use schemars::{generate::SchemaSettings, JsonSchema};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Version of the contract, sent as `schemaVersion`. Generators should reject a major version
+/// they do not know.
+pub const SCHEMA_VERSION: &str = "4.0";
+
+/// The kind of report, which generators may use to pick a template.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ReportKind {
+    CoupleAnnualReview,
+    IndividualAnnualReview,
+    CoupleNew
+}
+
+/// Everything a generator needs to produce one report document.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DocumentInstructions {
+    /// `SCHEMA_VERSION` of the service that produced the instructions
+    pub schema_version: String,
+    pub id: String,
+    /// Names of the clients the report is for
+    pub client: String,
+    /// The date the report is prepared as at, as `YYYY-MM-DD`
+    pub as_at: String,
+    pub report_kind: ReportKind,
+    /// Sections in the order they appear in the document
+    pub sections: Vec<SectionInstructions>,
+}
+
+/// One section of the document, tagged by `kind`.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum SectionInstructions {
+    Cover(CoverInstructions),
+    Contents(ContentsInstructions),
+    Background(BackgroundInstructions),
+    CurrentCircumstances(CurrentCircumstancesInstructions),
+    Recommendations(RecommendationsInstructions),
+    ContactDetails(ContactDetailsInstructions),
+}
+
+/// The title page.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CoverInstructions {
+    pub branding: BrandingInstructions,
+    pub report_title: String,
+    pub client_names_paragraph: String,
+    pub adviser_name_paragraph: String,
+    pub adviser_qualifications_paragraph: Option<String>,
+}
+
+/// The firm's identity and colours.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BrandingInstructions {
+    /// `s3://bucket/key` of the logo image
+    pub logo_location: String,
+    pub firm_name: String,
+    pub registered_address: Vec<String>,
+    pub regulatory_disclosure: String,
+    pub colour_theme: ColourThemeInstructions,
+}
+
+/// Colours as `#RRGGBB`.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ColourThemeInstructions {
+    pub primary: String,
+    pub secondary: String,
+    pub text: String,
+}
+
+/// The contents list, linking to the sections that follow.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ContentsInstructions {
+    pub entries: Vec<ContentsEntryInstructions>,
+}
+
+/// One line of the contents.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ContentsEntryInstructions {
+    pub id: String,
+    pub title: String,
+    /// Matches the `anchor` of the section it links to
+    pub anchor: String,
+}
+
+/// Why and how the meeting took place.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BackgroundInstructions {
+    pub anchor: String,
+    pub title: String,
+    pub paragraphs: Vec<String>,
+}
+
+/// Bullet points under a label, such as a client's name.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct LabelledBulletsInstructions {
+    pub label: String,
+    pub bullets: Vec<String>,
+}
+
+/// Changes since the last review, objectives and attitude to risk.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CurrentCircumstancesInstructions {
+    pub anchor: String,
+    pub title: String,
+    pub first_paragraph: String,
+    pub circumstances_introduction: Option<String>,
+    pub circumstances: Vec<String>,
+    pub change_in_objectives_paragraph: String,
+    pub objectives_introduction: String,
+    /// Each client's objectives, then shared objectives
+    pub objectives: Vec<LabelledBulletsInstructions>,
+    pub risk_review_paragraph: String,
+    /// How each objective relates to the risk profile, in the same order as `objectives`
+    pub objective_risk_profiles: Vec<LabelledBulletsInstructions>,
+    /// Present when any income objective has been projected
+    pub income_projections_introduction: Option<String>,
+    /// Whether each income objective is on track, in the same order as `objectives`
+    pub income_projections: Vec<LabelledBulletsInstructions>,
+    pub if_circumstances_have_changed_paragraph: String,
+    pub previous_review_paragraph: String,
+}
+
+/// Product and other advice, grouped by client.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RecommendationsInstructions {
+    pub anchor: String,
+    pub title: String,
+    pub introductory_paragraph: String,
+    /// Each client's products, with jointly held products last
+    pub products: Vec<ClientProductsInstructions>,
+    /// Each client's other advice areas, with joint advice last
+    pub other_advice: Vec<ClientAdviceInstructions>,
+}
+
+/// The products recommended for one client, or jointly.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ClientProductsInstructions {
+    pub client: String,
+    pub products: Vec<ProductInstructions>,
+}
+
+/// A recommendation for a product the client holds, or a new one, tagged by `kind`.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ProductInstructions {
+    Existing(ExistingProductInstructions),
+    New(NewProductInstructions),
+}
+
+/// An account number together with the provider scheme it belongs to.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AccountNumberInstructions {
+    /// Numbering scheme, such as `transact` or `abrdn`
+    pub scheme: String,
+    pub number: String,
+}
+
+/// A product the client already holds.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ExistingProductInstructions {
+    pub product_title: String,
+    pub platform_number: Option<AccountNumberInstructions>,
+    pub account_or_reference_number: AccountNumberInstructions,
+    pub retention_sentence: String,
+    pub rationale: String,
+    pub actions: Vec<ProductActionInstructions>,
+}
+
+/// A product to be opened.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct NewProductInstructions {
+    pub product_title: String,
+    pub platform_number: Option<AccountNumberInstructions>,
+    pub initial_sentence: String,
+    pub rationale: String,
+}
+
+/// Something to be done with an existing product.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProductActionInstructions {
+    pub title: String,
+    pub paragraph: String,
+}
+
+/// Advice outside products for one client, or jointly.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ClientAdviceInstructions {
+    pub client: String,
+    pub advice: Vec<AdviceInstructions>,
+}
+
+/// Advice on one area, such as wills or an emergency fund.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AdviceInstructions {
+    pub title: String,
+    pub paragraph: String,
+    /// Figures supporting the paragraph, shown after it
+    pub table: Option<TableInstructions>,
+}
+
+/// A table with a heading row.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TableInstructions {
+    pub header: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// How to reach the adviser.
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ContactDetailsInstructions {
+    pub anchor: String,
+    pub title: String,
+    pub introduction_paragraph: String,
+    pub adviser_name: String,
+    pub adviser_qualifications: Option<String>,
+    pub fca_reference_paragraph: String,
+    pub phone: String,
+    pub email: String,
+    pub office: String,
+    /// `s3://bucket/key` of the adviser's signature image
+    pub signature_image_location: Option<String>,
+}
+
+impl DocumentInstructions {
+    /// The JSON Schema describing the contract, published as
+    /// `schema/document_instructions.schema.json`.
+    /// The schema describes what the service sends, so optional fields are required and may
+    /// be `null`.
+    pub fn json_schema() -> Value {
+        let mut schema = SchemaSettings::draft2020_12()
+            .for_serialize()
+            .into_generator()
+            .into_root_schema_for::<DocumentInstructions>();
+        schema.insert("$id".to_string(), Value::String("https://gcwm.co.uk/schemas/document-instructions.schema.json".to_string()));
+        schema.insert("title".to_string(), Value::String(format!("Document instructions {}", SCHEMA_VERSION)));
+        schema.to_value()
+    }
+}
+
+/// Checks that generators built for this service can read instructions with the given version.
+pub fn is_supported_version(version: &str) -> bool {
+    let major = |version: &str| version.split('.').next().map(str::to_string);
+    major(version) == major(SCHEMA_VERSION)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// A document exercising every section kind and optional field.
+    pub(crate) fn sample_instructions() -> DocumentInstructions {
+        DocumentInstructions {
+            schema_version: SCHEMA_VERSION.to_string(),
+            id: "a1b2c3".to_string(),
+            client: "John Smith & Jane Smith".to_string(),
+            as_at: "2026-06-01".to_string(),
+            report_kind: ReportKind::CoupleAnnualReview,
+            sections: vec![
+                SectionInstructions::Cover(CoverInstructions {
+                    branding: BrandingInstructions {
+                        logo_location: "s3://gcwm-branding/logo.png".to_string(),
+                        firm_name: "Example Wealth Management Ltd".to_string(),
+                        registered_address: vec!["1 High Street".to_string(), "London".to_string()],
+                        regulatory_disclosure: "Authorised and regulated by the Financial Conduct Authority.".to_string(),
+                        colour_theme: ColourThemeInstructions {
+                            primary: "#1F3864".to_string(),
+                            secondary: "#8EAADB".to_string(),
+                            text: "#262626".to_string()
+                        }
+                    },
+                    report_title: "Annual Review Report".to_string(),
+                    client_names_paragraph: "Prepared for John & Jane Smith".to_string(),
+                    adviser_name_paragraph: "Prepared by Alex Jones".to_string(),
+                    adviser_qualifications_paragraph: Some("Chartered Financial Planner".to_string())
+                }),
+                SectionInstructions::Contents(ContentsInstructions {
+                    entries: vec![
+                        ContentsEntryInstructions { id: "background".to_string(), title: "Background".to_string(), anchor: "background".to_string() },
+                        ContentsEntryInstructions { id: "current-circumstances".to_string(), title: "Current circumstances and objectives".to_string(), anchor: "current-circumstances".to_string() },
+                        ContentsEntryInstructions { id: "recommendations".to_string(), title: "Our advice".to_string(), anchor: "recommendations".to_string() },
+                        ContentsEntryInstructions { id: "contact-details".to_string(), title: "Contact details".to_string(), anchor: "contact-details".to_string() }
+                    ]
+                }),
+                SectionInstructions::Background(BackgroundInstructions {
+                    anchor: "background".to_string(),
+                    title: "Background".to_string(),
+                    paragraphs: vec![
+                        "It was lovely to see you at the office.".to_string(),
+                        "The remainder of this report will address your products.".to_string()
+                    ]
+                }),
+                SectionInstructions::CurrentCircumstances(CurrentCircumstancesInstructions {
+                    anchor: "current-circumstances".to_string(),
+                    title: "Current circumstances and objectives".to_string(),
+                    first_paragraph: "Since our last meeting your circumstances have changed.".to_string(),
+                    circumstances_introduction: Some("We identified the following changes:".to_string()),
+                    circumstances: vec!["Retired".to_string(), "Moved house".to_string()],
+                    change_in_objectives_paragraph: "Your objectives remain the same.".to_string(),
+                    objectives_introduction: "Your objectives are:".to_string(),
+                    objectives: vec![
+                        LabelledBulletsInstructions { label: "John".to_string(), bullets: vec!["Retire at 60".to_string()] },
+                        LabelledBulletsInstructions { label: "Shared".to_string(), bullets: vec!["Help the children buy homes".to_string()] }
+                    ],
+                    risk_review_paragraph: "Your attitude to risk has not changed.".to_string(),
+                    objective_risk_profiles: vec![
+                        LabelledBulletsInstructions { label: "John".to_string(), bullets: vec!["Retire at 60: balanced".to_string()] }
+                    ],
+                    income_projections_introduction: Some("I have projected whether your products can provide your income.".to_string()),
+                    income_projections: vec![
+                        LabelledBulletsInstructions { label: "John".to_string(), bullets: vec!["Your income objective is on track.".to_string()] }
+                    ],
+                    if_circumstances_have_changed_paragraph: "Please tell us if anything changes.".to_string(),
+                    previous_review_paragraph: "We last reviewed your plans on 6 April 2024.".to_string()
+                }),
+                SectionInstructions::Recommendations(RecommendationsInstructions {
+                    anchor: "recommendations".to_string(),
+                    title: "Our advice".to_string(),
+                    introductory_paragraph: "This section will present my recommendations.".to_string(),
+                    products: vec![ClientProductsInstructions {
+                        client: "John Smith".to_string(),
+                        products: vec![
+                            ProductInstructions::Existing(ExistingProductInstructions {
+                                product_title: "Transact - ISA".to_string(),
+                                platform_number: Some(AccountNumberInstructions { scheme: "transact".to_string(), number: "IH00123456".to_string() }),
+                                account_or_reference_number: AccountNumberInstructions { scheme: "transact".to_string(), number: "AB123".to_string() },
+                                retention_sentence: "I recommend you continue to retain the Transact ISA.".to_string(),
+                                rationale: "It remains suitable.".to_string(),
+                                actions: vec![ProductActionInstructions { title: "Withdrawal".to_string(), paragraph: "Withdraw £5,000.".to_string() }]
+                            }),
+                            ProductInstructions::New(NewProductInstructions {
+                                product_title: "Transact - GIA".to_string(),
+                                platform_number: None,
+                                initial_sentence: "I recommend you open a general investment account.".to_string(),
+                                rationale: "You have used your ISA allowance.".to_string()
+                            })
+                        ]
+                    }],
+                    other_advice: vec![ClientAdviceInstructions {
+                        client: "Joint".to_string(),
+                        advice: vec![
+                            AdviceInstructions { title: "Will".to_string(), paragraph: "You should review your wills.".to_string(), table: None },
+                            AdviceInstructions {
+                                title: "Pension Annual Allowance".to_string(),
+                                paragraph: "Unused allowance would be carried forward.".to_string(),
+                                table: Some(TableInstructions {
+                                    header: vec!["Tax year".to_string(), "Carried forward".to_string()],
+                                    rows: vec![vec!["2023/2024".to_string(), "£10,000.00".to_string()]]
+                                })
+                            }
+                        ]
+                    }]
+                }),
+                SectionInstructions::ContactDetails(ContactDetailsInstructions {
+                    anchor: "contact-details".to_string(),
+                    title: "Contact details".to_string(),
+                    introduction_paragraph: "Please contact me with any questions.".to_string(),
+                    adviser_name: "Alex Jones".to_string(),
+                    adviser_qualifications: None,
+                    fca_reference_paragraph: "FCA reference number: ABC12345".to_string(),
+                    phone: "020 7946 0000".to_string(),
+                    email: "alex.jones@example.com".to_string(),
+                    office: "London".to_string(),
+                    signature_image_location: None
+                })
+            ]
+        }
+    }
+
+    /// Compares against a committed file, or rewrites it when `UPDATE_GOLDEN` is set.
+    pub(crate) fn assert_golden(file: &str, actual: &Value) {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("schema").join(file);
+        let actual = format!("{}\n", serde_json::to_string_pretty(actual).unwrap());
+
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(&path, &actual).unwrap();
+        }
+
+        let expected = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            expected == actual,
+            "{} is out of date. The contract has changed: review the change, bump its version if needed and rerun with UPDATE_GOLDEN=1",
+            path.display()
+        );
+    }
+
+    #[test]
+    fn test_schema_is_up_to_date() {
+        assert_golden("document_instructions.schema.json", &DocumentInstructions::json_schema());
+    }
+
+    #[test]
+    fn test_example_is_up_to_date() {
+        assert_golden("document_instructions.example.json", &serde_json::to_value(sample_instructions()).unwrap());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let json = serde_json::to_value(sample_instructions()).unwrap();
+        assert_eq!(json["sections"][0]["kind"], "cover");
+        assert_eq!(json["sections"][4]["products"][0]["products"][1]["kind"], "new");
+        assert_eq!(serde_json::from_value::<DocumentInstructions>(json).unwrap(), sample_instructions());
+    }
+
+    #[test]
+    fn test_unknown_fields_are_rejected() {
+        let mut json = serde_json::to_value(sample_instructions()).unwrap();
+        json["sections"][2]["subtitle"] = json!("Not in the contract");
+        assert!(serde_json::from_value::<DocumentInstructions>(json).is_err());
+    }
+
+    #[test]
+    fn test_supported_version() {
+        assert!(is_supported_version(SCHEMA_VERSION));
+        assert!(is_supported_version("4.7"));
+        assert!(!is_supported_version("3.0"));
+        assert!(!is_supported_version("5.0"));
+    }
+}
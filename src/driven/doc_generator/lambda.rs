@@ -1,8 +1,8 @@
 // driven/doc_generator/aws.rs
-use super::{DocumentGenerator, DocGenError};
+use super::{DocumentGenerator, DocGenError, OutputFormat};
 use async_trait::async_trait;
 use base64::prelude::*;
-use tracing::error;
+use tracing::{debug, error};
 
 // 1) Bring in the new defaults loader (instead of load_from_env())
 use aws_config::{defaults, meta::region::RegionProviderChain};
@@ -37,7 +37,12 @@ impl AwsLambdaDocGenerator {
 
 #[async_trait]
 impl DocumentGenerator for AwsLambdaDocGenerator {
-    async fn generate(&self, instructions: &Value) -> Result<String, DocGenError> {
+    async fn generate(&self, instructions: &Value, format: OutputFormat) -> Result<String, DocGenError> {
+        // The docx_generator Lambda only produces Word documents
+        if format != OutputFormat::Docx {
+            return Err(DocGenError::UnsupportedFormat(format));
+        }
+
         // 3) Serialize your JSON into bytes
         let bytes = serde_json::to_vec(instructions)
             .map_err(|e| DocGenError::InvocationError(e.to_string()))?;
@@ -58,12 +63,27 @@ impl DocumentGenerator for AwsLambdaDocGenerator {
             .payload(Blob::new(bytes))                         // Blob from aws-smithy-types :contentReference[oaicite:2]{index=2}
             .send()
             .await
-            .map_err(|e| DocGenError::InvocationError(e.to_string()))?;
+            .map_err(|e| match e.as_service_error() {
+                Some(service_error) if service_error.is_too_many_requests_exception() => DocGenError::Throttled(e.to_string()),
+                _ => DocGenError::InvocationError(e.to_string())
+            })?;
+
+        let tail = response.log_result
+            .as_ref()
+            .map(|tail| String::from_utf8_lossy(&BASE64_STANDARD.decode(tail).unwrap_or_default()).into_owned())
+            .unwrap_or_default();
 
-        if let Some(tail) = response.log_result {
-            let decoded = BASE64_STANDARD.decode(&tail).unwrap_or_default();
-            error!("▼ docx-generator stderr ▼\n{}", String::from_utf8_lossy(&decoded));
+        // An unhandled error in the generator still returns 200, with the error in the payload
+        if let Some(function_error) = response.function_error.as_deref() {
+            error!("▼ docx-generator stderr ▼\n{}", tail);
+            let message = response.payload
+                .as_ref()
+                .and_then(|blob| serde_json::from_slice::<Value>(blob.as_ref()).ok())
+                .and_then(|json| json.get("errorMessage").and_then(Value::as_str).map(str::to_owned))
+                .unwrap_or_default();
+            return Err(DocGenError::FunctionError(format!("{}: {}", function_error, message)));
         }
+        debug!("▼ docx-generator stderr ▼\n{}", tail);
 
         // 5) Pull out the response payload and parse it
         let blob = response.payload
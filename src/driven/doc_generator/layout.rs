@@ -0,0 +1,280 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::instructions::{
+    is_supported_version, ContactDetailsInstructions, CoverInstructions, CurrentCircumstancesInstructions,
+    DocumentInstructions, LabelledBulletsInstructions, ProductInstructions, RecommendationsInstructions,
+    SectionInstructions, SCHEMA_VERSION
+};
+use super::DocGenError;
+
+/// A format independent view of a report, built once from the document instructions and shared by every
+/// renderer so each output format presents the same content in the same order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentLayout {
+    pub title: String,
+    pub primary_colour: Option<String>,
+    pub text_colour: Option<String>,
+    pub blocks: Vec<Block>
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    Title(String),
+    Heading { level: u8, text: String, anchor: Option<String> },
+    Paragraph(String),
+    SmallPrint(String),
+    Bullets(Vec<String>),
+    Table { header: Option<Vec<String>>, rows: Vec<Vec<String>> },
+    /// Contents entries as (title, anchor) pairs linking to section headings.
+    Contents(Vec<(String, String)>),
+    PageBreak
+}
+
+/// Lays out versioned document instructions as blocks. The cover comes first on its own page,
+/// followed by the contents and then each section under its heading.
+pub fn layout_report(instructions: &Value) -> Result<DocumentLayout, DocGenError> {
+    let version = instructions
+        .get("schemaVersion")
+        .and_then(Value::as_str)
+        .ok_or_else(|| DocGenError::RenderError("instructions do not have a schema version".to_string()))?;
+    if !is_supported_version(version) {
+        return Err(DocGenError::RenderError(format!(
+            "instructions schema version {} is not supported, expected {}",
+            version, SCHEMA_VERSION
+        )));
+    }
+
+    let instructions = DocumentInstructions::deserialize(instructions)
+        .map_err(|e| DocGenError::RenderError(format!("invalid document instructions: {}", e)))?;
+
+    let mut layout = DocumentLayout {
+        title: "Report".to_string(),
+        primary_colour: None,
+        text_colour: None,
+        blocks: Vec::new()
+    };
+
+    for section in &instructions.sections {
+        match section {
+            SectionInstructions::Cover(cover) => layout_cover(cover, &mut layout),
+            SectionInstructions::Contents(contents) => {
+                layout.blocks.push(Block::Heading { level: 1, text: "Contents".to_string(), anchor: None });
+                layout.blocks.push(Block::Contents(
+                    contents.entries.iter().map(|entry| (entry.title.clone(), entry.anchor.clone())).collect()
+                ));
+                layout.blocks.push(Block::PageBreak);
+            }
+            SectionInstructions::Background(background) => {
+                heading(&background.title, &background.anchor, &mut layout.blocks);
+                for paragraph in &background.paragraphs {
+                    paragraphs(paragraph, &mut layout.blocks);
+                }
+            }
+            SectionInstructions::CurrentCircumstances(section) => layout_current_circumstances(section, &mut layout.blocks),
+            SectionInstructions::Recommendations(section) => layout_recommendations(section, &mut layout.blocks),
+            SectionInstructions::ContactDetails(section) => layout_contact_details(section, &mut layout.blocks)
+        }
+    }
+
+    Ok(layout)
+}
+
+fn layout_cover(cover: &CoverInstructions, layout: &mut DocumentLayout) {
+    let branding = &cover.branding;
+    layout.primary_colour = Some(branding.colour_theme.primary.trim_start_matches('#').to_string());
+    layout.text_colour = Some(branding.colour_theme.text.trim_start_matches('#').to_string());
+
+    layout.title = cover.report_title.clone();
+    layout.blocks.push(Block::Title(cover.report_title.clone()));
+
+    paragraphs(&cover.client_names_paragraph, &mut layout.blocks);
+    paragraphs(&cover.adviser_name_paragraph, &mut layout.blocks);
+    if let Some(qualifications) = &cover.adviser_qualifications_paragraph {
+        paragraphs(qualifications, &mut layout.blocks);
+    }
+
+    layout.blocks.push(Block::Paragraph(branding.firm_name.clone()));
+    layout.blocks.extend(branding.registered_address.iter().map(|line| Block::SmallPrint(line.clone())));
+    layout.blocks.push(Block::SmallPrint(branding.regulatory_disclosure.clone()));
+
+    layout.blocks.push(Block::PageBreak);
+}
+
+fn layout_current_circumstances(section: &CurrentCircumstancesInstructions, blocks: &mut Vec<Block>) {
+    heading(&section.title, &section.anchor, blocks);
+    paragraphs(&section.first_paragraph, blocks);
+    if let Some(introduction) = &section.circumstances_introduction {
+        paragraphs(introduction, blocks);
+    }
+    bullets(&section.circumstances, blocks);
+
+    paragraphs(&section.change_in_objectives_paragraph, blocks);
+    paragraphs(&section.objectives_introduction, blocks);
+    labelled_bullets(&section.objectives, blocks);
+
+    paragraphs(&section.risk_review_paragraph, blocks);
+    labelled_bullets(&section.objective_risk_profiles, blocks);
+
+    if let Some(introduction) = &section.income_projections_introduction {
+        paragraphs(introduction, blocks);
+    }
+    labelled_bullets(&section.income_projections, blocks);
+
+    paragraphs(&section.if_circumstances_have_changed_paragraph, blocks);
+    paragraphs(&section.previous_review_paragraph, blocks);
+}
+
+fn layout_recommendations(section: &RecommendationsInstructions, blocks: &mut Vec<Block>) {
+    heading(&section.title, &section.anchor, blocks);
+    paragraphs(&section.introductory_paragraph, blocks);
+
+    for client in &section.products {
+        blocks.push(Block::Heading { level: 2, text: client.client.clone(), anchor: None });
+        for product in &client.products {
+            match product {
+                ProductInstructions::Existing(product) => {
+                    blocks.push(Block::Heading { level: 3, text: product.product_title.clone(), anchor: None });
+                    if let Some(platform_number) = &product.platform_number {
+                        blocks.push(Block::Paragraph(format!("Platform number: {}", platform_number.number)));
+                    }
+                    blocks.push(Block::Paragraph(format!("Account or reference number: {}", product.account_or_reference_number.number)));
+                    paragraphs(&product.retention_sentence, blocks);
+                    paragraphs(&product.rationale, blocks);
+                    for action in &product.actions {
+                        blocks.push(Block::Heading { level: 4, text: action.title.clone(), anchor: None });
+                        paragraphs(&action.paragraph, blocks);
+                    }
+                }
+                ProductInstructions::New(product) => {
+                    blocks.push(Block::Heading { level: 3, text: product.product_title.clone(), anchor: None });
+                    if let Some(platform_number) = &product.platform_number {
+                        blocks.push(Block::Paragraph(format!("Platform number: {}", platform_number.number)));
+                    }
+                    paragraphs(&product.initial_sentence, blocks);
+                    paragraphs(&product.rationale, blocks);
+                }
+            }
+        }
+    }
+
+    for client in section.other_advice.iter().filter(|client| !client.advice.is_empty()) {
+        blocks.push(Block::Heading { level: 2, text: client.client.clone(), anchor: None });
+        blocks.push(Block::Table {
+            header: None,
+            rows: client.advice.iter().map(|advice| vec![advice.title.clone(), advice.paragraph.clone()]).collect()
+        });
+        for advice in &client.advice {
+            if let Some(table) = &advice.table {
+                blocks.push(Block::Heading { level: 3, text: advice.title.clone(), anchor: None });
+                blocks.push(Block::Table { header: Some(table.header.clone()), rows: table.rows.clone() });
+            }
+        }
+    }
+}
+
+fn layout_contact_details(section: &ContactDetailsInstructions, blocks: &mut Vec<Block>) {
+    heading(&section.title, &section.anchor, blocks);
+    paragraphs(&section.introduction_paragraph, blocks);
+    blocks.push(Block::Paragraph(section.adviser_name.clone()));
+    if let Some(qualifications) = &section.adviser_qualifications {
+        blocks.push(Block::Paragraph(qualifications.clone()));
+    }
+    paragraphs(&section.fca_reference_paragraph, blocks);
+    blocks.push(Block::Table {
+        header: None,
+        rows: vec![
+            vec!["Phone".to_string(), section.phone.clone()],
+            vec!["Email".to_string(), section.email.clone()],
+            vec!["Office".to_string(), section.office.clone()]
+        ]
+    });
+}
+
+fn heading(title: &str, anchor: &str, blocks: &mut Vec<Block>) {
+    blocks.push(Block::Heading { level: 1, text: title.to_string(), anchor: Some(anchor.to_string()) });
+}
+
+/// Wording templates may span several lines, each of which is its own paragraph.
+fn paragraphs(text: &str, blocks: &mut Vec<Block>) {
+    blocks.extend(text
+        .split('\n')
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Block::Paragraph(line.to_string()))
+    );
+}
+
+fn bullets(items: &[String], blocks: &mut Vec<Block>) {
+    if !items.is_empty() {
+        blocks.push(Block::Bullets(items.to_vec()));
+    }
+}
+
+fn labelled_bullets(groups: &[LabelledBulletsInstructions], blocks: &mut Vec<Block>) {
+    for group in groups {
+        blocks.push(Block::Heading { level: 2, text: group.label.clone(), anchor: None });
+        bullets(&group.bullets, blocks);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::driven::doc_generator::instructions::tests::sample_instructions;
+    use serde_json::json;
+
+    pub(crate) fn sample_document() -> Value {
+        serde_json::to_value(sample_instructions()).unwrap()
+    }
+
+    #[test]
+    fn test_layout_report() {
+        let layout = layout_report(&sample_document()).unwrap();
+
+        assert_eq!(layout.title, "Annual Review Report");
+        assert_eq!(layout.primary_colour.as_deref(), Some("1F3864"));
+        assert_eq!(layout.blocks[0], Block::Title("Annual Review Report".to_string()));
+        assert!(layout.blocks.contains(&Block::Contents(vec![
+            ("Background".to_string(), "background".to_string()),
+            ("Current circumstances and objectives".to_string(), "current-circumstances".to_string()),
+            ("Our advice".to_string(), "recommendations".to_string()),
+            ("Contact details".to_string(), "contact-details".to_string())
+        ])));
+        assert!(layout.blocks.contains(&Block::Heading { level: 1, text: "Our advice".to_string(), anchor: Some("recommendations".to_string()) }));
+        assert!(layout.blocks.contains(&Block::Heading { level: 2, text: "John Smith".to_string(), anchor: None }));
+        assert!(layout.blocks.contains(&Block::Heading { level: 3, text: "Transact - ISA".to_string(), anchor: None }));
+        assert!(layout.blocks.contains(&Block::Paragraph("Account or reference number: AB123".to_string())));
+        assert!(layout.blocks.contains(&Block::Bullets(vec!["Retired".to_string(), "Moved house".to_string()])));
+        assert!(layout.blocks.contains(&Block::Table {
+            header: None,
+            rows: vec![
+                vec!["Will".to_string(), "You should review your wills.".to_string()],
+                vec!["Pension Annual Allowance".to_string(), "Unused allowance would be carried forward.".to_string()]
+            ]
+        }));
+        assert!(layout.blocks.contains(&Block::Table {
+            header: Some(vec!["Tax year".to_string(), "Carried forward".to_string()]),
+            rows: vec![vec!["2023/2024".to_string(), "£10,000.00".to_string()]]
+        }));
+        assert_eq!(layout.blocks.iter().filter(|block| **block == Block::PageBreak).count(), 2);
+    }
+
+    #[test]
+    fn test_layout_rejects_other_json() {
+        assert!(matches!(layout_report(&json!({ "payload": "x" })), Err(DocGenError::RenderError(_))));
+
+        let mut unknown_section = sample_document();
+        unknown_section["sections"][2]["kind"] = json!("executiveSummary");
+        assert!(matches!(layout_report(&unknown_section), Err(DocGenError::RenderError(_))));
+    }
+
+    #[test]
+    fn test_layout_rejects_unsupported_version() {
+        let mut document = sample_document();
+        document["schemaVersion"] = json!("5.0");
+        match layout_report(&document) {
+            Err(DocGenError::RenderError(message)) => assert!(message.contains("5.0")),
+            other => panic!("unexpected result {:?}", other)
+        }
+    }
+}
@@ -0,0 +1,119 @@
+// driven/doc_generator/local.rs
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde_json::Value;
+use tracing::info;
+
+use crate::driven::storage::{DocumentStorage, ObjectName, ObjectNaming};
+
+use super::{docx::render_docx, html::render_html, markdown::render_markdown, pdf::render_pdf, DocGenError, DocumentGenerator, OutputFormat};
+
+/// Renders reports in process and puts them in document storage, returning a download link
+/// that expires after `link_expiry`. Used in place of the docx Lambda.
+pub struct LocalDocGenerator<S> {
+    storage: S,
+    naming: ObjectNaming,
+    link_expiry: Duration,
+}
+
+impl<S: DocumentStorage> LocalDocGenerator<S> {
+    pub fn new(storage: S, naming: ObjectNaming, link_expiry: Duration) -> Self {
+        LocalDocGenerator { storage, naming, link_expiry }
+    }
+}
+
+/// Renders the document instructions in the requested format.
+pub fn render(instructions: &Value, format: OutputFormat) -> Result<Vec<u8>, DocGenError> {
+    match format {
+        OutputFormat::Docx => render_docx(instructions),
+        OutputFormat::Html => render_html(instructions).map(String::into_bytes),
+        OutputFormat::Pdf => render_pdf(instructions),
+        OutputFormat::Markdown => render_markdown(instructions).map(String::into_bytes)
+    }
+}
+
+pub(crate) fn report_id(instructions: &Value) -> String {
+    instructions
+        .get("id")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string())
+}
+
+/// The date the report is prepared as at, so that regenerating a report files it under the same
+/// date.
+fn as_at(instructions: &Value) -> Result<NaiveDate, DocGenError> {
+    instructions
+        .get("asAt")
+        .and_then(Value::as_str)
+        .and_then(|as_at| NaiveDate::parse_from_str(as_at, "%Y-%m-%d").ok())
+        .ok_or_else(|| DocGenError::RenderError("instructions do not have a valid as-at date".to_string()))
+}
+
+/// The report kind, e.g. `coupleAnnualReview`.
+fn report_type(instructions: &Value) -> &str {
+    instructions
+        .get("reportKind")
+        .and_then(Value::as_str)
+        .unwrap_or("report")
+}
+
+#[async_trait]
+impl<S: DocumentStorage + Send + Sync> DocumentGenerator for LocalDocGenerator<S> {
+    async fn generate(&self, instructions: &Value, format: OutputFormat) -> Result<String, DocGenError> {
+        let bytes = render(instructions, format)?;
+
+        let report_id = report_id(instructions);
+        let key = self.naming.key(&ObjectName {
+            client: instructions.get("client").and_then(Value::as_str).unwrap_or_default(),
+            report_type: report_type(instructions),
+            date: as_at(instructions)?,
+            report_id: &report_id,
+            extension: format.extension(),
+        });
+
+        self.storage.put(&key, bytes, format.content_type()).await?;
+        info!(key = %key, format = %format, "Stored report");
+
+        Ok(self.storage.presigned_get(&key, self.link_expiry).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driven::doc_generator::layout::tests::sample_document;
+    use crate::driven::storage::filesystem::FilesystemStorage;
+
+    #[tokio::test]
+    async fn test_generate_writes_one_file_per_format() {
+        let output_dir = std::env::temp_dir().join("gcwm-local-generator-test");
+        let naming = ObjectNaming::new("{client}/{report_type}/{report_id}").unwrap();
+        let generator = LocalDocGenerator::new(FilesystemStorage::new(&output_dir), naming, Duration::from_secs(3600));
+
+        let links = generator
+            .generate_all(&sample_document(), &[OutputFormat::Docx, OutputFormat::Pdf, OutputFormat::Html])
+            .await
+            .unwrap();
+
+        assert_eq!(links.len(), 3);
+        for format in [OutputFormat::Docx, OutputFormat::Pdf, OutputFormat::Html] {
+            let path = output_dir.join(format!("john-smith-jane-smith/couple-annual-review/a1b2c3.{}", format.extension()));
+            assert_eq!(links[&format], format!("file://{}", path.display()));
+            assert!(path.exists());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_key_is_dated_as_at_the_report() {
+        let output_dir = std::env::temp_dir().join("gcwm-local-generator-as-at-test");
+        let naming = ObjectNaming::new("{date}/{report_id}").unwrap();
+        let generator = LocalDocGenerator::new(FilesystemStorage::new(&output_dir), naming, Duration::from_secs(3600));
+
+        let link = generator.generate(&sample_document(), OutputFormat::Markdown).await.unwrap();
+
+        assert_eq!(link, format!("file://{}", output_dir.join("2026-06-01/a1b2c3.md").display()));
+    }
+}
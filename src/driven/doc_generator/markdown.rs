@@ -0,0 +1,113 @@
+// driven/doc_generator/markdown.rs
+use serde_json::Value;
+
+use super::layout::{layout_report, Block, DocumentLayout};
+use super::DocGenError;
+
+/// Renders the report JSON to Markdown for review tools and text diffs. The output depends
+/// only on the report content: one line per paragraph, no dates or generated ids.
+pub fn render_markdown(instructions: &Value) -> Result<String, DocGenError> {
+    Ok(markdown(&layout_report(instructions)?))
+}
+
+/// Escapes characters that would otherwise be read as inline Markdown.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '|') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    // Text starting with a block marker would become a heading, quote or list
+    let starts_block = escaped.starts_with(['#', '>', '-', '+'])
+        || escaped
+            .split_once(". ")
+            .is_some_and(|(number, _)| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()));
+    if starts_block {
+        escaped.insert(0, '\\');
+    }
+    escaped
+}
+
+fn table_row(cells: &[String], columns: usize) -> String {
+    let cells: Vec<String> = (0..columns)
+        .map(|index| escape(cells.get(index).map(String::as_str).unwrap_or_default()))
+        .collect();
+    format!("| {} |\n", cells.join(" | "))
+}
+
+fn block(block: &Block) -> String {
+    match block {
+        Block::Title(text) => format!("# {}\n\n", escape(text)),
+        Block::Heading { level, text, anchor } => {
+            // The title is the only level one heading, so section headings start at level two
+            let hashes = "#".repeat(usize::from((*level).clamp(1, 5)) + 1);
+            match anchor {
+                Some(anchor) => format!("<a id=\"{}\"></a>\n{} {}\n\n", anchor, hashes, escape(text)),
+                None => format!("{} {}\n\n", hashes, escape(text))
+            }
+        }
+        Block::Paragraph(text) | Block::SmallPrint(text) => format!("{}\n\n", escape(text)),
+        Block::Bullets(items) => format!(
+            "{}\n",
+            items.iter().map(|item| format!("- {}\n", escape(item))).collect::<String>()
+        ),
+        Block::Table { header, rows } => {
+            let columns = header.iter().chain(rows.iter()).map(Vec::len).max().unwrap_or(1).max(1);
+            // Markdown tables need a header row, so headerless tables get an empty one
+            let header = table_row(header.as_deref().unwrap_or_default(), columns);
+            let separator = format!("|{}\n", " --- |".repeat(columns));
+            let body: String = rows.iter().map(|row| table_row(row, columns)).collect();
+            format!("{}{}{}\n", header, separator, body)
+        }
+        Block::Contents(entries) => format!(
+            "{}\n",
+            entries
+                .iter()
+                .enumerate()
+                .map(|(index, (title, anchor))| format!("{}. [{}](#{})\n", index + 1, escape(title), anchor))
+                .collect::<String>()
+        ),
+        Block::PageBreak => "---\n\n".to_string()
+    }
+}
+
+fn markdown(layout: &DocumentLayout) -> String {
+    let markdown: String = layout.blocks.iter().map(block).collect();
+    format!("{}\n", markdown.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driven::doc_generator::layout::tests::sample_document;
+
+    #[test]
+    fn test_render_markdown() {
+        let markdown = render_markdown(&sample_document()).unwrap();
+
+        assert!(markdown.starts_with("# Annual Review Report\n\nPrepared for John & Jane Smith\n\n"));
+        assert!(markdown.contains("1. [Background](#background)\n2. [Current circumstances and objectives](#current-circumstances)\n3. [Our advice](#recommendations)\n"));
+        assert!(markdown.contains("<a id=\"recommendations\"></a>\n## Our advice\n"));
+        assert!(markdown.contains("### John Smith\n\n#### Transact - ISA\n"));
+        assert!(markdown.contains("- Retired\n- Moved house\n"));
+        assert!(markdown.contains("|  |  |\n| --- | --- |\n| Will | You should review your wills. |\n"));
+        assert!(markdown.ends_with("| Office | London |\n"));
+    }
+
+    #[test]
+    fn test_render_is_deterministic() {
+        let mut other_run = sample_document();
+        other_run["id"] = Value::String("d4e5f6".to_string());
+        assert_eq!(render_markdown(&sample_document()).unwrap(), render_markdown(&other_run).unwrap());
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("# not a heading"), "\\# not a heading");
+        assert_eq!(escape("1. not a list"), "\\1. not a list");
+        assert_eq!(escape("5.5% growth | *net*"), "5.5% growth \\| \\*net\\*");
+    }
+}
@@ -1,21 +1,241 @@
 // driven/doc_generator.rs
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
 use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
 
+use resilient::{ResilienceConfig, ResilientDocGenerator};
+use crate::driven::storage::{filesystem::FilesystemStorage, s3::S3Storage, ObjectNaming, StorageError};
+
 pub mod lambda;
+pub mod instructions;
+pub mod layout;
+pub mod docx;
+pub mod html;
+pub mod pdf;
+pub mod markdown;
+pub mod local;
+pub mod resilient;
+
+/// Environment variable selecting the document generator: `lambda` (default) or `local`.
+pub const DOCUMENT_GENERATOR_ENV: &str = "DOCUMENT_GENERATOR";
+
+/// Where the local generator keeps documents: `filesystem` (default) or `s3`.
+pub const DOCUMENT_STORAGE_ENV: &str = "DOCUMENT_STORAGE";
+
+/// Directory filesystem storage writes to. Defaults to the system temp directory.
+pub const DOCUMENT_OUTPUT_DIR_ENV: &str = "DOCUMENT_OUTPUT_DIR";
+
+/// Bucket S3 storage writes to.
+pub const DOCUMENT_BUCKET_ENV: &str = "DOCUMENT_BUCKET";
+
+/// Optional endpoint of an S3-compatible service to use instead of AWS.
+pub const DOCUMENT_S3_ENDPOINT_ENV: &str = "DOCUMENT_S3_ENDPOINT";
+
+/// How long download links stay valid, in seconds.
+pub const DOCUMENT_LINK_EXPIRY_SECONDS_ENV: &str = "DOCUMENT_LINK_EXPIRY_SECONDS";
+
+/// Template for stored document names. See `ObjectNaming`.
+pub const DOCUMENT_OBJECT_NAMING_ENV: &str = "DOCUMENT_OBJECT_NAMING";
+
+/// Seconds one generation attempt may take.
+pub const DOCUMENT_GENERATOR_TIMEOUT_SECONDS_ENV: &str = "DOCUMENT_GENERATOR_TIMEOUT_SECONDS";
+
+/// Seconds one generation may take across all its formats and attempts.
+pub const DOCUMENT_GENERATOR_DEADLINE_SECONDS_ENV: &str = "DOCUMENT_GENERATOR_DEADLINE_SECONDS";
+
+/// Retries after a throttled or failed invocation.
+pub const DOCUMENT_GENERATOR_MAX_RETRIES_ENV: &str = "DOCUMENT_GENERATOR_MAX_RETRIES";
+
+/// `true` to retry timed out attempts as well.
+pub const DOCUMENT_GENERATOR_RETRY_TIMEOUTS_ENV: &str = "DOCUMENT_GENERATOR_RETRY_TIMEOUTS";
+
+/// Consecutive failures after which the generator is not called for a while.
+pub const DOCUMENT_GENERATOR_FAILURE_THRESHOLD_ENV: &str = "DOCUMENT_GENERATOR_FAILURE_THRESHOLD";
+
+/// Seconds the generator is left alone once the failure threshold is reached.
+pub const DOCUMENT_GENERATOR_COOL_DOWN_SECONDS_ENV: &str = "DOCUMENT_GENERATOR_COOL_DOWN_SECONDS";
+
+const DEFAULT_LINK_EXPIRY: Duration = Duration::from_secs(60 * 60);
+
+const DOCX_GENERATOR_FUNCTION: &str = "docx_generator";
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Docx,
+    Html,
+    Pdf,
+    Markdown
+}
+
+impl OutputFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Docx => "docx",
+            OutputFormat::Html => "html",
+            OutputFormat::Pdf => "pdf",
+            OutputFormat::Markdown => "md"
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            OutputFormat::Docx => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            OutputFormat::Html => "text/html; charset=utf-8",
+            OutputFormat::Pdf => "application/pdf",
+            OutputFormat::Markdown => "text/markdown; charset=utf-8"
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OutputFormat::Docx => "docx",
+            OutputFormat::Html => "html",
+            OutputFormat::Pdf => "pdf",
+            OutputFormat::Markdown => "markdown"
+        };
+        write!(f, "{}", name)
+    }
+}
 
 #[derive(Debug, Error)]
 pub enum DocGenError {
     #[error("AWS Lambda invocation failed: {0}")]
     InvocationError(String),
+    #[error("The document generator is throttling requests: {0}")]
+    Throttled(String),
+    #[error("The document generator did not respond within {0:?}")]
+    Timeout(Duration),
+    #[error("The document generator failed: {0}")]
+    FunctionError(String),
+    #[error("The document generator is unavailable after repeated failures, retry in {0:?}")]
+    CircuitOpen(Duration),
     #[error("Invalid response payload: {0}")]
     ResponseParseError(String),
+    #[error("Document rendering failed: {0}")]
+    RenderError(String),
+    #[error("Storing the document failed: {0}")]
+    StorageError(#[from] StorageError),
+    #[error("The document generator cannot produce {0} output")]
+    UnsupportedFormat(OutputFormat),
+}
+
+impl DocGenError {
+    /// Whether the same request may succeed if tried again.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, DocGenError::InvocationError(_) | DocGenError::Throttled(_) | DocGenError::Timeout(_))
+    }
 }
 
 #[async_trait]
 pub trait DocumentGenerator {
-    /// Sends the JSON instructions to the docx‐generator Lambda
-    /// and returns the download URL it responses with.
-    async fn generate(&self, instructions: &Value) -> Result<String, DocGenError>;
-}
\ No newline at end of file
+    /// Turns the report JSON instructions into a document in the given format
+    /// and returns the URL it can be downloaded from.
+    async fn generate(&self, instructions: &Value, format: OutputFormat) -> Result<String, DocGenError>;
+
+    /// Generates each requested format from the same instructions, returning a link per format.
+    async fn generate_all(&self, instructions: &Value, formats: &[OutputFormat]) -> Result<BTreeMap<OutputFormat, String>, DocGenError> {
+        let mut links = BTreeMap::new();
+        for format in formats {
+            links.insert(*format, self.generate(instructions, *format).await?);
+        }
+        Ok(links)
+    }
+}
+
+/// Generates the requested formats, or a single docx when none were requested, and returns
+/// the response payload: one link, or a link per format.
+pub async fn generate_links(generator: &(dyn DocumentGenerator + Send + Sync), instructions: &Value, formats: Option<&[OutputFormat]>) -> Result<Value, DocGenError> {
+    match formats {
+        Some(formats) => generator
+            .generate_all(instructions, formats)
+            .await
+            .map(|links| serde_json::json!(links)),
+        None => generator
+            .generate(instructions, OutputFormat::Docx)
+            .await
+            .map(Value::String)
+    }
+}
+
+fn env_number<T: std::str::FromStr>(name: &str) -> Result<Option<T>, String> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("{} must be a whole number, got '{}'", name, value)),
+        Err(_) => Ok(None)
+    }
+}
+
+fn env_flag(name: &str) -> Result<Option<bool>, String> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("{} must be true or false, got '{}'", name, value)),
+        Err(_) => Ok(None)
+    }
+}
+
+/// Reads the timeout, retry and circuit breaker settings, defaulting any that are not set.
+pub fn resilience_config_from_env() -> Result<ResilienceConfig, String> {
+    let defaults = ResilienceConfig::default();
+    Ok(ResilienceConfig {
+        timeout: env_number(DOCUMENT_GENERATOR_TIMEOUT_SECONDS_ENV)?.map(Duration::from_secs).unwrap_or(defaults.timeout),
+        deadline: env_number(DOCUMENT_GENERATOR_DEADLINE_SECONDS_ENV)?.map(Duration::from_secs).unwrap_or(defaults.deadline),
+        max_retries: env_number(DOCUMENT_GENERATOR_MAX_RETRIES_ENV)?.unwrap_or(defaults.max_retries),
+        retry_timeouts: env_flag(DOCUMENT_GENERATOR_RETRY_TIMEOUTS_ENV)?.unwrap_or(defaults.retry_timeouts),
+        failure_threshold: env_number(DOCUMENT_GENERATOR_FAILURE_THRESHOLD_ENV)?.unwrap_or(defaults.failure_threshold),
+        open_duration: env_number(DOCUMENT_GENERATOR_COOL_DOWN_SECONDS_ENV)?.map(Duration::from_secs).unwrap_or(defaults.open_duration),
+        ..defaults
+    })
+}
+
+/// Builds the generator named by `DOCUMENT_GENERATOR`, with the timeout, retry and circuit
+/// breaker settings applied.
+pub async fn document_generator_from_env() -> Result<Arc<dyn DocumentGenerator + Send + Sync>, String> {
+    let resilience = resilience_config_from_env()?;
+    match std::env::var(DOCUMENT_GENERATOR_ENV).as_deref() {
+        Ok("lambda") | Err(_) => Ok(Arc::new(ResilientDocGenerator::new(lambda::AwsLambdaDocGenerator::new(DOCX_GENERATOR_FUNCTION).await, resilience))),
+        Ok("local") => {
+            let naming = match std::env::var(DOCUMENT_OBJECT_NAMING_ENV) {
+                Ok(template) => ObjectNaming::new(template).map_err(|e| e.to_string())?,
+                Err(_) => ObjectNaming::default()
+            };
+            let link_expiry = match std::env::var(DOCUMENT_LINK_EXPIRY_SECONDS_ENV) {
+                Ok(seconds) => seconds
+                    .parse()
+                    .map(Duration::from_secs)
+                    .map_err(|_| format!("{} must be a whole number of seconds, got '{}'", DOCUMENT_LINK_EXPIRY_SECONDS_ENV, seconds))?,
+                Err(_) => DEFAULT_LINK_EXPIRY
+            };
+
+            match std::env::var(DOCUMENT_STORAGE_ENV).as_deref() {
+                Ok("filesystem") | Err(_) => {
+                    let output_dir = std::env::var(DOCUMENT_OUTPUT_DIR_ENV)
+                        .map(std::path::PathBuf::from)
+                        .unwrap_or_else(|_| std::env::temp_dir());
+                    Ok(Arc::new(ResilientDocGenerator::new(local::LocalDocGenerator::new(FilesystemStorage::new(output_dir), naming, link_expiry), resilience)))
+                }
+                Ok("s3") => {
+                    let bucket = std::env::var(DOCUMENT_BUCKET_ENV)
+                        .map_err(|_| format!("{} is required when {} is 's3'", DOCUMENT_BUCKET_ENV, DOCUMENT_STORAGE_ENV))?;
+                    let storage = S3Storage::new(bucket, std::env::var(DOCUMENT_S3_ENDPOINT_ENV).ok()).await;
+                    Ok(Arc::new(ResilientDocGenerator::new(local::LocalDocGenerator::new(storage, naming, link_expiry), resilience)))
+                }
+                Ok(other) => Err(format!("Unknown {} '{}', expected 'filesystem' or 's3'", DOCUMENT_STORAGE_ENV, other))
+            }
+        }
+        Ok(other) => Err(format!("Unknown {} '{}', expected 'lambda' or 'local'", DOCUMENT_GENERATOR_ENV, other))
+    }
+}
@@ -0,0 +1,332 @@
+// driven/doc_generator/pdf.rs
+use serde_json::Value;
+
+use super::layout::{layout_report, Block, DocumentLayout};
+use super::DocGenError;
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+const BULLET_INDENT: f32 = 14.0;
+const CELL_PADDING: f32 = 4.0;
+
+const DEFAULT_PRIMARY_COLOUR: &str = "1F3864";
+const DEFAULT_TEXT_COLOUR: &str = "262626";
+
+/// Helvetica advance widths for ASCII 32..=126 in thousandths of the font size.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584
+];
+
+/// Renders the report JSON to a PDF using the standard Helvetica fonts, so no fonts need
+/// embedding and the output is byte for byte reproducible.
+pub fn render_pdf(instructions: &Value) -> Result<Vec<u8>, DocGenError> {
+    let layout = layout_report(instructions)?;
+    let pages = PageWriter::new(&layout).write(&layout.blocks);
+    Ok(assemble(&layout.title, &pages))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Font {
+    Regular,
+    Bold
+}
+
+impl Font {
+    fn resource(&self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2"
+        }
+    }
+}
+
+type Colour = (f32, f32, f32);
+
+fn colour(hex: Option<&str>, default: &str) -> Colour {
+    let hex = hex.unwrap_or(default);
+    let channel = |index: usize| u8::from_str_radix(hex.get(index..index + 2).unwrap_or("00"), 16).unwrap_or(0) as f32 / 255.0;
+    (channel(0), channel(2), channel(4))
+}
+
+/// Maps a character to its WinAnsiEncoding byte, the encoding of the standard fonts.
+fn win_ansi(c: char) -> u8 {
+    match c {
+        ' '..='~' => c as u8,
+        '\u{a0}'..='\u{ff}' => c as u32 as u8,
+        '€' => 0x80,
+        '‚' => 0x82,
+        '„' => 0x84,
+        '…' => 0x85,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '™' => 0x99,
+        _ => b'?'
+    }
+}
+
+/// Encodes text as the body of a PDF literal string, keeping the output ASCII.
+fn pdf_string(text: &str) -> String {
+    text.chars()
+        .map(|c| match win_ansi(c) {
+            b'(' => "\\(".to_string(),
+            b')' => "\\)".to_string(),
+            b'\\' => "\\\\".to_string(),
+            byte @ 32..=126 => (byte as char).to_string(),
+            byte => format!("\\{:03o}", byte)
+        })
+        .collect()
+}
+
+fn text_width(text: &str, font: Font, size: f32) -> f32 {
+    let units: f32 = text
+        .chars()
+        .map(|c| match c {
+            ' '..='~' => HELVETICA_WIDTHS[(c as usize) - 32] as f32,
+            _ => 556.0
+        })
+        .sum();
+    // Helvetica-Bold is slightly wider; the allowance keeps wrapped lines inside the margin
+    let weight = if font == Font::Bold { 1.06 } else { 1.0 };
+    units * weight * size / 1000.0
+}
+
+fn wrap(text: &str, font: Font, size: f32, width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+        if !line.is_empty() && text_width(&candidate, font, size) > width {
+            lines.push(std::mem::replace(&mut line, word.to_string()));
+        } else {
+            line = candidate;
+        }
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+struct PageWriter {
+    pages: Vec<String>,
+    current: String,
+    y: f32,
+    primary: Colour,
+    text: Colour
+}
+
+impl PageWriter {
+    fn new(layout: &DocumentLayout) -> Self {
+        PageWriter {
+            pages: Vec::new(),
+            current: String::new(),
+            y: PAGE_HEIGHT - MARGIN,
+            primary: colour(layout.primary_colour.as_deref(), DEFAULT_PRIMARY_COLOUR),
+            text: colour(layout.text_colour.as_deref(), DEFAULT_TEXT_COLOUR)
+        }
+    }
+
+    fn write(mut self, blocks: &[Block]) -> Vec<String> {
+        for block in blocks {
+            self.block(block);
+        }
+        self.pages.push(self.current);
+        self.pages
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(std::mem::take(&mut self.current));
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < MARGIN && self.y < PAGE_HEIGHT - MARGIN {
+            self.new_page();
+        }
+    }
+
+    fn text(&mut self, x: f32, y: f32, text: &str, font: Font, size: f32, colour: Colour) {
+        self.current.push_str(&format!(
+            "BT /{} {:.1} Tf {:.3} {:.3} {:.3} rg {:.2} {:.2} Td ({}) Tj ET\n",
+            font.resource(), size, colour.0, colour.1, colour.2, x, y, pdf_string(text)
+        ));
+    }
+
+    fn lines(&mut self, text: &str, font: Font, size: f32, colour: Colour, indent: f32, space_after: f32) {
+        let leading = size * 1.35;
+        for line in wrap(text, font, size, PAGE_WIDTH - 2.0 * MARGIN - indent) {
+            self.ensure_space(leading);
+            self.y -= leading;
+            self.text(MARGIN + indent, self.y, &line, font, size, colour);
+        }
+        self.y -= space_after;
+    }
+
+    fn table(&mut self, header: &Option<Vec<String>>, rows: &[Vec<String>]) {
+        let columns = header.iter().chain(rows.iter()).map(Vec::len).max().unwrap_or(1).max(1);
+        let column_width = (PAGE_WIDTH - 2.0 * MARGIN) / columns as f32;
+        let size = 10.0;
+        let leading = size * 1.35;
+
+        let header_row = header.iter().map(|row| (row, Font::Bold));
+        for (row, font) in header_row.chain(rows.iter().map(|row| (row, Font::Regular))) {
+            let cells: Vec<Vec<String>> = (0..columns)
+                .map(|index| wrap(row.get(index).map(String::as_str).unwrap_or_default(), font, size, column_width - 2.0 * CELL_PADDING))
+                .collect();
+            let height = cells.iter().map(Vec::len).max().unwrap_or(1) as f32 * leading + 2.0 * CELL_PADDING;
+
+            self.ensure_space(height);
+            let top = self.y;
+            for (index, lines) in cells.iter().enumerate() {
+                let x = MARGIN + index as f32 * column_width;
+                for (line_index, line) in lines.iter().enumerate() {
+                    let baseline = top - CELL_PADDING - (line_index as f32 + 1.0) * leading + (leading - size);
+                    self.text(x + CELL_PADDING, baseline, line, font, size, self.text);
+                }
+                self.current.push_str(&format!(
+                    "{:.3} {:.3} {:.3} RG 0.5 w {:.2} {:.2} {:.2} {:.2} re S\n",
+                    self.primary.0, self.primary.1, self.primary.2, x, top - height, column_width, height
+                ));
+            }
+            self.y -= height;
+        }
+        self.y -= 8.0;
+    }
+
+    fn block(&mut self, block: &Block) {
+        match block {
+            Block::Title(text) => {
+                self.y -= 120.0;
+                self.lines(text, Font::Bold, 26.0, self.primary, 0.0, 24.0);
+            }
+            Block::Heading { level, text, .. } => {
+                let size = match level { 1 => 18.0, 2 => 14.0, _ => 12.0 };
+                // Keep a heading with at least the first lines of its content
+                self.ensure_space(size * 1.35 + 40.0);
+                self.y -= 6.0;
+                self.lines(text, Font::Bold, size, self.primary, 0.0, 4.0);
+            }
+            Block::Paragraph(text) => self.lines(text, Font::Regular, 11.0, self.text, 0.0, 6.0),
+            Block::SmallPrint(text) => self.lines(text, Font::Regular, 8.0, self.text, 0.0, 0.0),
+            Block::Bullets(items) => {
+                for item in items {
+                    self.ensure_space(11.0 * 1.35);
+                    let y = self.y - 11.0 * 1.35;
+                    self.text(MARGIN, y, "•", Font::Regular, 11.0, self.text);
+                    self.lines(item, Font::Regular, 11.0, self.text, BULLET_INDENT, 2.0);
+                }
+                self.y -= 4.0;
+            }
+            Block::Table { header, rows } => self.table(header, rows),
+            Block::Contents(entries) => {
+                for (title, _) in entries {
+                    self.lines(title, Font::Regular, 11.0, self.primary, 0.0, 2.0);
+                }
+            }
+            Block::PageBreak => {
+                if !self.current.is_empty() {
+                    self.new_page();
+                }
+            }
+        }
+    }
+}
+
+/// Writes the catalog, fonts and pages with a cross-reference table.
+fn assemble(title: &str, pages: &[String]) -> Vec<u8> {
+    let page_ids: Vec<usize> = (0..pages.len()).map(|index| 6 + 2 * index).collect();
+
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids.iter().map(|id| format!("{} 0 R", id)).collect::<Vec<_>>().join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_string(),
+        format!("<< /Title ({}) /Producer (gcwm-report-gen) >>", pdf_string(title)),
+    ];
+
+    for (page, content_id) in pages.iter().zip(page_ids.iter().map(|id| id + 1)) {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH, PAGE_HEIGHT, content_id
+        ));
+        objects.push(format!("<< /Length {} >>\nstream\n{}endstream", page.len(), page));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.push_str(&format!("{} 0 obj\n{}\nendobj\n", index + 1, object));
+    }
+
+    let xref_offset = pdf.len();
+    pdf.push_str(&format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1));
+    for offset in offsets {
+        pdf.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+    pdf.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref_offset
+    ));
+
+    pdf.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driven::doc_generator::layout::tests::sample_document;
+
+    #[test]
+    fn test_render_pdf() {
+        let bytes = render_pdf(&sample_document()).unwrap();
+        let pdf = String::from_utf8(bytes.clone()).unwrap();
+
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("(Annual Review Report) Tj"));
+        assert!(pdf.contains("(Prepared for John & Jane Smith) Tj"));
+        assert!(pdf.contains("(\\225) Tj"));
+        assert!(pdf.contains("/Count 4"));
+        assert_eq!(render_pdf(&sample_document()).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_cross_reference_offsets() {
+        let pdf = String::from_utf8(render_pdf(&sample_document()).unwrap()).unwrap();
+
+        let startxref: usize = pdf.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+        assert!(pdf[startxref..].starts_with("xref\n"));
+
+        for (index, line) in pdf[startxref..].lines().skip(3).take_while(|line| line.ends_with(" n ")).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj", index + 1)));
+        }
+    }
+
+    #[test]
+    fn test_wrap_and_encoding() {
+        let lines = wrap("one two three four five six seven eight nine ten", Font::Regular, 11.0, 100.0);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| text_width(line, Font::Regular, 11.0) <= 100.0));
+        assert_eq!(pdf_string("£5 (net) \\ ✓"), "\\2435 \\(net\\) \\\\ ?");
+    }
+}
@@ -0,0 +1,333 @@
+// driven/doc_generator/resilient.rs
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde_json::Value;
+use tracing::warn;
+
+use super::{DocGenError, DocumentGenerator, OutputFormat};
+
+/// Limits applied around every call to the wrapped generator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResilienceConfig {
+    /// How long one attempt may take before it is abandoned.
+    pub timeout: Duration,
+    /// How long a call may take across all its formats, attempts and the waits between them. No
+    /// retry starts once it has passed, and the last attempt is cut short to end by it.
+    pub deadline: Duration,
+    /// Attempts made after the first when the failure is transient.
+    pub max_retries: u32,
+    /// Whether a timed out attempt is retried. A generator too slow for one attempt is rarely
+    /// quicker on the next, so by default it is not.
+    pub retry_timeouts: bool,
+    /// Wait before the first retry, doubled for each retry after it.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Consecutive failed calls that open the circuit.
+    pub failure_threshold: u32,
+    /// How long an open circuit rejects calls before letting one through to test the generator.
+    pub open_duration: Duration,
+}
+
+impl Default for ResilienceConfig {
+    fn default() -> Self {
+        ResilienceConfig {
+            // Leaves time to respond inside API Gateway's 29 second limit
+            timeout: Duration::from_secs(25),
+            deadline: Duration::from_secs(25),
+            max_retries: 2,
+            retry_timeouts: false,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(2),
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum CircuitState {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    /// One call is testing the generator; others are rejected until it finishes, or until
+    /// `until` in case the testing call was cancelled
+    HalfOpen { until: Instant },
+}
+
+/// Adds timeouts, retries with exponential backoff and a circuit breaker to any generator.
+///
+/// Only throttling, failed invocations and, when configured, timeouts are retried, and only
+/// while the call's deadline allows. Function errors and rendering failures are returned
+/// straight away, since the same instructions would fail again.
+pub struct ResilientDocGenerator<G> {
+    inner: G,
+    config: ResilienceConfig,
+    circuit: Mutex<CircuitState>,
+}
+
+impl<G: DocumentGenerator> ResilientDocGenerator<G> {
+    pub fn new(inner: G, config: ResilienceConfig) -> Self {
+        ResilientDocGenerator { inner, config, circuit: Mutex::new(CircuitState::Closed { failures: 0 }) }
+    }
+
+    fn backoff(&self, retry: u32) -> Duration {
+        self.config
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.config.max_backoff)
+    }
+
+    fn circuit(&self) -> std::sync::MutexGuard<'_, CircuitState> {
+        // The state is always left valid, so a panic elsewhere does not make it unusable
+        self.circuit.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn acquire(&self) -> Result<(), DocGenError> {
+        let mut circuit = self.circuit();
+        match *circuit {
+            CircuitState::Closed { .. } => Ok(()),
+            CircuitState::Open { until } | CircuitState::HalfOpen { until } if Instant::now() >= until => {
+                *circuit = CircuitState::HalfOpen { until: Instant::now() + self.config.open_duration };
+                Ok(())
+            }
+            CircuitState::Open { until } | CircuitState::HalfOpen { until } => {
+                Err(DocGenError::CircuitOpen(until.saturating_duration_since(Instant::now())))
+            }
+        }
+    }
+
+    fn record<T>(&self, result: &Result<T, DocGenError>) {
+        let mut circuit = self.circuit();
+        let generator_unhealthy = match result {
+            Ok(_) => false,
+            Err(e) => e.is_retryable() || matches!(e, DocGenError::FunctionError(_)),
+        };
+
+        *circuit = match (&*circuit, generator_unhealthy) {
+            (_, false) => CircuitState::Closed { failures: 0 },
+            (CircuitState::Closed { failures }, true) if failures + 1 < self.config.failure_threshold => {
+                CircuitState::Closed { failures: failures + 1 }
+            }
+            (_, true) => {
+                warn!(open_for = ?self.config.open_duration, "Document generator circuit opened");
+                CircuitState::Open { until: Instant::now() + self.config.open_duration }
+            }
+        };
+    }
+
+    fn should_retry(&self, error: &DocGenError, retry: u32) -> bool {
+        error.is_retryable()
+            && retry < self.config.max_retries
+            && (self.config.retry_timeouts || !matches!(error, DocGenError::Timeout(_)))
+    }
+
+    async fn attempt(&self, instructions: &Value, format: OutputFormat, deadline: Instant) -> Result<String, DocGenError> {
+        let timeout = self.config.timeout.min(deadline.saturating_duration_since(Instant::now()));
+        tokio::time::timeout(timeout, self.inner.generate(instructions, format))
+            .await
+            .unwrap_or(Err(DocGenError::Timeout(timeout)))
+    }
+
+    /// Generates one format, retrying transient failures while `deadline` allows.
+    async fn generate_by(&self, instructions: &Value, format: OutputFormat, deadline: Instant) -> Result<String, DocGenError> {
+        let mut retry = 0;
+        loop {
+            match self.attempt(instructions, format, deadline).await {
+                Err(e) if self.should_retry(&e, retry) && Instant::now() + self.backoff(retry) < deadline => {
+                    let backoff = self.backoff(retry);
+                    warn!(error = %e, retry = retry + 1, backoff = ?backoff, "Retrying document generation");
+                    tokio::time::sleep(backoff).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<G: DocumentGenerator + Send + Sync> DocumentGenerator for ResilientDocGenerator<G> {
+    async fn generate(&self, instructions: &Value, format: OutputFormat) -> Result<String, DocGenError> {
+        self.acquire()?;
+
+        let result = self.generate_by(instructions, format, Instant::now() + self.config.deadline).await;
+
+        self.record(&result);
+        result
+    }
+
+    /// Generates the formats one after another within a single deadline, so that asking for
+    /// several formats cannot take longer than asking for one.
+    async fn generate_all(&self, instructions: &Value, formats: &[OutputFormat]) -> Result<BTreeMap<OutputFormat, String>, DocGenError> {
+        self.acquire()?;
+
+        let deadline = Instant::now() + self.config.deadline;
+        let mut links = BTreeMap::new();
+        let mut result = Ok(());
+        for format in formats {
+            match self.generate_by(instructions, *format, deadline).await {
+                Ok(link) => {
+                    links.insert(*format, link);
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+
+        self.record(&result);
+        result.map(|_| links)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails with the scripted errors in turn, then succeeds.
+    struct ScriptedGenerator {
+        calls: AtomicU32,
+        failures: Vec<fn() -> DocGenError>,
+        delay: Duration,
+    }
+
+    impl ScriptedGenerator {
+        fn new(failures: Vec<fn() -> DocGenError>) -> Self {
+            ScriptedGenerator { calls: AtomicU32::new(0), failures, delay: Duration::ZERO }
+        }
+    }
+
+    #[async_trait]
+    impl DocumentGenerator for ScriptedGenerator {
+        async fn generate(&self, _instructions: &Value, _format: OutputFormat) -> Result<String, DocGenError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) as usize;
+            tokio::time::sleep(self.delay).await;
+            match self.failures.get(call) {
+                Some(failure) => Err(failure()),
+                None => Ok("https://example.com/report.docx".to_string()),
+            }
+        }
+    }
+
+    fn config() -> ResilienceConfig {
+        ResilienceConfig {
+            timeout: Duration::from_millis(50),
+            deadline: Duration::from_secs(1),
+            max_retries: 2,
+            retry_timeouts: false,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(2),
+            failure_threshold: 2,
+            open_duration: Duration::from_millis(50),
+        }
+    }
+
+    fn throttled() -> DocGenError {
+        DocGenError::Throttled("rate exceeded".to_string())
+    }
+
+    fn crashed() -> DocGenError {
+        DocGenError::FunctionError("Unhandled: KeyError".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_retries_throttling() {
+        let generator = ResilientDocGenerator::new(ScriptedGenerator::new(vec![throttled, throttled]), config());
+
+        assert!(generator.generate(&Value::Null, OutputFormat::Docx).await.is_ok());
+        assert_eq!(generator.inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retries_are_bounded() {
+        let generator = ResilientDocGenerator::new(ScriptedGenerator::new(vec![throttled; 5]), config());
+
+        assert!(matches!(generator.generate(&Value::Null, OutputFormat::Docx).await, Err(DocGenError::Throttled(_))));
+        assert_eq!(generator.inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_function_errors_are_not_retried() {
+        let generator = ResilientDocGenerator::new(ScriptedGenerator::new(vec![crashed]), config());
+
+        assert!(matches!(generator.generate(&Value::Null, OutputFormat::Docx).await, Err(DocGenError::FunctionError(_))));
+        assert_eq!(generator.inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        let mut inner = ScriptedGenerator::new(vec![]);
+        inner.delay = Duration::from_millis(200);
+        let generator = ResilientDocGenerator::new(inner, ResilienceConfig { max_retries: 0, ..config() });
+
+        assert!(matches!(generator.generate(&Value::Null, OutputFormat::Docx).await, Err(DocGenError::Timeout(_))));
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_are_not_retried_by_default() {
+        let mut inner = ScriptedGenerator::new(vec![]);
+        inner.delay = Duration::from_millis(200);
+        let generator = ResilientDocGenerator::new(inner, config());
+
+        assert!(matches!(generator.generate(&Value::Null, OutputFormat::Docx).await, Err(DocGenError::Timeout(_))));
+        assert_eq!(generator.inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retries_stop_at_the_deadline() {
+        let mut inner = ScriptedGenerator::new(vec![throttled; 5]);
+        inner.delay = Duration::from_millis(40);
+        let config = ResilienceConfig { deadline: Duration::from_millis(100), max_retries: 5, ..config() };
+        let generator = ResilientDocGenerator::new(inner, config);
+
+        let started = Instant::now();
+        assert!(generator.generate(&Value::Null, OutputFormat::Docx).await.is_err());
+        assert!(started.elapsed() < Duration::from_millis(150));
+        assert!(generator.inner.calls.load(Ordering::SeqCst) < 4);
+    }
+
+    #[tokio::test]
+    async fn test_last_attempt_is_cut_short_at_the_deadline() {
+        let mut inner = ScriptedGenerator::new(vec![]);
+        inner.delay = Duration::from_millis(200);
+        let config = ResilienceConfig { timeout: Duration::from_secs(1), deadline: Duration::from_millis(50), retry_timeouts: true, ..config() };
+        let generator = ResilientDocGenerator::new(inner, config);
+
+        assert!(matches!(generator.generate(&Value::Null, OutputFormat::Docx).await, Err(DocGenError::Timeout(_))));
+        assert_eq!(generator.inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_formats_share_one_deadline() {
+        let mut inner = ScriptedGenerator::new(vec![]);
+        inner.delay = Duration::from_millis(40);
+        let config = ResilienceConfig { deadline: Duration::from_millis(100), ..config() };
+        let generator = ResilientDocGenerator::new(inner, config);
+
+        let started = Instant::now();
+        let result = generator.generate_all(&Value::Null, &[OutputFormat::Docx, OutputFormat::Html, OutputFormat::Pdf]).await;
+
+        // Two formats fit in the deadline; the third is cut short instead of starting afresh
+        assert!(matches!(result, Err(DocGenError::Timeout(_))));
+        assert!(started.elapsed() < Duration::from_millis(150));
+        assert_eq!(generator.inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_and_recovers() {
+        let generator = ResilientDocGenerator::new(ScriptedGenerator::new(vec![crashed, crashed]), config());
+
+        assert!(generator.generate(&Value::Null, OutputFormat::Docx).await.is_err());
+        assert!(generator.generate(&Value::Null, OutputFormat::Docx).await.is_err());
+        assert!(matches!(generator.generate(&Value::Null, OutputFormat::Docx).await, Err(DocGenError::CircuitOpen(_))));
+        assert_eq!(generator.inner.calls.load(Ordering::SeqCst), 2);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(generator.generate(&Value::Null, OutputFormat::Docx).await.is_ok());
+        assert!(generator.generate(&Value::Null, OutputFormat::Docx).await.is_ok());
+    }
+}
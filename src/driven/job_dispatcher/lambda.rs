@@ -0,0 +1,78 @@
+// driven/job_dispatcher/lambda.rs
+use async_trait::async_trait;
+use aws_config::{meta::region::RegionProviderChain, BehaviorVersion, Region};
+use aws_sdk_lambda::{primitives::Blob, types::InvocationType, Client};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+use super::{DispatchError, JobDispatcher};
+
+/// The event this function sends itself to generate a job, told apart from API Gateway events
+/// by its `generationJobId` field. It carries only the id, keeping it well within the payload
+/// limit of asynchronous invocations whatever the size of the instructions.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GenerationJobEvent {
+    pub generation_job_id: String,
+}
+
+/// Hands jobs to a fresh invocation of `function_name`, invoked asynchronously so that Lambda
+/// queues the event and retries it if the invocation fails. The invocation runs for as long as
+/// the generation takes, however the accepting one was frozen.
+#[derive(Debug)]
+pub struct LambdaJobDispatcher {
+    client: Client,
+    function_name: String,
+}
+
+impl LambdaJobDispatcher {
+    pub async fn new(function_name: impl Into<String>) -> Self {
+        let region_provider = RegionProviderChain::default_provider()
+            .or_else(Region::new("eu-west-2"));
+        let shared_config = aws_config::defaults(BehaviorVersion::latest())
+            .region(region_provider)
+            .load()
+            .await;
+
+        let function_name = function_name.into();
+        info!("Initialized Lambda client for generation jobs on `{}`", function_name);
+
+        Self { client: Client::new(&shared_config), function_name }
+    }
+}
+
+#[async_trait]
+impl JobDispatcher for LambdaJobDispatcher {
+    #[instrument(skip(self), fields(function_name = %self.function_name))]
+    async fn dispatch(&self, job_id: String) -> Result<(), DispatchError> {
+        let payload = serde_json::to_vec(&GenerationJobEvent { generation_job_id: job_id.clone() })
+            .map_err(|e| DispatchError::InvocationError(job_id.clone(), e.to_string()))?;
+
+        self.client
+            .invoke()
+            .function_name(&self.function_name)
+            .invocation_type(InvocationType::Event)
+            .payload(Blob::new(payload))
+            .send()
+            .await
+            .map_err(|e| DispatchError::InvocationError(job_id, e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_generation_job_event_is_not_an_api_request() {
+        let event = GenerationJobEvent { generation_job_id: "a1b2c3".to_string() };
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(json, json!({ "generationJobId": "a1b2c3" }));
+        assert_eq!(serde_json::from_value::<GenerationJobEvent>(json).unwrap(), event);
+        assert!(serde_json::from_value::<GenerationJobEvent>(json!({ "httpMethod": "POST", "path": "/jobs", "body": "{}" })).is_err());
+    }
+}
@@ -0,0 +1,20 @@
+// driven/job_dispatcher/mod.rs
+use async_trait::async_trait;
+use thiserror::Error;
+
+pub mod lambda;
+pub mod spawned;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum DispatchError {
+    #[error("Handing over generation job {0} failed: {1}")]
+    InvocationError(String, String),
+}
+
+/// Hands accepted jobs over to be generated after the API has responded. Only the job id is
+/// handed over: whatever carries the job out loads its instructions from the job repository.
+#[async_trait]
+pub trait JobDispatcher {
+    /// Returns once the job will be carried out without the caller waiting on it.
+    async fn dispatch(&self, job_id: String) -> Result<(), DispatchError>;
+}
@@ -0,0 +1,34 @@
+// driven/job_dispatcher/spawned.rs
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::generation_job::{run_generation_job, GenerationJob};
+use crate::driven::doc_generator::DocumentGenerator;
+use crate::driven::repository::GenerationJobRepository;
+
+use super::{DispatchError, JobDispatcher};
+
+/// Generates jobs on a task in the accepting process. Only suitable for a long-running server:
+/// Lambda freezes the process once the response is sent.
+pub struct SpawnedJobDispatcher<J> {
+    doc_gen: Arc<dyn DocumentGenerator + Send + Sync>,
+    job_repo: Arc<J>,
+}
+
+impl<J> SpawnedJobDispatcher<J> {
+    pub fn new(doc_gen: Arc<dyn DocumentGenerator + Send + Sync>, job_repo: Arc<J>) -> Self {
+        SpawnedJobDispatcher { doc_gen, job_repo }
+    }
+}
+
+#[async_trait]
+impl<J> JobDispatcher for SpawnedJobDispatcher<J>
+where
+    J: GenerationJobRepository<GenerationJob> + Send + Sync + 'static,
+{
+    async fn dispatch(&self, job_id: String) -> Result<(), DispatchError> {
+        tokio::spawn(run_generation_job(job_id, self.doc_gen.clone(), self.job_repo.clone()));
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
 pub mod ai_requests;
 pub mod repository;
-pub mod doc_generator;
\ No newline at end of file
+pub mod doc_generator;
+pub mod job_dispatcher;
+pub mod storage;pub mod config;
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_config::{meta::region::RegionProviderChain, BehaviorVersion, Region};
+use aws_sdk_dynamodb::{types::AttributeValue, Client};
+use tracing::{error, info, instrument};
+
+use crate::{
+    domain::report::adviser_profile::AdviserProfile,
+    driving::data_transfer_object::report_type_data_transfer_object::adviser_data_transfer_object::AdviserProfileDto,
+};
+
+use super::{AdviserRepository, FindAdviser, RepoSelectError};
+
+const TABLE_NAME: &str = "gcwm-advisers";
+
+#[derive(Debug)]
+pub struct AdviserDynamoDbRepo {
+    client: Client,
+}
+
+impl AdviserDynamoDbRepo {
+    pub async fn new() -> Self {
+        let region_provider = RegionProviderChain::default_provider()
+            .or_else(Region::new("eu-west-2"));
+        let shared_config = aws_config::defaults(BehaviorVersion::latest())
+            .region(region_provider)
+            .load()
+            .await;
+
+        let client = Client::new(&shared_config);
+        info!("Initialized DynamoDB client for table `{}`", TABLE_NAME);
+
+        Self { client }
+    }
+}
+
+fn string_attribute(item: &HashMap<String, AttributeValue>, name: &str) -> Result<String, RepoSelectError> {
+    item.get(name)
+        .and_then(|v| v.as_s().ok())
+        .cloned()
+        .ok_or_else(|| {
+            error!(attribute = name, "Missing attribute on adviser item");
+            RepoSelectError::Unknown(format!("Missing {}", name))
+        })
+}
+
+#[async_trait]
+impl AdviserRepository<AdviserProfile> for AdviserDynamoDbRepo {
+    #[instrument(skip(self), fields(adviser_id = %find_adviser.id))]
+    async fn find_one_adviser(
+        &self,
+        find_adviser: FindAdviser,
+    ) -> Result<AdviserProfile, RepoSelectError> {
+        let pk = format!("ADVISER#{}", find_adviser.id);
+
+        let response = self
+            .client
+            .get_item()
+            .table_name(TABLE_NAME)
+            .key("pk", AttributeValue::S(pk))
+            .send()
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to get adviser");
+                RepoSelectError::Unknown(e.to_string())
+            })?;
+
+        let item = response.item.ok_or_else(|| {
+            info!("No adviser found");
+            RepoSelectError::NotFound
+        })?;
+
+        let qualifications = match item.get("qualifications") {
+            Some(AttributeValue::L(values)) => values
+                .iter()
+                .filter_map(|v| v.as_s().ok().cloned())
+                .collect(),
+            Some(AttributeValue::Ss(values)) => values.clone(),
+            _ => Vec::new(),
+        };
+
+        let dto = AdviserProfileDto {
+            id: find_adviser.id,
+            first_name: string_attribute(&item, "firstName")?,
+            last_name: string_attribute(&item, "lastName")?,
+            qualifications,
+            fca_reference_number: string_attribute(&item, "fcaReferenceNumber")?,
+            phone: string_attribute(&item, "phone")?,
+            email: string_attribute(&item, "email")?,
+            office: string_attribute(&item, "office")?,
+            signature_image_location: string_attribute(&item, "signatureImageLocation").ok(),
+        };
+
+        AdviserProfile::try_from(dto).map_err(|e| {
+            error!(error = %e, "Domain conversion failed");
+            RepoSelectError::Unknown(e)
+        })
+    }
+}
@@ -0,0 +1,175 @@
+use std::io::{Read, Write};
+
+use async_trait::async_trait;
+use aws_config::{meta::region::RegionProviderChain, BehaviorVersion, Region};
+use aws_sdk_dynamodb::{primitives::Blob, types::AttributeValue, Client};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use tracing::{error, info, instrument};
+
+use crate::domain::generation_job::{GenerationJob, JobStatus};
+
+use super::{FindGenerationJob, GenerationJobRepository, RepoCreateError, RepoSelectError, RepoUpdateError};
+
+const TABLE_NAME: &str = "gcwm-generation-jobs";
+
+/// Jobs are only polled for shortly after they are accepted, so the table's TTL removes them after a day.
+const JOB_TIME_TO_LIVE_SECONDS: i64 = 24 * 60 * 60;
+
+#[derive(Debug)]
+pub struct GenerationJobDynamoDbRepo {
+    client: Client,
+}
+
+impl GenerationJobDynamoDbRepo {
+    pub async fn new() -> Self {
+        let region_provider = RegionProviderChain::default_provider()
+            .or_else(Region::new("eu-west-2"));
+        let shared_config = aws_config::defaults(BehaviorVersion::latest())
+            .region(region_provider)
+            .load()
+            .await;
+
+        let client = Client::new(&shared_config);
+        info!("Initialized DynamoDB client for table `{}`", TABLE_NAME);
+
+        Self { client }
+    }
+}
+
+fn partition_key(id: &str) -> AttributeValue {
+    AttributeValue::S(format!("JOB#{}", id))
+}
+
+fn read_job(compressed: &[u8]) -> Result<GenerationJob, String> {
+    let mut json = Vec::new();
+    GzDecoder::new(compressed).read_to_end(&mut json).map_err(|e| e.to_string())?;
+    serde_json::from_slice(&json).map_err(|e| e.to_string())
+}
+
+fn status_name(status: &JobStatus) -> &'static str {
+    match status {
+        JobStatus::Pending => "pending",
+        JobStatus::Done { .. } => "done",
+        JobStatus::Failed { .. } => "failed",
+    }
+}
+
+/// The job is kept as gzipped JSON alongside its status, which is duplicated for querying in the
+/// console. The job carries the report's instructions, which compress well below the item size limit.
+fn job_item(job: &GenerationJob) -> Result<Vec<(&'static str, AttributeValue)>, String> {
+    let json = serde_json::to_vec(job).map_err(|e| e.to_string())?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&json).map_err(|e| e.to_string())?;
+    let compressed = encoder.finish().map_err(|e| e.to_string())?;
+    let expires_at = chrono::Utc::now().timestamp() + JOB_TIME_TO_LIVE_SECONDS;
+
+    Ok(vec![
+        ("pk", partition_key(&job.id())),
+        ("status", AttributeValue::S(status_name(job.status()).to_string())),
+        ("job", AttributeValue::B(Blob::new(compressed))),
+        ("expiresAt", AttributeValue::N(expires_at.to_string())),
+    ])
+}
+
+#[async_trait]
+impl GenerationJobRepository<GenerationJob> for GenerationJobDynamoDbRepo {
+    #[instrument(skip(self, job), fields(job_id = %job.id()))]
+    async fn create_job(&self, job: GenerationJob) -> Result<GenerationJob, RepoCreateError> {
+        let item = job_item(&job).map_err(RepoCreateError::InvalidData)?;
+
+        self.client
+            .put_item()
+            .table_name(TABLE_NAME)
+            .set_item(Some(item.into_iter().map(|(name, value)| (name.to_string(), value)).collect()))
+            .condition_expression("attribute_not_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to create job");
+                RepoCreateError::Unknown(e.to_string())
+            })?;
+
+        Ok(job)
+    }
+
+    #[instrument(skip(self), fields(job_id = %find_job.id))]
+    async fn find_one_job(&self, find_job: FindGenerationJob) -> Result<GenerationJob, RepoSelectError> {
+        let response = self
+            .client
+            .get_item()
+            .table_name(TABLE_NAME)
+            .key("pk", partition_key(&find_job.id))
+            .send()
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to get job");
+                RepoSelectError::Unknown(e.to_string())
+            })?;
+
+        let item = response.item.ok_or_else(|| {
+            info!("No job found");
+            RepoSelectError::NotFound
+        })?;
+
+        let compressed = item
+            .get("job")
+            .and_then(|v| v.as_b().ok())
+            .ok_or_else(|| {
+                error!("Missing job attribute on job item");
+                RepoSelectError::Unknown("Missing job".to_string())
+            })?;
+
+        read_job(compressed.as_ref()).map_err(|e| {
+            error!(error = %e, "Stored job could not be read");
+            RepoSelectError::Unknown(e.to_string())
+        })
+    }
+
+    #[instrument(skip(self, job), fields(job_id = %job.id()))]
+    async fn update_job(&self, job: GenerationJob) -> Result<GenerationJob, RepoUpdateError> {
+        let item = job_item(&job).map_err(RepoUpdateError::InvalidData)?;
+
+        self.client
+            .put_item()
+            .table_name(TABLE_NAME)
+            .set_item(Some(item.into_iter().map(|(name, value)| (name.to_string(), value)).collect()))
+            .condition_expression("attribute_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                let service_error = e.as_service_error();
+                if service_error.is_some_and(|e| e.is_conditional_check_failed_exception()) {
+                    return RepoUpdateError::NotFound;
+                }
+                error!(error = %e, "Failed to update job");
+                RepoUpdateError::Unknown(e.to_string())
+            })?;
+
+        Ok(job)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driven::doc_generator::OutputFormat;
+    use crate::driving::data_transfer_object::report_type_data_transfer_object::adviser_data_transfer_object::AdviserDataTransferObject;
+    use serde_json::json;
+
+    #[test]
+    fn test_job_item_keeps_instructions() {
+        let adviser = AdviserDataTransferObject { id: None, adviser_first_name: "Alex".to_string(), adviser_last_name: "Jones".to_string() };
+        let instructions = json!({ "id": "a1b2c3", "elements": vec![json!({ "type": "paragraph", "text": "Lorem ipsum" }); 1000] });
+        let job = GenerationJob::new(adviser, instructions.clone(), Some(vec![OutputFormat::Pdf]));
+
+        let item = job_item(&job).unwrap();
+        let (_, stored) = item.iter().find(|(name, _)| *name == "job").unwrap();
+        let compressed = stored.as_b().unwrap().as_ref();
+        assert!(compressed.len() < instructions.to_string().len() / 10);
+
+        let read = read_job(compressed).unwrap();
+        assert_eq!(read.id(), job.id());
+        assert_eq!(read.instructions(), &instructions);
+        assert_eq!(read.output_formats(), Some(&[OutputFormat::Pdf][..]));
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use serde::Deserialize;
+
+use crate::{
+    domain::{generation_job::GenerationJob, report::{adviser_profile::AdviserProfile, investment_holdings::InvestmentPortfolio}},
+    driving::data_transfer_object::report_type_data_transfer_object::{
+        adviser_data_transfer_object::AdviserProfileDto,
+        investment_holdings::{InvestmentPortfolioDto, ModelPortfolioIdDto},
+    },
+};
+
+use super::{
+    AdviserRepository, FindAdviser, FindGenerationJob, FindModelPortfolio, GenerationJobRepository, InvestmentPortfoliosRepository,
+    RepoCreateError, RepoSelectError, RepoUpdateError,
+};
+
+/// Stand-in adviser directory for local runs and tests.
+#[derive(Debug, Default)]
+pub struct InMemoryAdviserRepo {
+    advisers: HashMap<String, AdviserProfile>,
+}
+
+impl InMemoryAdviserRepo {
+    pub fn new(advisers: Vec<AdviserProfile>) -> Self {
+        Self {
+            advisers: advisers.into_iter().map(|adviser| (adviser.id().clone(), adviser)).collect(),
+        }
+    }
+
+    /// Builds the directory from a JSON array of adviser profiles.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let dtos: Vec<AdviserProfileDto> = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let advisers = dtos
+            .into_iter()
+            .map(AdviserProfile::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::new(advisers))
+    }
+}
+
+#[async_trait]
+impl AdviserRepository<AdviserProfile> for InMemoryAdviserRepo {
+    async fn find_one_adviser(&self, find_adviser: FindAdviser) -> Result<AdviserProfile, RepoSelectError> {
+        self.advisers
+            .get(&find_adviser.id)
+            .cloned()
+            .ok_or(RepoSelectError::NotFound)
+    }
+}
+
+/// One model portfolio in a fixture file, identified the same way as in a request.
+#[derive(Deserialize)]
+struct ModelPortfolioFixture {
+    id: ModelPortfolioIdDto,
+    portfolio: InvestmentPortfolioDto,
+}
+
+/// Stand-in model portfolio table for local runs and tests. Like the DynamoDB table, it keeps
+/// the current holdings of each model and ignores the effective date being asked for.
+#[derive(Debug, Default)]
+pub struct InMemoryInvestmentPortfolioRepo {
+    portfolios: HashMap<String, InvestmentPortfolio>,
+}
+
+/// Matches the sort key used by the DynamoDB table.
+fn model_portfolio_key(provider: &impl ToString, proposition: &impl ToString, sri: bool, risk_profile: &impl ToString, product_type: &impl ToString) -> String {
+    format!(
+        "{}#{}#{}#{}#{}",
+        risk_profile.to_string(),
+        proposition.to_string(),
+        sri,
+        provider.to_string(),
+        product_type.to_string()
+    ).to_uppercase()
+}
+
+impl InMemoryInvestmentPortfolioRepo {
+    /// Builds the table from a JSON array of `{ "id": <model portfolio id>, "portfolio": { "fundHoldings": [...] } }`.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let fixtures: Vec<ModelPortfolioFixture> = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let portfolios = fixtures
+            .into_iter()
+            .map(|fixture| {
+                let id = fixture.id;
+                let key = model_portfolio_key(&id.provider, &id.service_proposition, id.sri, &id.risk_profile, &id.product_type);
+                InvestmentPortfolio::try_from(fixture.portfolio).map(|portfolio| (key, portfolio))
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        Ok(Self { portfolios })
+    }
+}
+
+#[async_trait]
+impl InvestmentPortfoliosRepository<InvestmentPortfolio> for InMemoryInvestmentPortfolioRepo {
+    async fn find_one_model_portfolio(&self, model_portfolio: FindModelPortfolio) -> Result<InvestmentPortfolio, RepoSelectError> {
+        let key = model_portfolio_key(
+            &model_portfolio.provider,
+            &model_portfolio.service_proposition,
+            model_portfolio.sri,
+            &model_portfolio.risk_profile,
+            &model_portfolio.product_type
+        );
+        self.portfolios.get(&key).cloned().ok_or(RepoSelectError::NotFound)
+    }
+}
+
+/// Keeps generation jobs for the life of the process, for local runs and tests.
+#[derive(Debug, Default)]
+pub struct InMemoryGenerationJobRepo {
+    jobs: Mutex<HashMap<String, GenerationJob>>,
+}
+
+#[async_trait]
+impl GenerationJobRepository<GenerationJob> for InMemoryGenerationJobRepo {
+    async fn create_job(&self, job: GenerationJob) -> Result<GenerationJob, RepoCreateError> {
+        let mut jobs = self.jobs.lock().map_err(|e| RepoCreateError::Unknown(e.to_string()))?;
+        if jobs.contains_key(&job.id()) {
+            return Err(RepoCreateError::InvalidData(format!("Job {} already exists", job.id())));
+        }
+        jobs.insert(job.id(), job.clone());
+        Ok(job)
+    }
+
+    async fn find_one_job(&self, find_job: FindGenerationJob) -> Result<GenerationJob, RepoSelectError> {
+        let jobs = self.jobs.lock().map_err(|e| RepoSelectError::Unknown(e.to_string()))?;
+        jobs.get(&find_job.id).cloned().ok_or(RepoSelectError::NotFound)
+    }
+
+    async fn update_job(&self, job: GenerationJob) -> Result<GenerationJob, RepoUpdateError> {
+        let mut jobs = self.jobs.lock().map_err(|e| RepoUpdateError::Unknown(e.to_string()))?;
+        match jobs.get_mut(&job.id()) {
+            Some(stored) => {
+                *stored = job.clone();
+                Ok(job)
+            }
+            None => Err(RepoUpdateError::NotFound)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driving::data_transfer_object::report_type_data_transfer_object::{
+        investment_holdings::{InvestmentStrategyProductTypeDto, InvestmentStrategyProviderDto, InvestmentStrategyServicePropositionDto, MonthYearDto},
+        risk_assessment_dto::RiskProfileDto,
+    };
+
+    #[tokio::test]
+    async fn test_model_portfolios_from_json() {
+        let repo = InMemoryInvestmentPortfolioRepo::from_json(include_str!("../../../config/model_portfolios.example.json")).unwrap();
+        let find = |sri| FindModelPortfolio {
+            provider: InvestmentStrategyProviderDto::Transact,
+            service_proposition: InvestmentStrategyServicePropositionDto::Prime,
+            sri,
+            risk_profile: RiskProfileDto::Moderate,
+            product_type: InvestmentStrategyProductTypeDto::Standard,
+            effective_date: MonthYearDto::Aug24
+        };
+
+        let portfolio = repo.find_one_model_portfolio(find(false)).await.unwrap();
+        assert!(!portfolio.fund_holdings().is_empty());
+        assert!(matches!(repo.find_one_model_portfolio(find(true)).await, Err(RepoSelectError::NotFound)));
+    }
+}
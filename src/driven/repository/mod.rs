@@ -2,8 +2,12 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 pub mod dynamo_db;
+pub mod adviser_dynamo_db;
+pub mod in_memory;
+pub mod wording_templates_dynamo_db;
+pub mod generation_job_dynamo_db;
 
-use crate::{domain::{constrained_types::client_id::ClientId, report::{investment_holdings::MonthYear, Report}, traits::Entity}, driving::data_transfer_object::report_type_data_transfer_object::{investment_holdings::{InvestmentStrategyProductTypeDto, InvestmentStrategyProviderDto, InvestmentStrategyServicePropositionDto, ModelPortfolioIdDto, MonthYearDto}, risk_assessment_dto::RiskProfileDto}, main};
+use crate::{domain::{constrained_types::client_id::ClientId, report::{investment_holdings::MonthYear, Report}, traits::Entity}, driving::data_transfer_object::report_type_data_transfer_object::{investment_holdings::{InvestmentStrategyProductTypeDto, InvestmentStrategyProviderDto, InvestmentStrategyServicePropositionDto, ModelPortfolioIdDto, MonthYearDto}, risk_assessment_dto::RiskProfileDto}};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FindReport {
@@ -21,6 +25,21 @@ pub struct FindModelPortfolio {
     pub effective_date: MonthYearDto
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FindAdviser {
+    pub id: String
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FindWordingTemplates {
+    pub version: String
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FindGenerationJob {
+    pub id: String
+}
+
 // #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 // pub struct MainContactAddress {
 //     pub address_line_one: String,
@@ -188,4 +207,34 @@ pub trait InvestmentPortfoliosRepository<R> where R: Entity   {
     /// Find and return on single model portfolio investment holdings from the persistence system
     async fn find_one_model_portfolio(&self, model_portfolio: FindModelPortfolio) -> Result<R, RepoSelectError>;
 
-}
\ No newline at end of file
+}
+
+#[async_trait]
+pub trait AdviserRepository<R> where R: Entity   {
+
+    /// Find and return one single adviser directory entry from the persistence system
+    async fn find_one_adviser(&self, adviser: FindAdviser) -> Result<R, RepoSelectError>;
+
+}
+
+#[async_trait]
+pub trait WordingTemplatesRepository<R> where R: Entity   {
+
+    /// Find and return one version of the wording templates from the persistence system
+    async fn find_wording_templates(&self, templates: FindWordingTemplates) -> Result<R, RepoSelectError>;
+
+}
+
+#[async_trait]
+pub trait GenerationJobRepository<R> where R: Entity   {
+
+    /// Insert a newly accepted job in the persistence system
+    async fn create_job(&self, job: R) -> Result<R, RepoCreateError>;
+
+    /// Find and return one single job from the persistence system
+    async fn find_one_job(&self, job: FindGenerationJob) -> Result<R, RepoSelectError>;
+
+    /// Update one single job already present in the persistence system
+    async fn update_job(&self, job: R) -> Result<R, RepoUpdateError>;
+
+}
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_config::{meta::region::RegionProviderChain, BehaviorVersion, Region};
+use aws_sdk_dynamodb::{types::AttributeValue, Client};
+use tracing::{error, info, instrument};
+
+use crate::domain::report::wording_templates::{TemplateName, WordingTemplates};
+
+use super::{FindWordingTemplates, RepoSelectError, WordingTemplatesRepository};
+
+const TABLE_NAME: &str = "gcwm-wording-templates";
+
+#[derive(Debug)]
+pub struct WordingTemplatesDynamoDbRepo {
+    client: Client,
+}
+
+impl WordingTemplatesDynamoDbRepo {
+    pub async fn new() -> Self {
+        let region_provider = RegionProviderChain::default_provider()
+            .or_else(Region::new("eu-west-2"));
+        let shared_config = aws_config::defaults(BehaviorVersion::latest())
+            .region(region_provider)
+            .load()
+            .await;
+
+        let client = Client::new(&shared_config);
+        info!("Initialized DynamoDB client for table `{}`", TABLE_NAME);
+
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl WordingTemplatesRepository<WordingTemplates> for WordingTemplatesDynamoDbRepo {
+    #[instrument(skip(self), fields(version = %find_templates.version))]
+    async fn find_wording_templates(
+        &self,
+        find_templates: FindWordingTemplates,
+    ) -> Result<WordingTemplates, RepoSelectError> {
+        let pk = format!("TEMPLATES#{}", find_templates.version);
+
+        let response = self
+            .client
+            .get_item()
+            .table_name(TABLE_NAME)
+            .key("pk", AttributeValue::S(pk))
+            .send()
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to get wording templates");
+                RepoSelectError::Unknown(e.to_string())
+            })?;
+
+        let item = response.item.ok_or_else(|| {
+            info!("No wording templates found");
+            RepoSelectError::NotFound
+        })?;
+
+        let stored = match item.get("templates") {
+            Some(AttributeValue::M(templates)) => templates,
+            _ => {
+                error!("Missing templates attribute on wording templates item");
+                return Err(RepoSelectError::Unknown("Missing templates".to_string()));
+            }
+        };
+
+        // Stored sets may hold only the templates a firm has changed, like template files
+        let mut overrides = HashMap::new();
+        for (key, value) in stored {
+            let name: TemplateName = serde_json::from_value(serde_json::Value::String(key.clone()))
+                .map_err(|_| RepoSelectError::Unknown(format!("Unknown template '{}'", key)))?;
+            let text = value
+                .as_s()
+                .map_err(|_| RepoSelectError::Unknown(format!("Template '{}' is not a string", key)))?;
+            overrides.insert(name, text.clone());
+        }
+
+        WordingTemplates::with_overrides(find_templates.version, overrides).map_err(|e| {
+            error!(error = %e, "Domain conversion failed");
+            RepoSelectError::Unknown(e)
+        })
+    }
+}
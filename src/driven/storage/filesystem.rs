@@ -0,0 +1,65 @@
+// driven/storage/filesystem.rs
+use std::path::PathBuf;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use super::{DocumentStorage, StorageError};
+
+/// Keeps documents in a local directory. Links are `file://` paths, which do not expire,
+/// so this is meant for development and offline generation.
+#[derive(Debug)]
+pub struct FilesystemStorage {
+    root: PathBuf,
+}
+
+impl FilesystemStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        FilesystemStorage { root: root.into() }
+    }
+}
+
+#[async_trait]
+impl DocumentStorage for FilesystemStorage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, _content_type: &str) -> Result<(), StorageError> {
+        let path = self.root.join(key);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| StorageError::PutError(key.to_string(), e.to_string()))?;
+        }
+        std::fs::write(&path, bytes)
+            .map_err(|e| StorageError::PutError(key.to_string(), e.to_string()))
+    }
+
+    async fn presigned_get(&self, key: &str, _expires_in: Duration) -> Result<String, StorageError> {
+        let path = self.root.join(key);
+        if !path.exists() {
+            return Err(StorageError::PresignError(key.to_string(), "no such file".to_string()));
+        }
+        Ok(format!("file://{}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_put_then_get() {
+        let root = std::env::temp_dir().join("gcwm-filesystem-storage-test");
+        let storage = FilesystemStorage::new(&root);
+
+        storage.put("smith/report/a1b2c3.md", b"# Report\n".to_vec(), "text/markdown").await.unwrap();
+        let link = storage.presigned_get("smith/report/a1b2c3.md", Duration::from_secs(60)).await.unwrap();
+
+        let path = root.join("smith/report/a1b2c3.md");
+        assert_eq!(link, format!("file://{}", path.display()));
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "# Report\n");
+    }
+
+    #[tokio::test]
+    async fn test_get_missing_document() {
+        let storage = FilesystemStorage::new(std::env::temp_dir().join("gcwm-filesystem-storage-test"));
+        assert!(storage.presigned_get("missing.pdf", Duration::from_secs(60)).await.is_err());
+    }
+}
@@ -0,0 +1,162 @@
+// driven/storage/mod.rs
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use thiserror::Error;
+
+pub mod filesystem;
+pub mod s3;
+
+/// Default object name: one folder per client, report type and day.
+pub const DEFAULT_OBJECT_NAMING: &str = "{client}/{report_type}/{date}/{report_id}";
+
+const NAMING_VARIABLES: [&str; 4] = ["client", "report_type", "date", "report_id"];
+
+#[derive(Debug, Error, PartialEq)]
+pub enum StorageError {
+    #[error("Storing '{0}' failed: {1}")]
+    PutError(String, String),
+    #[error("Creating a download link for '{0}' failed: {1}")]
+    PresignError(String, String),
+    #[error("Invalid object naming template: {0}")]
+    InvalidNaming(String),
+}
+
+/// Somewhere generated documents are kept and downloaded from.
+#[async_trait]
+pub trait DocumentStorage {
+    /// Stores the document under `key`, replacing anything already there.
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), StorageError>;
+
+    /// Returns a link the document under `key` can be downloaded from until `expires_in` has passed.
+    async fn presigned_get(&self, key: &str, expires_in: Duration) -> Result<String, StorageError>;
+}
+
+/// What a stored document is named after.
+#[derive(Debug, Clone)]
+pub struct ObjectName<'a> {
+    pub client: &'a str,
+    pub report_type: &'a str,
+    pub date: NaiveDate,
+    pub report_id: &'a str,
+    pub extension: &'a str,
+}
+
+/// Template for object keys, with `{client}`, `{report_type}`, `{date}` and `{report_id}`
+/// placeholders. The file extension is always appended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectNaming {
+    template: String,
+}
+
+impl ObjectNaming {
+    pub fn new(template: impl Into<String>) -> Result<Self, StorageError> {
+        let template = template.into();
+
+        let mut rest = template.as_str();
+        let mut has_report_id = false;
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| StorageError::InvalidNaming(format!("unclosed placeholder in '{}'", template)))?;
+            let variable = &rest[start + 1..start + end];
+            if !NAMING_VARIABLES.contains(&variable) {
+                return Err(StorageError::InvalidNaming(format!("unknown placeholder '{{{}}}' in '{}'", variable, template)));
+            }
+            has_report_id |= variable == "report_id";
+            rest = &rest[start + end + 1..];
+        }
+
+        // Without the report id two reports for the same client on the same day would overwrite each other
+        if !has_report_id {
+            return Err(StorageError::InvalidNaming(format!("'{}' must include {{report_id}}", template)));
+        }
+        if template.starts_with('/') || template.split('/').any(|segment| segment == "..") {
+            return Err(StorageError::InvalidNaming(format!("'{}' must be a relative path", template)));
+        }
+
+        Ok(ObjectNaming { template })
+    }
+
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Builds the object key for a document.
+    pub fn key(&self, name: &ObjectName) -> String {
+        let key = self.template
+            .replace("{client}", &slug(name.client))
+            .replace("{report_type}", &slug(name.report_type))
+            .replace("{date}", &name.date.format("%Y-%m-%d").to_string())
+            .replace("{report_id}", &slug(name.report_id));
+        format!("{}.{}", key, name.extension)
+    }
+}
+
+impl Default for ObjectNaming {
+    fn default() -> Self {
+        ObjectNaming { template: DEFAULT_OBJECT_NAMING.to_string() }
+    }
+}
+
+/// Lower case words separated by hyphens, splitting camelCase, so names are safe in keys and URLs.
+fn slug(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    let mut previous_lowercase = false;
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && previous_lowercase {
+                slug.push('-');
+            }
+            slug.push(c.to_ascii_lowercase());
+            previous_lowercase = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+            previous_lowercase = false;
+        }
+    }
+
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() { "unknown".to_string() } else { slug.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name() -> ObjectName<'static> {
+        ObjectName {
+            client: "John Smith & Jane O'Neil",
+            report_type: "coupleAnnualReviewReport",
+            date: NaiveDate::from_ymd_opt(2025, 4, 6).unwrap(),
+            report_id: "a1b2c3",
+            extension: "pdf",
+        }
+    }
+
+    #[test]
+    fn test_default_naming() {
+        assert_eq!(
+            ObjectNaming::default().key(&name()),
+            "john-smith-jane-o-neil/couple-annual-review-report/2025-04-06/a1b2c3.pdf"
+        );
+    }
+
+    #[test]
+    fn test_custom_naming() {
+        let naming = ObjectNaming::new("reports/{date}-{report_id}").unwrap();
+        assert_eq!(naming.key(&name()), "reports/2025-04-06-a1b2c3.pdf");
+    }
+
+    #[test]
+    fn test_invalid_naming_is_rejected() {
+        assert!(ObjectNaming::new("{client}/{date}").is_err());
+        assert!(ObjectNaming::new("{client}/{adviser}/{report_id}").is_err());
+        assert!(ObjectNaming::new("{client/{report_id}").is_err());
+        assert!(ObjectNaming::new("/{report_id}").is_err());
+        assert!(ObjectNaming::new("../{report_id}").is_err());
+    }
+}
@@ -0,0 +1,96 @@
+// driven/storage/s3.rs
+use std::time::Duration;
+
+use async_trait::async_trait;
+use aws_config::{meta::region::RegionProviderChain, BehaviorVersion, Region};
+use aws_sdk_s3::{presigning::PresigningConfig, primitives::ByteStream, Client};
+use tracing::{info, instrument};
+
+use super::{DocumentStorage, StorageError};
+
+/// Keeps documents in an S3 bucket and hands out presigned download links.
+#[derive(Debug)]
+pub struct S3Storage {
+    client: Client,
+    bucket: String,
+}
+
+impl S3Storage {
+    /// `endpoint_url` points the client at an S3-compatible service, such as a local stand-in,
+    /// instead of AWS. Those services generally only support path-style addressing.
+    pub async fn new(bucket: impl Into<String>, endpoint_url: Option<String>) -> Self {
+        let region_provider = RegionProviderChain::default_provider()
+            .or_else(Region::new("eu-west-2"));
+        let shared_config = aws_config::defaults(BehaviorVersion::latest())
+            .region(region_provider)
+            .load()
+            .await;
+
+        Self::from_config(aws_sdk_s3::config::Builder::from(&shared_config), bucket, endpoint_url)
+    }
+
+    fn from_config(mut config: aws_sdk_s3::config::Builder, bucket: impl Into<String>, endpoint_url: Option<String>) -> Self {
+        if let Some(endpoint_url) = endpoint_url {
+            config = config.endpoint_url(endpoint_url).force_path_style(true);
+        }
+
+        let bucket = bucket.into();
+        info!("Initialized S3 client for bucket `{}`", bucket);
+
+        Self { client: Client::from_conf(config.build()), bucket }
+    }
+}
+
+#[async_trait]
+impl DocumentStorage for S3Storage {
+    #[instrument(skip(self, bytes), fields(bucket = %self.bucket))]
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), StorageError> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(bytes))
+            .send()
+            .await
+            .map_err(|e| StorageError::PutError(key.to_string(), e.to_string()))?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(bucket = %self.bucket))]
+    async fn presigned_get(&self, key: &str, expires_in: Duration) -> Result<String, StorageError> {
+        let presigning_config = PresigningConfig::expires_in(expires_in)
+            .map_err(|e| StorageError::PresignError(key.to_string(), e.to_string()))?;
+
+        let request = self.client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .presigned(presigning_config)
+            .await
+            .map_err(|e| StorageError::PresignError(key.to_string(), e.to_string()))?;
+
+        Ok(request.uri().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_s3::config::Credentials;
+
+    /// Presigning is done locally, so this needs credentials but no running service.
+    #[tokio::test]
+    async fn test_presigned_get_uses_endpoint_and_expiry() {
+        let config = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("eu-west-2"))
+            .credentials_provider(Credentials::new("test", "test", None, None, "test"));
+        let storage = S3Storage::from_config(config, "reports", Some("http://localhost:9000".to_string()));
+
+        let link = storage.presigned_get("smith/a1b2c3.pdf", Duration::from_secs(900)).await.unwrap();
+
+        assert!(link.starts_with("http://localhost:9000/reports/smith/a1b2c3.pdf?"));
+        assert!(link.contains("X-Amz-Expires=900"));
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use lambda_http::{request::RequestContext, Request, RequestExt};
+use serde_json::Value;
+
+use crate::driving::data_transfer_object::report_type_data_transfer_object::adviser_data_transfer_object::AdviserDataTransferObject;
+
+/// Role that allows a caller to submit reports on behalf of any adviser.
+pub const PARAPLANNER_ROLE: &str = "paraplanner";
+
+const FIRST_NAME_CLAIMS: [&str; 3] = ["given_name", "givenName", "custom:first_name"];
+const LAST_NAME_CLAIMS: [&str; 3] = ["family_name", "familyName", "custom:last_name"];
+const ROLE_CLAIMS: [&str; 3] = ["cognito:groups", "custom:roles", "roles"];
+
+/// The identity of the caller as asserted by the API Gateway authorizer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthenticatedCaller {
+    pub first_name: String,
+    pub last_name: String,
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuthError {
+    MissingClaims,
+    MissingClaim(&'static str),
+    AdviserMismatch,
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::MissingClaims => write!(f, "No authorizer claims present on the request"),
+            AuthError::MissingClaim(claim) => write!(f, "Authorizer claims did not include the caller's {}", claim),
+            AuthError::AdviserMismatch => write!(f, "Reports may only be submitted for the authenticated adviser"),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+impl AuthenticatedCaller {
+    /// Resolves the caller from the authorizer claims attached to the request context.
+    pub fn from_request(request: &Request) -> Result<Self, AuthError> {
+        let claims = match request.request_context_ref() {
+            Some(RequestContext::ApiGatewayV1(context)) => authorizer_claims(&context.authorizer.fields),
+            None => return Err(AuthError::MissingClaims),
+        };
+
+        Self::from_claims(&claims)
+    }
+
+    pub fn from_claims(claims: &HashMap<String, String>) -> Result<Self, AuthError> {
+        if claims.is_empty() {
+            return Err(AuthError::MissingClaims);
+        }
+
+        let first_name = first_claim(claims, &FIRST_NAME_CLAIMS).ok_or(AuthError::MissingClaim("first name"))?;
+        let last_name = first_claim(claims, &LAST_NAME_CLAIMS).ok_or(AuthError::MissingClaim("last name"))?;
+        let roles = first_claim(claims, &ROLE_CLAIMS)
+            .map(|roles| parse_roles(&roles))
+            .unwrap_or_default();
+
+        Ok(Self { first_name, last_name, roles })
+    }
+
+    pub fn is_paraplanner(&self) -> bool {
+        self.roles.iter().any(|role| role.eq_ignore_ascii_case(PARAPLANNER_ROLE))
+    }
+
+    pub fn is_adviser(&self, adviser: &AdviserDataTransferObject) -> bool {
+        names_match(&self.first_name, &adviser.adviser_first_name)
+            && names_match(&self.last_name, &adviser.adviser_last_name)
+    }
+
+    /// Checks that the caller may submit a report naming the given adviser.
+    /// Paraplanners prepare reports for advisers, so they are exempt from the check.
+    pub fn authorise_adviser(&self, adviser: &AdviserDataTransferObject) -> Result<(), AuthError> {
+        if self.is_paraplanner() || self.is_adviser(adviser) {
+            Ok(())
+        } else {
+            Err(AuthError::AdviserMismatch)
+        }
+    }
+}
+
+/// Cognito user pool authorizers nest the token claims under `claims`, whereas Lambda
+/// authorizers place their context values directly on the authorizer.
+fn authorizer_claims(fields: &HashMap<String, Value>) -> HashMap<String, String> {
+    let source = match fields.get("claims") {
+        Some(Value::Object(claims)) => claims.iter().collect::<Vec<_>>(),
+        _ => fields.iter().collect::<Vec<_>>(),
+    };
+
+    source
+        .into_iter()
+        .filter_map(|(key, value)| match value {
+            Value::String(value) => Some((key.clone(), value.clone())),
+            Value::Array(values) => Some((
+                key.clone(),
+                values.iter().filter_map(Value::as_str).collect::<Vec<_>>().join(","),
+            )),
+            Value::Null | Value::Object(_) => None,
+            other => Some((key.clone(), other.to_string())),
+        })
+        .collect()
+}
+
+fn first_claim(claims: &HashMap<String, String>, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .filter_map(|key| claims.get(*key))
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+}
+
+/// Group claims arrive either comma separated or in the bracketed form `[a b]`.
+fn parse_roles(roles: &str) -> Vec<String> {
+    roles
+        .trim_matches(|c| c == '[' || c == ']')
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|role| !role.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn names_match(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn adviser(first: &str, last: &str) -> AdviserDataTransferObject {
+        AdviserDataTransferObject {
+            id: None,
+            adviser_first_name: first.to_string(),
+            adviser_last_name: last.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_missing_claims() {
+        assert_eq!(AuthenticatedCaller::from_claims(&HashMap::new()), Err(AuthError::MissingClaims));
+        assert_eq!(
+            AuthenticatedCaller::from_claims(&claims(&[("given_name", "Jane")])),
+            Err(AuthError::MissingClaim("last name"))
+        );
+    }
+
+    #[test]
+    fn test_adviser_may_submit_own_report() {
+        let caller = AuthenticatedCaller::from_claims(&claims(&[("given_name", "Jane"), ("family_name", "Smith")])).unwrap();
+        assert!(caller.authorise_adviser(&adviser("jane", " Smith ")).is_ok());
+        assert_eq!(caller.authorise_adviser(&adviser("John", "Smith")), Err(AuthError::AdviserMismatch));
+    }
+
+    #[test]
+    fn test_paraplanner_may_submit_for_any_adviser() {
+        let caller = AuthenticatedCaller::from_claims(&claims(&[
+            ("given_name", "Pat"),
+            ("family_name", "Jones"),
+            ("cognito:groups", "[staff Paraplanner]"),
+        ]))
+        .unwrap();
+        assert!(caller.is_paraplanner());
+        assert!(caller.authorise_adviser(&adviser("Jane", "Smith")).is_ok());
+    }
+
+    #[test]
+    fn test_request_with_cognito_claims() {
+        let request = lambda_http::request::from_str(
+            r#"{
+                "resource": "/",
+                "path": "/",
+                "httpMethod": "POST",
+                "headers": {},
+                "multiValueHeaders": {},
+                "requestContext": {
+                    "httpMethod": "POST",
+                    "authorizer": {
+                        "claims": { "given_name": "Jane", "family_name": "Smith", "cognito:groups": "adviser" }
+                    }
+                },
+                "body": null,
+                "isBase64Encoded": false
+            }"#,
+        )
+        .unwrap();
+
+        let caller = AuthenticatedCaller::from_request(&request).unwrap();
+        assert_eq!(caller.first_name, "Jane");
+        assert_eq!(caller.roles, vec!["adviser".to_string()]);
+        assert!(!caller.is_paraplanner());
+    }
+
+    #[test]
+    fn test_request_without_context() {
+        assert_eq!(AuthenticatedCaller::from_request(&Request::default()), Err(AuthError::MissingClaims));
+    }
+}
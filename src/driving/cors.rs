@@ -0,0 +1,64 @@
+use http::{header, HeaderMap};
+
+/// Environment variable holding a comma separated list of origins allowed to call the API.
+pub const ALLOWED_ORIGINS_ENV: &str = "CORS_ALLOWED_ORIGINS";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CorsConfig {
+    allowed_origins: Vec<String>,
+}
+
+impl CorsConfig {
+    pub fn new(allowed_origins: Vec<String>) -> Self {
+        Self {
+            allowed_origins: allowed_origins
+                .into_iter()
+                .map(|origin| origin.trim().trim_end_matches('/').to_string())
+                .filter(|origin| !origin.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Reads the allow-list from `CORS_ALLOWED_ORIGINS`. With no configuration no origin is allowed.
+    pub fn from_env() -> Self {
+        let origins = std::env::var(ALLOWED_ORIGINS_ENV).unwrap_or_default();
+        Self::new(origins.split(',').map(str::to_string).collect())
+    }
+
+    /// Returns the value for `Access-Control-Allow-Origin` if the request origin is allowed.
+    pub fn allowed_origin(&self, request_headers: &HeaderMap) -> Option<String> {
+        let origin = request_headers.get(header::ORIGIN)?.to_str().ok()?.trim_end_matches('/');
+
+        self.allowed_origins
+            .iter()
+            .find(|allowed| allowed.as_str() == "*" || allowed.eq_ignore_ascii_case(origin))
+            .map(|_| origin.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    fn headers(origin: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ORIGIN, HeaderValue::from_str(origin).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_allowed_origin() {
+        let config = CorsConfig::new(vec!["https://reports.example.com/".to_string(), " https://admin.example.com".to_string()]);
+        assert_eq!(config.allowed_origin(&headers("https://reports.example.com")), Some("https://reports.example.com".to_string()));
+        assert_eq!(config.allowed_origin(&headers("https://admin.example.com")), Some("https://admin.example.com".to_string()));
+    }
+
+    #[test]
+    fn test_disallowed_or_missing_origin() {
+        let config = CorsConfig::new(vec!["https://reports.example.com".to_string()]);
+        assert_eq!(config.allowed_origin(&headers("https://evil.example.com")), None);
+        assert_eq!(config.allowed_origin(&HeaderMap::new()), None);
+        assert_eq!(CorsConfig::default().allowed_origin(&headers("https://reports.example.com")), None);
+    }
+}
@@ -1,12 +1,64 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde_json::Value;
+
+use crate::domain::clock::{Clock, FixedClock, SystemClock};
+use crate::driven::doc_generator::OutputFormat;
 
 pub mod report_type_data_transfer_object;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DataTransferObject {
     // pub id: Uuid,
-    pub report_type: report_type_data_transfer_object::ReportTypeDataTransferObject
+    pub report_type: report_type_data_transfer_object::ReportTypeDataTransferObject,
+    /// Formats to generate. When omitted a single docx link is returned, as before formats
+    /// could be requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_formats: Option<Vec<OutputFormat>>,
+    /// The date to prepare the report as at, which date checks such as whether a tax year has
+    /// ended are made against. When omitted the report is prepared as at today.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_at: Option<NaiveDate>
+}
+
+impl DataTransferObject {
+    /// The clock to build the report with: fixed at `asAt` when given, otherwise the system clock.
+    pub fn clock(&self) -> Box<dyn Clock> {
+        match self.as_at {
+            Some(as_at) => Box::new(FixedClock(as_at)),
+            None => Box::new(SystemClock)
+        }
+    }
+
+    /// The JSON Schema for the request body, published for each release as
+    /// `schema/request.schema.json`.
+    pub fn json_schema() -> Value {
+        let mut schema = schemars::schema_for!(DataTransferObject);
+        schema.insert("$id".to_string(), Value::String("https://gcwm.co.uk/schemas/report-request.schema.json".to_string()));
+        schema.insert("title".to_string(), Value::String(format!("Report request {}", env!("CARGO_PKG_VERSION"))));
+        schema.to_value()
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driven::doc_generator::instructions::tests::assert_golden;
 
+    #[test]
+    fn test_schema_is_up_to_date() {
+        assert_golden("request.schema.json", &DataTransferObject::json_schema());
+    }
+
+    #[test]
+    fn test_schema_follows_serde_attributes() {
+        let schema = DataTransferObject::json_schema().to_string();
+
+        assert!(schema.contains(r#""required":["reportType"]"#));
+        assert!(schema.contains(r#""enum":["docx","html","pdf","markdown"]"#));
+        assert!(schema.contains(r#""investmentStrategyProvider""#));
+        assert!(schema.contains(r#""coupleAnnualReviewReport""#));
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AdviceAreasDto(Vec<AdviceAreaDto>);
 
@@ -14,7 +15,7 @@ impl AdviceAreasDto {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum AdviceAreaDto {
@@ -25,31 +26,42 @@ pub enum AdviceAreaDto {
     Other(OtherAdvice)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IhtAdvice {
     pub advice: String
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct WillAdvice {
     pub advice: String
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct EmergencyFundAdvice {
-    pub advice: String
+    /// The adviser's own advice, used in place of the standard paragraph
+    pub advice: Option<String>,
+    pub calculation: Option<EmergencyFundCalculationDto>
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EmergencyFundCalculationDto {
+    pub monthly_essential_expenditure: f64,
+    pub target_months: u32,
+    /// Cash held outside Cash ISAs, such as in current and savings accounts
+    pub other_cash: f64
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PoaAdvice {
     pub advice: String
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OtherAdvice {
     pub advice_description: String,
@@ -1,18 +1,79 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
 use super::{advice_areas::{AdviceAreaDto, AdviceAreasDto}, product::{ExistingNewJointSingleProductDto, ProductsDto}};
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CoupleAdviceAreasAndProductsDto {
     pub client_1: Option<AdviceAreasAndProductsDto>,
     pub client_2: Option<AdviceAreasAndProductsDto>,
-    pub joint: Option<AdviceAreasAndProductsDto>
+    pub joint: Option<AdviceAreasAndProductsDto>,
+    /// Needed to estimate Inheritance Tax, as spouses and civil partners can leave their estates to each other tax free
+    pub married_or_in_civil_partnership: Option<bool>
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AdviceAreasAndProductsDto {
     pub advice_areas: Option<AdviceAreasDto>,
-    pub products: Option<ProductsDto>
-}
\ No newline at end of file
+    pub products: Option<ProductsDto>,
+    pub pension_allowances: Option<Vec<PensionAllowanceBasisDto>>,
+    pub iht_estate: Option<IhtEstateBasisDto>
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PensionAllowanceBasisDto {
+    pub tax_year: String,
+    pub relevant_earnings: f64,
+    pub threshold_income: f64,
+    pub adjusted_income: f64,
+    pub flexibly_accessed: bool,
+    pub unused_allowance: UnusedPensionAllowanceDto
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UnusedPensionAllowanceDto {
+    pub three_years_before: f64,
+    pub two_years_before: f64,
+    pub one_year_before: f64
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IhtEstateBasisDto {
+    /// The client's share of their home
+    pub main_residence: Option<MainResidenceDto>,
+    /// Assets other than the products in the report, such as property, cash and chattels
+    pub other_assets: Vec<DeclaredAssetDto>,
+    pub liabilities: f64,
+    /// Gifts made in the last seven years, after any exemptions
+    pub gifts: Vec<GiftDto>,
+    /// The percentage of a late spouse or civil partner's nil-rate band transferred to the client
+    pub transferred_nil_rate_band: f64,
+    pub transferred_residence_nil_rate_band: f64
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MainResidenceDto {
+    pub value: f64,
+    pub passes_to_direct_descendants: bool
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclaredAssetDto {
+    pub description: String,
+    pub value: f64
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GiftDto {
+    /// DD/MM/YYYY
+    pub date: String,
+    pub value: f64
+}
@@ -1,11 +1,28 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 // use uuid::Uuid;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AdviserDataTransferObject {
-    //#[serde(with = "uuid::serde::simple")]
-    //id: uuid::Uuid,
+    /// Key into the adviser directory. When omitted the report carries the adviser's name only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     pub adviser_first_name: String,
     pub adviser_last_name: String
-}
\ No newline at end of file
+}
+
+/// An adviser directory entry as held in the persistence system.
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdviserProfileDto {
+    pub id: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub qualifications: Vec<String>,
+    pub fca_reference_number: String,
+    pub phone: String,
+    pub email: String,
+    pub office: String,
+    pub signature_image_location: Option<String>
+}
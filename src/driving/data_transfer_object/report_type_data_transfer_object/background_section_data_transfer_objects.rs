@@ -1,8 +1,9 @@
 use std::fmt;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
-// #[derive(Deserialize, Serialize, Debug, Clone)]
+// #[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 // #[serde(rename_all = "camelCase")]
 // pub struct AnnualReviewBackgroundSectionDataTransferObject{
 //     pub meeting_location: MeetingLocationDataTransferObject,
@@ -11,7 +12,7 @@ use serde::{Deserialize, Serialize};
 //     pub meeting_date: String
 // }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(tag = "type")]
 pub enum MeetingLocationDataTransferObject{
     Home(HomeMeetingLocationDataTransferObject),
@@ -31,19 +32,19 @@ impl fmt::Display for MeetingLocationDataTransferObject {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct HomeMeetingLocationDataTransferObject{
     pub town: String,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OtherLocationDataTransferObject{
     pub other_location: String,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AdditionalMeetingAttendeeDataTransferObject {
     pub first_name: String,
@@ -51,7 +52,7 @@ pub struct AdditionalMeetingAttendeeDataTransferObject {
     pub relationship_to_client: RelationshipToClientDataTransferObject
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(tag = "type")]
 pub enum RelationshipToClientDataTransferObject {
     Accountant,
@@ -59,13 +60,13 @@ pub enum RelationshipToClientDataTransferObject {
     Other(OtherRelationshipToClientDataTransferObject)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OtherRelationshipToClientDataTransferObject {
     pub description_of_relationship: String
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AdditionalCompanyMeetingAttendeeDataTransferObject {
     pub first_name: String,
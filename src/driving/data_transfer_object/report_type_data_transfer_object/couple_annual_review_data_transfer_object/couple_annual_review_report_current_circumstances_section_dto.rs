@@ -1,14 +1,18 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use chrono::NaiveDate;
 
 use crate::driving::data_transfer_object::report_type_data_transfer_object::{current_circumstances_section_dto::{CoupleIsChangeRiskToleranceDto, IsChangeInCircumstancesDto, IsChangeRiskToleranceDto}, objectives_dto::{ChangeInObjectivesDto, CoupleObjectivesAnnualReviewDto}};
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CoupleAnnualReviewReportCurrentCircumstancesSectionDto {
     pub last_meeting_date: String,
     pub last_review_report_date: String,
     pub is_change_in_circumstances: IsChangeInCircumstancesDto,
     pub couple_objectives: CoupleObjectivesAnnualReviewDto,
-    pub couple_is_risk_tolerance_change: CoupleIsChangeRiskToleranceDto
+    pub couple_is_risk_tolerance_change: CoupleIsChangeRiskToleranceDto,
+    /// DD/MM/YYYY. Income objectives are only projected when the dates of birth they depend on are given.
+    pub client_1_date_of_birth: Option<String>,
+    pub client_2_date_of_birth: Option<String>
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
 pub mod couple_annual_review_report_sections_data_transfer_object;
 pub mod couple_annual_review_report_background_section_dto;
@@ -6,7 +7,7 @@ pub mod couple_annual_review_report_current_circumstances_section_dto;
 
 use crate::driving::data_transfer_object::report_type_data_transfer_object::adviser_data_transfer_object::AdviserDataTransferObject;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CoupleAnnualReviewReportDataTransferObject {
     pub individual_one_first_name: String,
@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
 use super::couple_new_report_background_section_dto::CoupleNewReportBackgroundSectionDto;
 use super::couple_new_report_current_circumstances_section_dto::CoupleNewReportCurrentCircumstancesSectionDto;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 
 pub struct CoupleNewReportSectionsDto {
     pub background: CoupleNewReportBackgroundSectionDto,
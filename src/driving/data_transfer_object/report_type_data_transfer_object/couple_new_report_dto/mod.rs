@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
 pub mod couple_new_report_sections_dto;
 pub mod couple_new_report_background_section_dto;
@@ -6,7 +7,7 @@ pub mod couple_new_report_current_circumstances_section_dto;
 
 use super::adviser_data_transfer_object::AdviserDataTransferObject;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CoupleNewReportDto {
     pub individual_one_first_name: String,
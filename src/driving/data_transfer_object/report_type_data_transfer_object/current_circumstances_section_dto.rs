@@ -1,17 +1,18 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
 use crate::{domain::report::{couple_annual_review_report::couple_annual_review_report_current_circumstances_section::CoupleAnnualReviewReportCurrentCircumstancesSection, current_circumstances_section::CurrentCircumstancesSection}, driving::data_transfer_object::report_type_data_transfer_object::couple_annual_review_data_transfer_object::couple_annual_review_report_current_circumstances_section_dto::CoupleAnnualReviewReportCurrentCircumstancesSectionDto};
 
 use super::risk_assessment_dto::RiskProfileDto;
 
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(untagged)]
 pub enum CurrentCircumstancesSectionDto {
     CoupleAnnualReviewReportCurrentCircumstancesSectionDto(CoupleAnnualReviewReportCurrentCircumstancesSectionDto),
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum IsChangeInCircumstancesDto {
@@ -20,20 +21,20 @@ pub enum IsChangeInCircumstancesDto {
     ChangeInCircumstances(ChangeInCircumstancesDto)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ChangeInCircumstancesDto {
     pub circumstances: Vec<String>
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CoupleIsChangeRiskToleranceDto {
     pub client_1: IsChangeRiskToleranceDto,
     pub client_2: IsChangeRiskToleranceDto
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum IsChangeRiskToleranceDto {
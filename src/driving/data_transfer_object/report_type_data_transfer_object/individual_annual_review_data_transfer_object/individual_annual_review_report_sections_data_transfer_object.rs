@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
 use super::individual_annual_review_report_background_section::IndividualAnnualReviewBackgroundSectionDataTransferObject;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 
 pub struct IndividualAnnualReviewReportSectionsDataTransferObject {
     pub background: IndividualAnnualReviewBackgroundSectionDataTransferObject
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
 pub mod individual_annual_review_report_sections_data_transfer_object;
 pub mod individual_annual_review_report_background_section;
@@ -8,7 +9,7 @@ use crate::driving::data_transfer_object::report_type_data_transfer_object::advi
 use crate::domain::report::report_type::ReportType;
 use crate::domain::report::individual_annual_review_report::IndividualAnnualReviewReport;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IndividualAnnualReviewReportDataTransferObject {
     pub individual_one_first_name: String,
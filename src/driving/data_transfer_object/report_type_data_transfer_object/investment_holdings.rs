@@ -1,11 +1,12 @@
 use std::fmt;
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
 use super::risk_assessment_dto::RiskProfileDto;
 
 /// The “model” metadata that identifies *which* GCWM portfolio you mean:
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 pub struct ModelPortfolioIdDto {
     pub provider: InvestmentStrategyProviderDto,       
     pub service_proposition: InvestmentStrategyServicePropositionDto,         
@@ -14,7 +15,7 @@ pub struct ModelPortfolioIdDto {
     pub product_type: InvestmentStrategyProductTypeDto,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(tag = "investmentStrategyProvider")]
 pub enum InvestmentStrategyProviderDto {
     Transact,
@@ -31,7 +32,7 @@ impl fmt::Display for InvestmentStrategyProviderDto {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(tag = "investmentStrategyProposition")]
 pub enum InvestmentStrategyServicePropositionDto {
     Prime,
@@ -48,7 +49,7 @@ impl fmt::Display for InvestmentStrategyServicePropositionDto {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(tag = "investmentStrategyProductType")]
 pub enum InvestmentStrategyProductTypeDto {
     Standard,
@@ -66,7 +67,7 @@ impl fmt::Display for InvestmentStrategyProductTypeDto {
 }
 
 /// A time‐stamped portfolio (the actual holdings & charges):
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 pub struct VersionedPortfolioDto {
     pub id: ModelPortfolioIdDto,
     pub effective_date: MonthYearDto, // e.g. Aug2024
@@ -74,7 +75,7 @@ pub struct VersionedPortfolioDto {
 }
 
 /// Bespoke portfolios don’t have an “id,” only the holdings & when they were created:
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 pub struct BespokePortfolioDto {
     pub created: MonthYearDto,
     pub portfolio: BespokeInvestmentPortfolioDto,
@@ -83,7 +84,7 @@ pub struct BespokePortfolioDto {
 }
 
 /// Finally, an investment strategy is just *one* of those two:
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 pub enum InvestmentStrategyDto {
   /// A GCWM model portfolio, possibly past (if `as_of < today`) or still current.
   Model(VersionedPortfolioDto),
@@ -92,25 +93,25 @@ pub enum InvestmentStrategyDto {
   Bespoke(BespokePortfolioDto),
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InvestmentPortfolioDto {
     pub fund_holdings: Vec<FundHoldingDto>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BespokeInvestmentPortfolioDto {
     pub fund_holdings: Option<Vec<FundHoldingDto>>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum MonthYearDto {
     Aug24
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FundHoldingDto {
     pub fund_name: String,
@@ -145,21 +146,21 @@ pub struct FundHoldingDto {
 
 
 
-// #[derive(Deserialize, Serialize, Debug, Clone)]
+// #[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 // #[serde(rename_all = "camelCase")]
 // pub enum InvestmentStrategyDto {
 //     PastInvestmentStrategy(PastInvestmentStrategyDto),
 //     InvestableInvestmentStrategy(InvestableInvestmentStrategyDto)
 // }
 
-// #[derive(Deserialize, Serialize, Debug, Clone)]
+// #[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 // #[serde(rename_all = "camelCase")]
 // pub enum PastInvestmentStrategyDto {
 //     BespokeInvestmentStrategy(InvestmentPortfolioDto),
 //     GCWMInvestmentStrategy(GCWMPastInvestmentStrategyDto)
 // }
 
-// #[derive(Deserialize, Serialize, Debug, Clone)]
+// #[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 // #[serde(rename_all = "camelCase")]
 // pub enum InvestableInvestmentStrategyDto {
 //     BespokeInvestmentStrategy(InvestmentPortfolioDto),
@@ -168,7 +169,7 @@ pub struct FundHoldingDto {
 
 
 
-// #[derive(Deserialize, Serialize, Debug, Clone)]
+// #[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 // #[serde(rename_all = "camelCase")]
 // pub enum GCWMPastInvestmentStrategyDto {
 //     TransactPrimeCautious(StrategyMonthYearDto),
@@ -233,7 +234,7 @@ pub struct FundHoldingDto {
 //     AbrdnSippActiveSriAdventurous(StrategyMonthYearDto),
 // }
 
-// #[derive(Deserialize, Serialize, Debug, Clone)]
+// #[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 // #[serde(rename_all = "camelCase")]
 // pub enum GCWMPresentInvestmentStrategyDto {
 //     TransactPrimeCautious(StrategyMonthYearDto),
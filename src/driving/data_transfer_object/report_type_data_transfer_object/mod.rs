@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
 pub mod background_section_data_transfer_objects;
 pub mod individual_annual_review_data_transfer_object;
@@ -17,7 +18,7 @@ use couple_annual_review_data_transfer_object::CoupleAnnualReviewReportDataTrans
 use individual_annual_review_data_transfer_object::IndividualAnnualReviewReportDataTransferObject;
 use couple_new_report_dto::CoupleNewReportDto;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(tag = "type")]
 pub enum ReportTypeDataTransferObject {
     #[serde(rename(serialize = "coupleAnnualReviewReport", deserialize = "coupleAnnualReviewReport"))]
@@ -30,3 +31,30 @@ pub enum ReportTypeDataTransferObject {
 
 
 
+
+impl ReportTypeDataTransferObject {
+    pub fn adviser(&self) -> &adviser_data_transfer_object::AdviserDataTransferObject {
+        match self {
+            ReportTypeDataTransferObject::CoupleAnnualReviewReportDataTransferObject(dto) => &dto.adviser,
+            ReportTypeDataTransferObject::IndividualAnnualReviewReportDataTransferObject(dto) => &dto.adviser,
+            ReportTypeDataTransferObject::CoupleNewReportDto(dto) => &dto.adviser,
+        }
+    }
+
+    /// Full names of the clients the report is prepared for.
+    pub fn client_names(&self) -> Vec<String> {
+        match self {
+            ReportTypeDataTransferObject::CoupleAnnualReviewReportDataTransferObject(dto) => vec![
+                format!("{} {}", dto.individual_one_first_name, dto.individual_one_last_name),
+                format!("{} {}", dto.individual_two_first_name, dto.individual_two_last_name)
+            ],
+            ReportTypeDataTransferObject::IndividualAnnualReviewReportDataTransferObject(dto) => vec![
+                format!("{} {}", dto.individual_one_first_name, dto.individual_one_last_name)
+            ],
+            ReportTypeDataTransferObject::CoupleNewReportDto(dto) => vec![
+                format!("{} {}", dto.individual_one_first_name, dto.individual_one_last_name),
+                format!("{} {}", dto.individual_two_first_name, dto.individual_two_last_name)
+            ],
+        }
+    }
+}
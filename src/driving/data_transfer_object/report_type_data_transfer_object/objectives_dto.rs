@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use uuid::Uuid;
 
 use super::risk_assessment_dto::RiskProfileDto;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CoupleObjectivesAnnualReviewDto {
     pub client_1_objectives: Option<ChangeInObjectivesDto>,
@@ -11,7 +12,7 @@ pub struct CoupleObjectivesAnnualReviewDto {
     pub shared_objectives: Option<ChangeInObjectivesDto>
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type", content = "objectives")]
 pub enum ChangeInObjectivesDto {
@@ -19,7 +20,7 @@ pub enum ChangeInObjectivesDto {
     ChangeInObjectives(Vec<ObjectiveTypeDto>)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum ObjectiveTypeDto {
@@ -31,7 +32,7 @@ pub enum ObjectiveTypeDto {
     OtherObjective(OtherObjectiveDto)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CoupleIncomeObjectiveDto {
     pub id: Uuid,
@@ -41,7 +42,7 @@ pub struct CoupleIncomeObjectiveDto {
     pub from_age: Option<ClientFromAgeDto>,
     pub linked_risk_profile: RiskProfileDto
 }
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum ClientFromAgeDto {
@@ -49,7 +50,7 @@ pub enum ClientFromAgeDto {
     Client2(i32)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IncomeObjectiveDto {
     pub id: Uuid,
@@ -60,7 +61,7 @@ pub struct IncomeObjectiveDto {
     pub linked_risk_profile: RiskProfileDto
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InRetirementIncomeObjectiveDto {
     pub id: Uuid,
@@ -69,7 +70,7 @@ pub struct InRetirementIncomeObjectiveDto {
     pub linked_risk_profile: RiskProfileDto
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OtherObjectiveDto {
     pub id: Uuid,
@@ -78,14 +79,14 @@ pub struct OtherObjectiveDto {
     pub linked_risk_profile: RiskProfileDto
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IhtObjectiveDto {
     pub id: Uuid,
     pub linked_risk_profile: RiskProfileDto
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CapitalProtectionObjectiveDto {
     pub id: Uuid,
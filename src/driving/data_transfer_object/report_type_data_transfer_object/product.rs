@@ -1,5 +1,6 @@
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use uuid::Uuid;
 
 use super::risk_assessment_dto::RiskProfileDto;
@@ -8,7 +9,7 @@ use super::investment_holdings::{
     InvestmentStrategyDto
 };
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 pub struct ProductsDto(Vec<ExistingNewJointSingleProductDto>);
 
 impl ProductsDto {
@@ -22,7 +23,7 @@ impl ProductsDto {
 }
 
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum ExistingNewJointSingleProductDto {
@@ -31,7 +32,7 @@ pub enum ExistingNewJointSingleProductDto {
     NewSingleOwnedProduct(NewSingleOwnedProductDto)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 pub struct ExistingJointlyOwnedProductDto {
     pub id: String, 
     pub platform_or_account_number: Option<PlatformAccountNumberTypeDto>,
@@ -39,7 +40,7 @@ pub struct ExistingJointlyOwnedProductDto {
     pub account_type: CanBeJointlyOwnedAccountTypeDto
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 pub struct ExistingSingleOwnedProductDto {
     pub id: String,
     pub platform_or_account_number: Option<PlatformAccountNumberTypeDto>,
@@ -47,7 +48,7 @@ pub struct ExistingSingleOwnedProductDto {
     pub account_type: AccountTypeDto
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 pub struct NewSingleOwnedProductDto {
     pub id: String,
     pub recommendations: NewProductRecommendationsDto,
@@ -56,14 +57,14 @@ pub struct NewSingleOwnedProductDto {
     pub account_type: AccountTypeDto
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 pub enum CanBeJointlyOwnedAccountTypeDto {
     GeneralInvestmentAccount(GeneralInvestmentAccountDto),
     OnshoreInvestmentBond(OnshoreInvestmentBondDto),
     OffshoreInvestmentBond(OffshoreInvestmentBondDto),
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 pub enum AccountTypeDto {
     IsaStocksAndShares(IsaStocksAndSharesDto),
     SelfInvestedPersonalPension(SelfInvestedPersonalPensionDto),
@@ -75,7 +76,7 @@ pub enum AccountTypeDto {
     OffshoreInvestmentBond(OffshoreInvestmentBondDto),
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IsaStocksAndSharesDto {
     pub provider: ProviderDto,
@@ -87,7 +88,7 @@ pub struct IsaStocksAndSharesDto {
     pub recommendations: ExistingProductRecommendationsDto,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GeneralInvestmentAccountDto {
     pub ownership: OwnershipDto,
@@ -98,10 +99,11 @@ pub struct GeneralInvestmentAccountDto {
     pub linked_cash_or_fee_payment_wrapper: AccountOrReferenceNumberTypeDto,
     pub charges: ProductChargesDto,
     pub current_tax_position: CapitalGainsPositionDto,
+    pub capital_gains_tax_basis: Option<CapitalGainsTaxBasisDto>,
     pub recommendations: ExistingProductRecommendationsDto,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OnshoreInvestmentBondDto {
     pub ownership: OwnershipDto,
@@ -112,10 +114,11 @@ pub struct OnshoreInvestmentBondDto {
     pub linked_cash_or_fee_payment_wrapper: AccountOrReferenceNumberTypeDto,
     pub charges: ProductChargesDto,
     pub current_tax_position: ChargeableGainsPositionDto,
+    pub chargeable_gains_basis: Option<ChargeableGainsBasisDto>,
     pub recommendations: ExistingProductRecommendationsDto,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OffshoreInvestmentBondDto {
     pub ownership: OwnershipDto,
@@ -128,10 +131,11 @@ pub struct OffshoreInvestmentBondDto {
     pub linked_cash_or_fee_payment_wrapper: AccountOrReferenceNumberTypeDto,
     pub charges: ProductChargesDto,
     pub current_tax_position: ChargeableGainsPositionDto,
+    pub chargeable_gains_basis: Option<ChargeableGainsBasisDto>,
     pub recommendations: ExistingProductRecommendationsDto,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SelfInvestedPersonalPensionDto {
     pub provider: ProviderDto,
@@ -143,7 +147,7 @@ pub struct SelfInvestedPersonalPensionDto {
     pub recommendations: ExistingProductRecommendationsDto,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PersonalPensionDto {
     pub provider: ProviderDto,
@@ -155,10 +159,11 @@ pub struct PersonalPensionDto {
     pub recommendations: ExistingProductRecommendationsDto,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct JuniorIsaStocksAndSharesDto {
     pub provider: ProviderDto,
+    pub child: String,
     pub optional_description: Option<String>,
     pub current_investment_strategy: InvestmentStrategyDto,
     pub current_value: ValuationDto,
@@ -167,7 +172,7 @@ pub struct JuniorIsaStocksAndSharesDto {
     pub recommendations: ExistingProductRecommendationsDto,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CashIsaDto {
     pub provider: ProviderDto,
@@ -178,7 +183,7 @@ pub struct CashIsaDto {
     pub recommendations: ExistingProductRecommendationsDto,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OwnershipDto {
     pub client_first_name: String,
@@ -186,7 +191,7 @@ pub struct OwnershipDto {
     pub percentage_owned: f32
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProviderDto(ProvidersDto);
 
@@ -201,7 +206,7 @@ impl ProviderDto {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum ProvidersDto {
@@ -214,14 +219,14 @@ pub enum ProvidersDto {
     JamesHay
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ValuationDto {
     pub value: f64,
     pub date_of_valuation: String
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProductChargesDto {
     pub ongoing_advice_charge: f32,
@@ -230,13 +235,13 @@ pub struct ProductChargesDto {
     pub other_charges: Option<OtherChargeDto>
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CurrentProductTaxPositionDto {
     pub product_tax_position: ProductTaxPositionDto
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ExistingProductRecommendationsDto {
     //pub recommended_product_charges: ProductChargesDto,
@@ -246,7 +251,7 @@ pub struct ExistingProductRecommendationsDto {
     //pub recommendation_actions: Vec<RecommendedActionDto>
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NewProductRecommendationsDto {
     pub rationale: String,
@@ -256,21 +261,21 @@ pub struct NewProductRecommendationsDto {
     pub recommendation_actions: Vec<RecommendedActionDto>
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OtherChargeDto {
     pub ongoing_charges: Option<Vec<OngoingChargeDto>>,
     pub incidental_charges: Option<Vec<IncidentalChargeDto>>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RecommendedInvestmentAndRiskStrategyDto {
     pub recommended_investment_strategy: InvestmentStrategyDto,
     pub risk_level: RiskProfileDto
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum CapitalGainsPositionDto {
@@ -279,7 +284,29 @@ pub enum CapitalGainsPositionDto {
     CapitalGainsTaxIncurLiability(CapitalGainsTaxIncurLiabilityDto)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CapitalGainsTaxBasisDto {
+    pub base_cost: f64,
+    pub tax_year: String,
+    pub taxable_income: f64,
+    pub other_gains: f64,
+    pub other_losses: f64
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChargeableGainsBasisDto {
+    pub premiums_paid: f64,
+    pub policy_years: u32,
+    pub previous_withdrawals: f64,
+    pub previous_chargeable_gains: f64,
+    pub last_excess_event_policy_year: Option<u32>,
+    pub tax_year: String,
+    pub taxable_income: f64
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum ChargeableGainsPositionDto {
@@ -288,7 +315,7 @@ pub enum ChargeableGainsPositionDto {
     ChargeableGainsTaxIncurLiability(ChargeableGainsTaxIncurLiabilityDto)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type", content = "content")]
 pub enum ProductRetentionDto {
@@ -298,7 +325,7 @@ pub enum ProductRetentionDto {
     //PartialTransfer(PartialTransferDto)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum RecommendedActionDto {
@@ -310,7 +337,7 @@ pub enum RecommendedActionDto {
     StopWithdrawal(StopWithdrawalDto)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type", content = "content")]
 pub enum ProductTaxPositionDto {
@@ -318,7 +345,7 @@ pub enum ProductTaxPositionDto {
     ChargeableGainsPositionDto(ChargeableGainsPositionDto)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OngoingChargeDto {
     pub charge_description: String,
@@ -326,7 +353,7 @@ pub struct OngoingChargeDto {
     pub frequency: String
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IncidentalChargeDto {
     pub charge_description: String,
@@ -335,49 +362,49 @@ pub struct IncidentalChargeDto {
     pub trigger_event: String
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CapitalGainsTaxAvoidLiabilityDto {
     pub unrealised_gains: f32,
     pub capital_gains_tax_discussion: String
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CapitalGainsTaxNoLiabilityDto {
     pub unrealised_gains: f32,
     pub capital_gains_tax_discussion: String
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CapitalGainsTaxIncurLiabilityDto {
     pub unrealised_gains: f32,
     pub capital_gains_tax_discussion: String
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ChargeableGainsTaxAvoidLiabilityDto {
     pub unrealised_gains: f32,
     pub chargeable_gains_tax_discussion: String
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ChargeableGainsTaxNoLiabilityDto {
     pub unrealised_gains: f32,
     pub chargeable_gains_tax_discussion: String
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ChargeableGainsTaxIncurLiabilityDto {
     pub unrealised_gains: f32,
     pub chargeable_gains_tax_discussion: String
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RetainDto {
     pub rationale: String,
@@ -387,35 +414,35 @@ pub struct RetainDto {
     pub recommendation_actions: Option<Vec<RecommendedActionDto>>
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum RealignOrRebalanceDto {
     Realign(RealignDto),
     Rebalance(RebalanceDto)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RealignDto {
     pub rationale: String,
     pub recommended_investment_strategy: InvestmentStrategyDto
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RebalanceDto {
     pub rationale: String,
     pub recommended_investment_strategy: InvestmentStrategyDto
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum ReplaceDto {
     FullyReplace(FullyReplaceDto),
     PartiallyReplace(PartiallyReplaceDto)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FullyReplaceDto {
     pub rationale: String,
@@ -424,34 +451,34 @@ pub struct FullyReplaceDto {
     pub linked_objectives: Vec<String>
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FullyReplaceDetailDto {
     pub method_of_transfer: Vec<MethodOfTransferDto>,
     pub transfer_to_account_or_reference_number: AccountOrReferenceNumberTypeDto
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum MethodOfTransferDto {
     InSpecieMethod(InSpecieMethodDto),
     CashMethod(CashMethodDto)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InSpecieMethodDto {
     pub value: f64,
     pub funds_to_inspecie_transfer: Vec<FundHoldingDto>
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CashMethodDto {
     pub value: f64
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PartiallyReplaceDto {
     pub rationale: String,
@@ -460,7 +487,7 @@ pub struct PartiallyReplaceDto {
     pub linked_objectives: Vec<String>
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PartiallyReplaceDetailDto {
     pub method_of_transfer: Vec<MethodOfTransferDto>,
@@ -469,7 +496,7 @@ pub struct PartiallyReplaceDetailDto {
     pub reason_for_leaving_in_existing_product: String
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PartialTransferDto {
     pub rationale: String,
@@ -477,23 +504,26 @@ pub struct PartialTransferDto {
     pub replacement_product_information: ReplacementProductInformationDto
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FullyEncashDto {
     pub rationale: String,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SingleWithdrawalDto {
     pub value: f64,
     pub executive_summary_description: String,
     pub rationale: String,
     pub date_of_action: Option<String>,
-    pub tax_year_of_action: Option<String>
+    pub tax_year_of_action: Option<String>,
+    /// Whether the withdrawal is given away, taking it out of the client's estate
+    #[serde(default)]
+    pub gifted: bool
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SingleContributionDto {
     pub value: f64,
@@ -503,7 +533,7 @@ pub struct SingleContributionDto {
     pub tax_year_of_action: Option<String>
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RegularContributionDto {
     pub value: f64,
@@ -515,7 +545,7 @@ pub struct RegularContributionDto {
     pub end_date_of_action: Option<String>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RegularWithdrawalDto {
     pub value: f64,
@@ -525,9 +555,12 @@ pub struct RegularWithdrawalDto {
     pub start_date_of_action: String,
     pub tax_year_of_action: Option<String>,
     pub end_date_of_action: Option<String>,
+    /// Whether the withdrawals are given away, taking them out of the client's estate
+    #[serde(default)]
+    pub gifted: bool
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TransferDto {
     pub id: String,
@@ -540,7 +573,7 @@ pub struct TransferDto {
     pub transfer_details: TransferDetailDto
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct StopWithdrawalDto {
     pub value: f64,
@@ -551,35 +584,35 @@ pub struct StopWithdrawalDto {
     pub end_date_of_action: Option<String>,
 }
 
-// #[derive(Deserialize, Serialize, Debug, Clone)]
+// #[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 // #[serde(rename_all = "camelCase")]
 // pub struct InvestmentStrategyDto {
 //     pub risk_level: RiskProfileDto,
 //     pub fund_allocations: BespokeOrFirmInvestmentStrategyDto
 // }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TransferDetailDto {
     pub transfer_to_account_or_reference_number: AccountOrReferenceNumberTypeDto,
     pub transfer_from_account_or_reference_number: KnownOrUnknownAccountDto,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum KnownOrUnknownAccountDto {
     Known(AccountOrReferenceNumberTypeDto),
     Unknown { description: String, account_type: String }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum ReplacementProductInformationDto {
     PensionReplacementProductInformation(PensionReplacementProductInformationDto),
     InvestmentReplacementProductInformation(InvestmentReplacementProductInformationDto)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type", content = "content")]
 pub enum BespokeOrFirmInvestmentStrategyDto {
@@ -587,21 +620,21 @@ pub enum BespokeOrFirmInvestmentStrategyDto {
     FirmInvestmentStrategy(PresentFirmInvestmentStrategyDto)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BespokeInvestmentStrategyDto {
     pub description: String,
     pub fund_allocation: Option<FundHoldingDto>
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type", content = "content")]
 pub enum PresentFirmInvestmentStrategyDto {
     PrimeModerate(Vec<FundHoldingDto>)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PensionReplacementProductInformationDto {
     pub start_date: String,
@@ -632,7 +665,7 @@ pub struct PensionReplacementProductInformationDto {
     pub other_features: Vec<(String, String)>
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InvestmentReplacementProductInformationDto {
     pub start_date: String,
@@ -647,7 +680,7 @@ pub struct InvestmentReplacementProductInformationDto {
     pub other_features: Vec<(String, String)>
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type", content = "content")]
 pub enum PlatformAccountNumberTypeDto {
@@ -656,7 +689,7 @@ pub enum PlatformAccountNumberTypeDto {
     Other(String)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type", content = "content")]
 pub enum AccountOrReferenceNumberTypeDto {
@@ -667,7 +700,7 @@ pub enum AccountOrReferenceNumberTypeDto {
     NewAccount(String)
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type", content = "content")]
 pub enum PlatformOrAccountReferenceNumberTypeDto {
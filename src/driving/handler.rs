@@ -0,0 +1,327 @@
+use std::sync::Arc;
+
+use http::Method;
+use lambda_http::{ext::PayloadError, http::StatusCode, lambda_runtime, Error, Request, RequestExt, RequestPayloadExt, Response};
+use serde_json::{error::Category, json};
+use tracing::{info, warn, error, instrument};
+
+use crate::domain::generation_job::GenerationJob;
+use crate::domain::report::{adviser_profile::AdviserProfile, investment_holdings::InvestmentPortfolio, report_config::ReportConfig};
+use crate::driven::doc_generator::{generate_links, markdown::render_markdown, DocGenError, DocumentGenerator, OutputFormat};
+use crate::driven::job_dispatcher::JobDispatcher;
+use crate::driven::repository::{AdviserRepository, FindGenerationJob, GenerationJobRepository, InvestmentPortfoliosRepository, RepoSelectError};
+use crate::driving::data_transfer_object::DataTransferObject;
+use crate::driving::{auth::AuthenticatedCaller, cors::CorsConfig};
+use crate::{domain, driving, helpers};
+
+/// Handles one API request. Shared by the Lambda and the standalone server, which differ only
+/// in how requests arrive, which repositories they are given and how background jobs are
+/// handed over.
+#[instrument(skip(event, doc_gen, job_dispatcher))]
+#[allow(clippy::too_many_arguments)]
+pub async fn function_handler<R, A, J>(
+    event: Request, 
+    investment_portfolio_repo: Arc<R>,
+    adviser_repo: Arc<A>,
+    cors_config: Arc<CorsConfig>,
+    report_config: Arc<ReportConfig>,
+    doc_gen: Arc<dyn DocumentGenerator + Send + Sync>,
+    job_repo: Arc<J>,
+    job_dispatcher: Arc<dyn JobDispatcher + Send + Sync>
+) -> Result<Response<String>, Error> 
+where 
+    R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Send + Sync + 'static + std::fmt::Debug,
+    A: AdviserRepository<AdviserProfile> + Send + Sync + 'static + std::fmt::Debug,
+    J: GenerationJobRepository<GenerationJob> + Send + Sync + 'static + std::fmt::Debug,
+{
+
+    info!(method = %event.method(), path = %event.uri(), "received request");
+
+    let method = event.method();
+    let path_parameters = event.path_parameters();
+    let allowed_origin = cors_config.allowed_origin(event.headers());
+
+    enum PayloadType {
+        Test(DataTransferObject),
+        Production(Result<Option<DataTransferObject>, PayloadError>)
+    }
+
+    // let payload = if path_parameters.first("proxy") == Some("test") {
+    //     PayloadType::Test(helpers::test_helpers::create_mock_data_transfer_object())
+    // } else {
+    //     match event.payload::<driving::data_transfer_object::DataTransferObject>() {
+    //         Ok(payload) => {
+    //             info!(payload = ?payload, "deserialized request payload");
+    //             PayloadType::Production(Ok(payload))
+    //         }
+    //         Err(payload_error) => {
+    //             error!(error = ?payload_error, "failed to deserialize request");
+    //             PayloadType::Production(Err(payload_error))
+    //         }
+    //     }
+    // };
+
+    let payload = match event.payload::<driving::data_transfer_object::DataTransferObject>() {
+        Ok(payload) => {
+            info!(payload = ?payload, "deserialized request payload");
+            PayloadType::Production(Ok(payload))
+        }
+        Err(payload_error) => {
+            error!(error = ?payload_error, "failed to deserialize request");
+            PayloadType::Production(Err(payload_error))
+        }
+    };
+
+    //println!("Payload: {:?}", payload);
+
+    match method {
+        &Method::POST => {
+            // if !path_parameters.is_empty() {
+                
+            //     let unexpected_additional_parameters_response = helpers::response_helpers::message_response("Unexpected additional parameters included");
+
+            //     unexpected_additional_parameters_response
+
+            // } else {
+
+                match payload {
+                    PayloadType::Test(data_transfer_object) => {
+            
+                        let clock = data_transfer_object.clock();
+                        let report = domain::report::create_report::create_report(data_transfer_object.report_type, investment_portfolio_repo, adviser_repo, &report_config, clock.as_ref()).await?;
+
+                        let response = helpers::response_helpers::cors_response_builder(StatusCode::OK, allowed_origin.as_deref())
+                            .body(json!({
+                                "payload": report
+                            }).to_string())
+                            .map_err(Box::new)?;
+
+                        Ok(response)
+
+                    }
+                    PayloadType::Production(payload) => {
+                        match payload {
+                            Ok(data_transfer_object) => {
+                                match data_transfer_object {
+                                    Some(data_transfer_object) => {
+
+                                        // Only the authenticated adviser, or a paraplanner acting for them, may
+                                        // submit a report in an adviser's name
+
+                                        let caller = match AuthenticatedCaller::from_request(&event) {
+                                            Ok(caller) => caller,
+                                            Err(auth_error) => {
+                                                warn!(error = %auth_error, "rejected unauthenticated request");
+                                                return helpers::response_helpers::status_message_response(StatusCode::UNAUTHORIZED, &auth_error.to_string(), allowed_origin.as_deref());
+                                            }
+                                        };
+
+                                        if let Err(auth_error) = caller.authorise_adviser(data_transfer_object.report_type.adviser()) {
+                                            warn!(error = %auth_error, caller_last_name = %caller.last_name, "rejected report submitted for another adviser");
+                                            return helpers::response_helpers::status_message_response(StatusCode::FORBIDDEN, &auth_error.to_string(), allowed_origin.as_deref());
+                                        }
+
+                                        let adviser = data_transfer_object.report_type.adviser().clone();
+                                        let clock = data_transfer_object.clock();
+
+                                        let output_formats = match data_transfer_object.output_formats {
+                                            Some(formats) if formats.is_empty() => {
+                                                return helpers::response_helpers::status_message_response(StatusCode::BAD_REQUEST, "outputFormats must list at least one format", allowed_origin.as_deref());
+                                            }
+                                            Some(mut formats) => {
+                                                formats.sort();
+                                                formats.dedup();
+                                                Some(formats)
+                                            }
+                                            None => None
+                                        };
+
+                                        // Create report text, tables and apply domain logic ready to be sent to a document generator resposible for
+                                        // creating the the document, applying formatting and presentation etc 
+
+                                        let document_instructions = serde_json::to_value(
+                                            domain::report::create_report::create_report(data_transfer_object.report_type, investment_portfolio_repo, adviser_repo, &report_config, clock.as_ref()).await?.instructions()
+                                        ).map_err(|e| {
+                                            e
+                                        })?;
+        
+                                        // Call document generator here.  On successully completion a presigned url for downloading the document
+                                        // will be included in the response
+                                        
+                                        // Review tools read the Markdown rendition directly rather than via a download link
+
+                                        if event.uri().path().trim_end_matches('/').ends_with("/markdown") {
+                                            let markdown = render_markdown(&document_instructions).map_err(lambda_runtime::Error::from)?;
+                                            let response = helpers::response_helpers::cors_response_builder_with_content_type(StatusCode::OK, OutputFormat::Markdown.content_type(), allowed_origin.as_deref())
+                                                .body(markdown)
+                                                .map_err(Box::new)?;
+
+                                            return Ok(response);
+                                        }
+
+                                        // Large reports can take longer than API Gateway waits, so `/jobs` accepts the request
+                                        // and hands generation over to the job dispatcher. The caller polls `GET /jobs/{id}` for the link.
+
+                                        if event.uri().path().trim_end_matches('/').ends_with("/jobs") {
+                                            let job = job_repo
+                                                .create_job(GenerationJob::new(adviser, document_instructions, output_formats))
+                                                .await
+                                                .map_err(|e| lambda_runtime::Error::from(format!("Failed to create generation job: {:?}", e)))?;
+                                            info!(job_id = %job.id(), "accepted generation job");
+
+                                            if let Err(e) = job_dispatcher.dispatch(job.id()).await {
+                                                error!(job_id = %job.id(), error = %e, "failed to hand over generation job");
+                                                if let Err(e) = job_repo.update_job(job.fail(e.to_string())).await {
+                                                    error!(error = ?e, "failed to record generation job outcome");
+                                                }
+                                                return Err(lambda_runtime::Error::from(e));
+                                            }
+
+                                            let response = helpers::response_helpers::cors_response_builder(StatusCode::ACCEPTED, allowed_origin.as_deref())
+                                                .body(json!({
+                                                    "payload": { "jobId": job.id(), "status": job.status() }
+                                                }).to_string())
+                                                .map_err(Box::new)?;
+
+                                            return Ok(response);
+                                        }
+
+                                        let generated = generate_links(doc_gen.as_ref(), &document_instructions, output_formats.as_deref()).await;
+
+                                        let payload = match generated {
+                                            Ok(payload) => payload,
+                                            Err(DocGenError::UnsupportedFormat(format)) => {
+                                                warn!(format = %format, "requested format is not supported by the document generator");
+                                                return helpers::response_helpers::status_message_response(StatusCode::BAD_REQUEST, &DocGenError::UnsupportedFormat(format).to_string(), allowed_origin.as_deref());
+                                            }
+                                            Err(e @ DocGenError::Timeout(_)) => {
+                                                warn!(error = %e, "document generation timed out");
+                                                return helpers::response_helpers::status_message_response(StatusCode::GATEWAY_TIMEOUT, &e.to_string(), allowed_origin.as_deref());
+                                            }
+                                            Err(e @ (DocGenError::Throttled(_) | DocGenError::CircuitOpen(_))) => {
+                                                warn!(error = %e, "document generator unavailable");
+                                                return helpers::response_helpers::status_message_response(StatusCode::SERVICE_UNAVAILABLE, &e.to_string(), allowed_origin.as_deref());
+                                            }
+                                            Err(e) => return Err(lambda_runtime::Error::from(e))
+                                        };
+
+                                        // Respond to the calling api with the presigned url, or a url per requested format, on successful completion.
+
+                                        let response = helpers::response_helpers::cors_response_builder(StatusCode::OK, allowed_origin.as_deref())
+                                            .body(json!({
+                                                "payload": payload
+                                            }).to_string())
+                                            .map_err(Box::new)?;
+                
+                                        Ok(response)
+                                    }
+                                    None => {
+                                        let empty_payload_received_response = helpers::response_helpers::message_response("Empty payload request received");
+        
+                                        empty_payload_received_response
+                                    }
+                                }
+                            }
+                            Err(error) => {
+
+                                match error {
+                                    PayloadError::Json(error) => {
+
+                                        let json_error_clasification = match error.classify() {
+                                            Category::Io => "Io".to_string(),
+                                            Category::Syntax => "Syntax".to_string(),
+                                            Category::Data => "Data".to_string(),
+                                            Category::Eof => "Eof".to_string()
+                                        };
+
+                                        let response = helpers::response_helpers::cors_response_builder(StatusCode::OK, allowed_origin.as_deref())
+                                            .body(
+                                                format!(
+                                                    "Json deserializing error category: {}, at line {} and column {}.", 
+                                                    json_error_clasification, 
+                                                    error.line().to_string(),
+                                                    error.column().to_string()
+                                                )
+                                            )
+                                            .map_err(Box::new)?;
+
+                                        Ok(response)
+                                    }
+                                    PayloadError::WwwFormUrlEncoded(error) => {
+                                        
+                                        let response = helpers::response_helpers::cors_response_builder(StatusCode::OK, allowed_origin.as_deref())
+                                            .body(error.to_string())
+                                            .map_err(Box::new)?;
+                    
+                                        Ok(response)
+                                    }
+                                }
+                                
+                                
+                            }
+                        }
+                    }
+                }
+            }
+        // }
+        &Method::GET => {
+
+            // The request schema describes the public contract, so it is served without authentication
+
+            if event.uri().path().trim_end_matches('/').ends_with("/schema") {
+                let response = helpers::response_helpers::cors_response_builder_with_content_type(StatusCode::OK, "application/schema+json", allowed_origin.as_deref())
+                    .body(DataTransferObject::json_schema().to_string())
+                    .map_err(Box::new)?;
+
+                return Ok(response);
+            }
+
+            let Some((_, job_id)) = event.uri().path().trim_end_matches('/').rsplit_once("/jobs/") else {
+                return helpers::response_helpers::status_message_response(StatusCode::NOT_FOUND, "Not found", allowed_origin.as_deref());
+            };
+
+            let caller = match AuthenticatedCaller::from_request(&event) {
+                Ok(caller) => caller,
+                Err(auth_error) => {
+                    warn!(error = %auth_error, "rejected unauthenticated request");
+                    return helpers::response_helpers::status_message_response(StatusCode::UNAUTHORIZED, &auth_error.to_string(), allowed_origin.as_deref());
+                }
+            };
+
+            let job = match job_repo.find_one_job(FindGenerationJob { id: job_id.to_string() }).await {
+                Ok(job) => job,
+                Err(RepoSelectError::NotFound) => {
+                    return helpers::response_helpers::status_message_response(StatusCode::NOT_FOUND, &format!("Job {} not found", job_id), allowed_origin.as_deref());
+                }
+                Err(RepoSelectError::Unknown(e)) => return Err(lambda_runtime::Error::from(e))
+            };
+
+            // Jobs are visible to the same callers who could have submitted the report
+
+            if let Err(auth_error) = caller.authorise_adviser(job.adviser()) {
+                warn!(error = %auth_error, caller_last_name = %caller.last_name, "rejected job status request for another adviser");
+                return helpers::response_helpers::status_message_response(StatusCode::FORBIDDEN, &auth_error.to_string(), allowed_origin.as_deref());
+            }
+
+            let mut payload = json!(job.status());
+            payload["jobId"] = json!(job.id());
+
+            let response = helpers::response_helpers::cors_response_builder(StatusCode::OK, allowed_origin.as_deref())
+                .body(json!({
+                    "payload": payload
+                }).to_string())
+                .map_err(Box::new)?;
+
+            Ok(response)
+        }
+        _ => {
+            
+            let no_method_context_received_response = helpers::response_helpers::message_response("No method context received by lambda function");
+
+            no_method_context_received_response
+        }
+    }
+    
+}
+
+
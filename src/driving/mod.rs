@@ -1 +1,5 @@
 pub mod data_transfer_object;
+pub mod auth;
+pub mod cors;
+pub mod handler;
+pub mod server;
@@ -0,0 +1,207 @@
+// driving/server.rs
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use http::header::HeaderName;
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper_util::rt::TokioIo;
+use lambda_http::aws_lambda_events::apigw::ApiGatewayProxyRequestContext;
+use lambda_http::request::RequestContext;
+use lambda_http::{http::StatusCode, Body, RequestExt};
+use serde_json::Value;
+use tokio::net::TcpListener;
+use tracing::{error, info, warn};
+
+use crate::domain::generation_job::GenerationJob;
+use crate::domain::report::{adviser_profile::AdviserProfile, investment_holdings::InvestmentPortfolio, report_config::ReportConfig};
+use crate::driven::doc_generator::DocumentGenerator;
+use crate::driven::job_dispatcher::spawned::SpawnedJobDispatcher;
+use crate::driven::job_dispatcher::JobDispatcher;
+use crate::driven::repository::{AdviserRepository, GenerationJobRepository, InvestmentPortfoliosRepository};
+use crate::helpers::response_helpers::status_message_response;
+
+use super::cors::CorsConfig;
+use super::handler::function_handler;
+
+/// Names the request header holding the caller's claims.
+pub const AUTH_CLAIMS_HEADER_ENV: &str = "AUTH_CLAIMS_HEADER";
+
+pub const DEFAULT_AUTH_CLAIMS_HEADER: &str = "x-authenticated-claims";
+
+/// Serves the API routes over plain HTTP for deployments without the Lambda runtime.
+///
+/// There is no API Gateway authorizer in front of the server, so the caller's claims are read
+/// as a JSON object from `claims_header`. The authenticating proxy in front of the server must
+/// set that header and remove any copy sent by the client. Background jobs are generated on
+/// tasks in the server's own process.
+pub struct Server<R, A, J> {
+    pub investment_portfolio_repo: Arc<R>,
+    pub adviser_repo: Arc<A>,
+    pub job_repo: Arc<J>,
+    pub doc_gen: Arc<dyn DocumentGenerator + Send + Sync>,
+    pub report_config: Arc<ReportConfig>,
+    pub cors_config: Arc<CorsConfig>,
+    pub claims_header: HeaderName,
+}
+
+impl<R, A, J> Server<R, A, J>
+where
+    R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Send + Sync + 'static + std::fmt::Debug,
+    A: AdviserRepository<AdviserProfile> + Send + Sync + 'static + std::fmt::Debug,
+    J: GenerationJobRepository<GenerationJob> + Send + Sync + 'static + std::fmt::Debug,
+{
+    /// Accepts connections until the listener fails.
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> std::io::Result<()> {
+        info!(address = ?listener.local_addr()?, "Serving requests");
+
+        loop {
+            let (stream, remote) = listener.accept().await?;
+            let server = self.clone();
+
+            tokio::spawn(async move {
+                let service = service_fn(move |request| {
+                    let server = server.clone();
+                    async move { Ok::<_, Infallible>(server.handle(request).await) }
+                });
+
+                if let Err(e) = http1::Builder::new().serve_connection(TokioIo::new(stream), service).await {
+                    warn!(error = %e, remote = %remote, "Connection closed with an error");
+                }
+            });
+        }
+    }
+
+    async fn handle(&self, request: hyper::Request<Incoming>) -> hyper::Response<Full<Bytes>> {
+        let job_dispatcher: Arc<dyn JobDispatcher + Send + Sync> = Arc::new(SpawnedJobDispatcher::new(self.doc_gen.clone(), self.job_repo.clone()));
+        let response = match self.lambda_request(request).await {
+            Ok(request) => function_handler(
+                request,
+                self.investment_portfolio_repo.clone(),
+                self.adviser_repo.clone(),
+                self.cors_config.clone(),
+                self.report_config.clone(),
+                self.doc_gen.clone(),
+                self.job_repo.clone(),
+                job_dispatcher
+            ).await,
+            Err(e) => status_message_response(StatusCode::BAD_REQUEST, &format!("Request body could not be read: {}", e), None)
+        };
+
+        // The Lambda runtime reports handler errors to API Gateway, which responds with a 502
+        let response = response.unwrap_or_else(|e| {
+            error!(error = %e, "Request failed");
+            status_message_response(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error", None)
+                .expect("a static response is valid")
+        });
+
+        response.map(|body| Full::new(Bytes::from(body)))
+    }
+
+    /// Builds the request the handler would receive from API Gateway.
+    async fn lambda_request(&self, request: hyper::Request<Incoming>) -> Result<lambda_http::Request, hyper::Error> {
+        let (parts, body) = request.into_parts();
+        let bytes = body.collect().await?.to_bytes();
+        let body = match String::from_utf8(bytes.to_vec()) {
+            Ok(text) if text.is_empty() => Body::Empty,
+            Ok(text) => Body::Text(text),
+            Err(e) => Body::Binary(e.into_bytes())
+        };
+
+        let mut context = ApiGatewayProxyRequestContext::default();
+        context.authorizer.fields = self.claims(&parts.headers);
+
+        Ok(lambda_http::Request::from_parts(parts, body).with_request_context(RequestContext::ApiGatewayV1(context)))
+    }
+
+    fn claims(&self, headers: &http::HeaderMap) -> HashMap<String, Value> {
+        let Some(header) = headers.get(&self.claims_header) else {
+            return HashMap::new();
+        };
+
+        header
+            .to_str()
+            .ok()
+            .and_then(|claims| serde_json::from_str(claims).ok())
+            .unwrap_or_else(|| {
+                warn!(header = %self.claims_header, "Ignored claims header that is not a JSON object");
+                HashMap::new()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driven::config::parse_firm_branding;
+    use crate::driven::doc_generator::local::LocalDocGenerator;
+    use crate::driven::repository::in_memory::{InMemoryAdviserRepo, InMemoryGenerationJobRepo, InMemoryInvestmentPortfolioRepo};
+    use crate::driven::storage::{filesystem::FilesystemStorage, ObjectNaming};
+    use crate::driving::data_transfer_object::report_type_data_transfer_object::adviser_data_transfer_object::AdviserDataTransferObject;
+
+    async fn start(job_repo: Arc<InMemoryGenerationJobRepo>) -> String {
+        let server = Arc::new(Server {
+            investment_portfolio_repo: Arc::new(InMemoryInvestmentPortfolioRepo::default()),
+            adviser_repo: Arc::new(InMemoryAdviserRepo::default()),
+            job_repo,
+            doc_gen: Arc::new(LocalDocGenerator::new(
+                FilesystemStorage::new(std::env::temp_dir().join("gcwm-server-test")),
+                ObjectNaming::default(),
+                std::time::Duration::from_secs(60)
+            )),
+            report_config: Arc::new(ReportConfig {
+                branding: parse_firm_branding(include_str!("../../config/branding.example.json")).unwrap(),
+                contents: Default::default(),
+                templates: Default::default(),
+                tax_rules: Default::default(),
+                projection_assumptions: Default::default()
+            }),
+            cors_config: Arc::new(CorsConfig::default()),
+            claims_header: HeaderName::from_static(DEFAULT_AUTH_CLAIMS_HEADER),
+        });
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(server.serve(listener));
+        format!("http://{}", address)
+    }
+
+    #[tokio::test]
+    async fn test_serves_schema() {
+        let base = start(Arc::new(InMemoryGenerationJobRepo::default())).await;
+
+        let response = reqwest::get(format!("{}/schema", base)).await.unwrap();
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["content-type"], "application/schema+json");
+        assert!(serde_json::from_str::<Value>(&response.text().await.unwrap()).unwrap()["$defs"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_job_status_uses_claims_header() {
+        let job_repo = Arc::new(InMemoryGenerationJobRepo::default());
+        let job = job_repo.create_job(GenerationJob::new(AdviserDataTransferObject {
+            id: None,
+            adviser_first_name: "Alex".to_string(),
+            adviser_last_name: "Jones".to_string()
+        }, Value::Null, None)).await.unwrap();
+        let base = start(job_repo).await;
+        let url = format!("{}/jobs/{}", base, job.id());
+        let client = reqwest::Client::new();
+
+        let anonymous = client.get(&url).send().await.unwrap();
+        assert_eq!(anonymous.status(), 401);
+
+        let adviser = client
+            .get(&url)
+            .header(DEFAULT_AUTH_CLAIMS_HEADER, r#"{"given_name":"Alex","family_name":"Jones"}"#)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(adviser.status(), 200);
+        assert_eq!(serde_json::from_str::<Value>(&adviser.text().await.unwrap()).unwrap()["payload"]["status"], "pending");
+    }
+}
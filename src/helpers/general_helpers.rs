@@ -1,5 +1,18 @@
+use crate::domain::constrained_types::constrained_money_amount_large::ConstrainedMoneyAmountLarge;
 use crate::domain::report::objectives::{CoupleObjectivesAnnualReview, ObjectiveType, ChangeInObjectives};
 
+/// Formats an amount as pounds and pence, such as "£30,000.00".
+pub fn money(amount: f64) -> String {
+    ConstrainedMoneyAmountLarge::try_from(amount)
+        .map(|amount| amount.to_string())
+        .unwrap_or_else(|_| format!("£{:.2}", amount))
+}
+
+/// Formats a probability as a whole percentage, such as "87%".
+pub fn probability(fraction: f64) -> String {
+    format!("{:.0}%", fraction * 100.0)
+}
+
 /// Struct to hold the extracted objectives for each party.
 #[derive(Debug)]
 pub struct ExtractedObjectives {
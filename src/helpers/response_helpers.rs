@@ -1,4 +1,6 @@
-use http::{Response, StatusCode};
+
+
+use http::{response::Builder, Response, StatusCode};
 use lambda_http::Error;
 use serde_json::json;
 
@@ -17,3 +19,40 @@ pub fn message_response(message: &str) -> Result<Response<String>, Error> {
     Ok(response)
 
 }
+
+/// Starts a JSON response carrying the CORS headers. `Access-Control-Allow-Origin` is only
+/// set when the request origin is on the configured allow-list.
+pub fn cors_response_builder(status: StatusCode, allowed_origin: Option<&str>) -> Builder {
+
+    cors_response_builder_with_content_type(status, "application/json", allowed_origin)
+
+}
+
+/// As `cors_response_builder`, for responses that are not JSON.
+pub fn cors_response_builder_with_content_type(status: StatusCode, content_type: &str, allowed_origin: Option<&str>) -> Builder {
+
+    let builder = Response::builder()
+                    .status(status)
+                    .header("Content-Type", content_type)
+                    .header("Access-Control-Allow-Headers", "*")
+                    .header("Access-Control-Allow-Methods", "OPTIONS,POST,GET")
+                    .header("Vary", "Origin");
+
+    match allowed_origin {
+        Some(origin) => builder.header("Access-Control-Allow-Origin", origin),
+        None => builder
+    }
+
+}
+
+pub fn status_message_response(status: StatusCode, message: &str, allowed_origin: Option<&str>) -> Result<Response<String>, Error> {
+
+    let response = cors_response_builder(status, allowed_origin)
+                    .body(json!({
+                        "message": message,
+                    }).to_string())
+                    .map_err(Box::new)?;
+
+    Ok(response)
+
+}
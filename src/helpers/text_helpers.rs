@@ -5,10 +5,11 @@ use crate::domain::report::background_section::{
 };
 use crate::domain::constrained_types::meeting_date::MeetingDate;
 use crate::domain::report::background_section::RelationshipToClient;
+use crate::domain::report::wording_templates::{TemplateName, WordingTemplates};
 
 pub fn create_background_text<T>(
     dto: T,
-    base_greeting: &str, // Base greeting for all cases
+    templates: &WordingTemplates,
 ) -> Result<String, ReportError>
 where
     T: BackgroundSectionDtoTrait,
@@ -51,26 +52,27 @@ where
     let additional_attendees = additional_attendees?;
     let additional_company_attendees = additional_company_attendees?;
 
-    // Determine the greeting and location text
+    // Determine the location text
     let location_text = match meeting_location {
-        MeetingLocation::Teams => "our virtual Teams meeting".to_string(),
-        MeetingLocation::Home(home) => format!("your home in {}", home.town),
-        MeetingLocation::Office => "the office".to_string(),
-        MeetingLocation::OtherLocation(other) => format!("{}", other.other_location),
+        MeetingLocation::Teams => templates.render(TemplateName::BackgroundLocationTeams, &[]),
+        MeetingLocation::Home(home) => templates.render(TemplateName::BackgroundLocationHome, &[("town", &home.town.to_string())]),
+        MeetingLocation::Office => templates.render(TemplateName::BackgroundLocationOffice, &[]),
+        MeetingLocation::OtherLocation(other) => other.other_location.to_string(),
     };
 
-    // Build greeting text dynamically
-    let mut greeting_text = base_greeting.to_string();
-
-    if !additional_company_attendees.is_empty() {
+    let company_attendees_text = if additional_company_attendees.is_empty() {
+        String::new()
+    } else {
         let company_names: Vec<String> = additional_company_attendees
             .iter()
             .map(|a| format!("{} {}", a.first_name, a.last_name))
             .collect();
-        greeting_text.push_str(&format!(" with my colleagues {}", company_names.join(", ")));
-    }
+        templates.render(TemplateName::BackgroundCompanyAttendees, &[("names", &company_names.join(", "))])
+    };
 
-    if !additional_attendees.is_empty() {
+    let client_attendees_text = if additional_attendees.is_empty() {
+        String::new()
+    } else {
         let attendee_details: Vec<String> = additional_attendees
             .iter()
             .map(|a| match &a.relationship_to_client {
@@ -86,16 +88,19 @@ where
                 ),
             })
             .collect();
-        greeting_text.push_str(&format!(" and {}", attendee_details.join(", ")));
-    }
-
-    let mut background_text = format!("{} at {}", greeting_text, location_text);
+        templates.render(TemplateName::BackgroundClientAttendees, &[("names", &attendee_details.join(", "))])
+    };
 
-    // Add meeting date and conclusion
-    background_text.push_str(&format!(
-        " on the {} for our regular annual review meeting. In the meeting, we discussed your current financial position, objectives, and invested capital that you own.\nThe remainder of this report will address a full review of your existing products in line with your financial objectives.",
-        meeting_date
-    ));
+    let background_text = templates.render(
+        TemplateName::BackgroundParagraph,
+        &[
+            ("greeting", &templates.render(TemplateName::BackgroundGreeting, &[])),
+            ("company_attendees", &company_attendees_text),
+            ("client_attendees", &client_attendees_text),
+            ("location", &location_text),
+            ("meeting_date", &meeting_date),
+        ],
+    );
 
     Ok(background_text)
 }
@@ -0,0 +1,4 @@
+pub mod domain;
+pub mod driven;
+pub mod driving;
+pub mod helpers;
@@ -1,20 +1,19 @@
-use domain::report::investment_holdings::InvestmentPortfolio;
-use driven::repository::{dynamo_db::{self, InvestmentPortfolioDynamoDbRepo}, InvestmentPortfoliosRepository};
-use driving::data_transfer_object::{self, DataTransferObject};
-use lambda_http::{ext::PayloadError, http::{Response, StatusCode}, lambda_runtime, run, service_fn, Error, IntoResponse, Request, RequestExt, RequestPayloadExt};
-use tracing::{info, warn, error, instrument};
+use driven::repository::{adviser_dynamo_db::AdviserDynamoDbRepo, dynamo_db::InvestmentPortfolioDynamoDbRepo};
+use lambda_http::{request::LambdaRequest, service_fn, Adapter, Error, LambdaEvent, Request, Service};
+use serde_json::Value;
 use tracing_subscriber::{fmt, EnvFilter};
-use serde_json::{error::Category, json};
-use http::Method;
 use dotenv::dotenv;
 use std::sync::Arc;
 
-use crate::driven::doc_generator::{lambda::AwsLambdaDocGenerator, DocumentGenerator};
+use gcwm_report_gen::driven::config::{load_report_config, load_stored_wording_templates, WORDING_TEMPLATES_VERSION_ENV};
+use gcwm_report_gen::driven::repository::wording_templates_dynamo_db::WordingTemplatesDynamoDbRepo;
+use gcwm_report_gen::domain::generation_job::run_generation_job;
+use gcwm_report_gen::driven::doc_generator::document_generator_from_env;
+use gcwm_report_gen::driven::job_dispatcher::lambda::{GenerationJobEvent, LambdaJobDispatcher};
+use gcwm_report_gen::driven::repository::generation_job_dynamo_db::GenerationJobDynamoDbRepo;
+use gcwm_report_gen::driving::{cors::CorsConfig, handler::function_handler};
 
-mod domain;
-mod driven;
-mod driving;
-mod helpers;
+use gcwm_report_gen::driven;
 
 
 #[tokio::main]
@@ -30,202 +29,42 @@ async fn main() -> Result<(), Error> {
 
     let dynamo_db_repo = InvestmentPortfolioDynamoDbRepo::new().await;
     let dynamo_db_repo = Arc::new(dynamo_db_repo);
-
-    run(service_fn( move |request: Request| {
-        let dynamo_db_repo = dynamo_db_repo.clone();
-        async move { function_handler(request, dynamo_db_repo).await }
-    }))
-    .await
-}
-
-#[instrument(skip(event))]
-pub async fn function_handler<R>(
-    event: Request, 
-    investment_portfolio_repo: Arc<R>
-) -> Result<impl IntoResponse, Error> 
-where 
-    R: InvestmentPortfoliosRepository<InvestmentPortfolio> + Send + Sync + 'static + std::fmt::Debug,
-{
-
-    info!(method = %event.method(), path = %event.uri(), "received request");
-
-    let method = event.method();
-    let path_parameters = event.path_parameters();
-
-    enum PayloadType {
-        Test(DataTransferObject),
-        Production(Result<Option<DataTransferObject>, PayloadError>)
+    let adviser_repo = Arc::new(AdviserDynamoDbRepo::new().await);
+    let cors_config = Arc::new(CorsConfig::from_env());
+    let mut report_config = load_report_config()?;
+    if let Ok(version) = std::env::var(WORDING_TEMPLATES_VERSION_ENV) {
+        report_config.templates = load_stored_wording_templates(&WordingTemplatesDynamoDbRepo::new().await, version).await?;
     }
+    let report_config = Arc::new(report_config);
+    let doc_gen = document_generator_from_env().await?;
+    let job_repo = Arc::new(GenerationJobDynamoDbRepo::new().await);
 
-    // let payload = if path_parameters.first("proxy") == Some("test") {
-    //     PayloadType::Test(helpers::test_helpers::create_mock_data_transfer_object())
-    // } else {
-    //     match event.payload::<driving::data_transfer_object::DataTransferObject>() {
-    //         Ok(payload) => {
-    //             info!(payload = ?payload, "deserialized request payload");
-    //             PayloadType::Production(Ok(payload))
-    //         }
-    //         Err(payload_error) => {
-    //             error!(error = ?payload_error, "failed to deserialize request");
-    //             PayloadType::Production(Err(payload_error))
-    //         }
-    //     }
-    // };
-
-    let payload = match event.payload::<driving::data_transfer_object::DataTransferObject>() {
-        Ok(payload) => {
-            info!(payload = ?payload, "deserialized request payload");
-            PayloadType::Production(Ok(payload))
-        }
-        Err(payload_error) => {
-            error!(error = ?payload_error, "failed to deserialize request");
-            PayloadType::Production(Err(payload_error))
-        }
-    };
-
-    //println!("Payload: {:?}", payload);
-
-    match method {
-        &Method::POST => {
-            // if !path_parameters.is_empty() {
-                
-            //     let unexpected_additional_parameters_response = helpers::response_helpers::message_response("Unexpected additional parameters included");
-
-            //     unexpected_additional_parameters_response
-
-            // } else {
-
-                match payload {
-                    PayloadType::Test(data_transfer_object) => {
-            
-                        let report = domain::report::create_report::create_report(data_transfer_object.report_type, investment_portfolio_repo).await?;
+    let job_dispatcher = Arc::new(LambdaJobDispatcher::new(std::env::var("AWS_LAMBDA_FUNCTION_NAME")?).await);
 
-                        //"REPLACE * WITH DOMAIN FOR SECURITY IN CORS");
-
-                        let response = Response::builder()
-                            .status(StatusCode::OK)
-                            .header("Content-Type", "application/json")
-                            .header("Access-Control-Allow-Origin", "*")
-                            .header("Access-Control-Allow-Headers", "*")
-                            .header("Access-Control-Allow-Methods", "OPTIONS,POST,GET")
-                            .body(json!({
-                                "payload": report
-                            }).to_string())
-                            .map_err(Box::new)?;
-
-                        Ok(response)
-
-                    }
-                    PayloadType::Production(payload) => {
-                        match payload {
-                            Ok(data_transfer_object) => {
-                                match data_transfer_object {
-                                    Some(data_transfer_object) => {
-                                        
-                                        // Create report text, tables and apply domain logic ready to be sent to a document generator resposible for
-                                        // creating the the document, applying formatting and presentation etc 
-
-                                        let document_instructions = serde_json::to_value(
-                                            domain::report::create_report::create_report(data_transfer_object.report_type, investment_portfolio_repo).await?
-                                        ).map_err(|e| {
-                                            e
-                                        })?;
-        
-                                        // Call document generator here.  On successully completion a presigned url for downloading the document
-                                        // will be included in the response
-                                        
-                                        let doc_gen = AwsLambdaDocGenerator::new("docx_generator").await;
-                                        let download_url = doc_gen
-                                            .generate(&document_instructions)
-                                            .await
-                                            .map_err(|e| {
-                                                lambda_runtime::Error::from(e)
-                                            })?;
-
-                                        // Respond to the calling api with the presigned url on successful completion.
-
-                                        //"REPLACE * WITH DOMAIN FOR SECURITY IN CORS"
-
-                                        let response = Response::builder()
-                                            .status(StatusCode::OK)
-                                            .header("Content-Type", "application/json")
-                                            .header("Access-Control-Allow-Origin", "*")
-                                            .header("Access-Control-Allow-Headers", "*")
-                                            .header("Access-Control-Allow-Methods", "OPTIONS,POST,GET")
-                                            .body(json!({
-                                                "payload": download_url
-                                            }).to_string())
-                                            .map_err(Box::new)?;
-                
-                                        Ok(response)
-                                    }
-                                    None => {
-                                        let empty_payload_received_response = helpers::response_helpers::message_response("Empty payload request received");
-        
-                                        empty_payload_received_response
-                                    }
-                                }
-                            }
-                            Err(error) => {
-
-                                match error {
-                                    PayloadError::Json(error) => {
-
-                                        let json_error_clasification = match error.classify() {
-                                            Category::Io => "Io".to_string(),
-                                            Category::Syntax => "Syntax".to_string(),
-                                            Category::Data => "Data".to_string(),
-                                            Category::Eof => "Eof".to_string()
-                                        };
-
-                                        let response = Response::builder()
-                                            .status(StatusCode::OK)
-                                            .header("Content-Type", "application/json")
-                                            .header("Access-Control-Allow-Origin", "*")
-                                            .header("Access-Control-Allow-Headers", "*")
-                                            .header("Access-Control-Allow-Methods", "OPTIONS,POST,GET")
-                                            .body(
-                                                format!(
-                                                    "Json deserializing error category: {}, at line {} and column {}.", 
-                                                    json_error_clasification, 
-                                                    error.line().to_string(),
-                                                    error.column().to_string()
-                                                )
-                                            )
-                                            .map_err(Box::new)?;
-
-                                        Ok(response)
-                                    }
-                                    PayloadError::WwwFormUrlEncoded(error) => {
-                                        
-                                        let response = Response::builder()
-                                            .status(StatusCode::OK)
-                                            .header("Content-Type", "application/json")
-                                            .header("Access-Control-Allow-Origin", "*")
-                                            .header("Access-Control-Allow-Headers", "*")
-                                            .header("Access-Control-Allow-Methods", "OPTIONS,POST,GET")
-                                            .body(error.to_string())
-                                            .map_err(Box::new)?;
-                    
-                                        Ok(response)
-                                    }
-                                }
-                                
-                                
-                            }
-                        }
-                    }
-                }
+    // The function receives API Gateway requests and the generation jobs it hands to itself,
+    // so events are routed by shape before the HTTP adapter sees them.
+    lambda_http::lambda_runtime::run(service_fn(move |event: LambdaEvent<Value>| {
+        let dynamo_db_repo = dynamo_db_repo.clone();
+        let adviser_repo = adviser_repo.clone();
+        let cors_config = cors_config.clone();
+        let report_config = report_config.clone();
+        let doc_gen = doc_gen.clone();
+        let job_repo = job_repo.clone();
+        let job_dispatcher = job_dispatcher.clone();
+        async move {
+            let (payload, context) = event.into_parts();
+            if let Ok(job_event) = serde_json::from_value::<GenerationJobEvent>(payload.clone()) {
+                run_generation_job(job_event.generation_job_id, doc_gen, job_repo).await;
+                return Ok::<Value, Error>(Value::Null);
             }
-        // }
-        _ => {
-            
-            let no_method_context_received_response = helpers::response_helpers::message_response("No method context received by lambda function");
 
-            no_method_context_received_response
+            let request: LambdaRequest = serde_json::from_value(payload)?;
+            let mut adapter = Adapter::from(service_fn(move |request: Request| {
+                function_handler(request, dynamo_db_repo.clone(), adviser_repo.clone(), cors_config.clone(), report_config.clone(), doc_gen.clone(), job_repo.clone(), job_dispatcher.clone())
+            }));
+            let response = adapter.call(LambdaEvent::new(request, context)).await?;
+            Ok(serde_json::to_value(response)?)
         }
-    }
-    
+    }))
+    .await
 }
-
-